@@ -102,7 +102,7 @@ impl Board {
                         );
                         return (
                             Board {
-                                state: state.clone(),
+                                state: *state,
                                 unused_nums: self.unused_nums.clone(),
                             },
                             &DO_NOTHING,
@@ -145,7 +145,7 @@ impl Board {
                             println!("Impossible state reached during board.simplify(): corners case.\n{:?}", state);
                             return (
                                 Board {
-                                    state: state.clone(),
+                                    state: *state,
                                     unused_nums: self.unused_nums.clone(),
                                 },
                                 &DO_NOTHING,
@@ -226,7 +226,7 @@ pub fn compute_best_uncover(
 
     // This is used to know whether we have to compute lines next or not.
     let n = board.state.iter().filter(|&x| x != &255).count();
-    let state_clone = board.state.clone();
+    let state_clone = board.state;
     let empty_indices = state_clone
         .iter()
         .enumerate()
@@ -376,7 +376,7 @@ fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     let mut board = Board {
         state: [255, 255, 255, 255, 255, 255, 255, 255, 255],
-        unused_nums: (0..9).into_iter().collect(),
+        unused_nums: (0..9).collect(),
     };
     let now = Instant::now();
     // This puts all of the things it computes into the dictionary and returns something that needs a little more computation.
@@ -397,9 +397,9 @@ fn main() {
     let path = Path::new(&env::var("OUT_DIR").unwrap()).join("codegen.rs");
     // println!("cargo:warning={}", path.to_str().unwrap());
     let mut file = BufWriter::new(File::create(&path).unwrap());
-    write!(
+    writeln!(
         &mut file,
-        "static PRECOMPUTED_BOARDS: phf::Map<u32, (usize, [u32; 16])> = {};\n",
+        "static PRECOMPUTED_BOARDS: phf::Map<u32, (usize, [u32; 16])> = {};",
         phf_map.build()
     )
     .unwrap();
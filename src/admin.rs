@@ -0,0 +1,129 @@
+use crate::{logging, minicact};
+use crate::settings::reload_settings;
+use crate::commands::{command_locale, send_interaction_response_message, Permission};
+use crate::locale::{describe_command, describe_option, tr, trf, Locale};
+use crate::registry::SlashCommand;
+
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::InteractionResponseType;
+use serenity::model::application::interaction::application_command::{ApplicationCommandInteraction, CommandDataOption, CommandDataOptionValue};
+use serenity::model::prelude::Activity;
+use serenity::model::user::OnlineStatus;
+use serenity::prelude::*;
+
+use lazy_static::lazy_static;
+
+// /admin, for keeping an eye on the bot without restarting it. Bot admins only.
+// /shutdown is still its own command, since it's the one people go looking for.
+
+const DEFAULT_PRESENCE: &str = "using /minicact_play";
+
+// Discord shows this as "Playing ...".
+lazy_static! { static ref PRESENCE: Mutex<String> = Mutex::new(DEFAULT_PRESENCE.to_string()); }
+
+// pretty awesome that this doesn't return errors.
+pub async fn update_presence(ctx: &Context) {
+    let presence = PRESENCE.lock().await.clone();
+    ctx.set_presence(Some(Activity::playing(presence)), OnlineStatus::Online).await;
+}
+
+pub const COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        name: "admin",
+        permission: Permission::BotOwner,
+        create: |command| {
+            describe_command(command, "cmd.admin")
+                .create_option(|option| {
+                    describe_option(option.name("games"), "opt.admin.games")
+                        .kind(CommandOptionType::SubCommand)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("expire"), "opt.admin.expire")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|option| {
+                            describe_option(option.name("user"), "opt.admin.expire.user")
+                                .kind(CommandOptionType::User)
+                                .required(true)
+                        })
+                })
+                .create_option(|option| {
+                    describe_option(option.name("reload"), "opt.admin.reload")
+                        .kind(CommandOptionType::SubCommand)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("presence"), "opt.admin.presence")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|option| {
+                            describe_option(option.name("text"), "opt.admin.presence.text")
+                                .kind(CommandOptionType::String)
+                        })
+                })
+                .create_option(|option| {
+                    describe_option(option.name("selfcheck"), "opt.admin.selfcheck")
+                        .kind(CommandOptionType::SubCommand)
+                })
+        },
+        handler: |ctx, command| Box::pin(admin_command(ctx, command)),
+    },
+];
+
+async fn admin_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    // Discord always sends exactly one subcommand, with its own options inside it.
+    let subcommand = command.data.options.first().ok_or(SerenityError::Other("Admin command without a subcommand??"))?;
+    info!("Admin {} from user {}", subcommand.name, logging::user(&command.user));
+    let locale = command_locale(&command).await;
+    match subcommand.name.as_str() {
+        "games" => send_interaction_response_message(&ctx, &command, minicact::admin::describe_active_games(locale).await, true).await,
+        "expire" => {
+            let content = match get_sub_option(subcommand, "user") {
+                Some(CommandDataOptionValue::User(user, _)) => match minicact::admin::expire_game(user).await {
+                    Some(mode) => trf(locale, "admin.expired", &[&user.mention(), &tr(locale, format!("mode.{}", mode.name()).as_str())]),
+                    None => trf(locale, "admin.no_game", &[&user.mention()]),
+                },
+                _ => tr(locale, "admin.choose_user").to_string()
+            };
+            send_interaction_response_message(&ctx, &command, content, true).await
+        },
+        // Also happens by itself whenever the file changes, this is for when that doesn't work (like the file being on a network drive).
+        "reload" => {
+            let content = match reload_settings() {
+                Ok(result) => result.message(locale),
+                Err(why) => {
+                    warn!("{}", why.message(Locale::English));
+                    why.message(locale)
+                }
+            };
+            send_interaction_response_message(&ctx, &command, content, true).await
+        },
+        "presence" => {
+            // Discord cuts activities off at 128 characters anyways.
+            let presence = match get_sub_option(subcommand, "text") {
+                Some(CommandDataOptionValue::String(text)) if !text.trim().is_empty() => text.trim().chars().take(128).collect(),
+                _ => DEFAULT_PRESENCE.to_string()
+            };
+            *PRESENCE.lock().await = presence.clone();
+            update_presence(&ctx).await;
+            let content = trf(locale, "admin.presence", &[&presence]);
+            send_interaction_response_message(&ctx, &command, content, true).await
+        },
+        "selfcheck" => {
+            // Plays every possible ticket, which is longer than Discord will wait for a response.
+            command.create_interaction_response(&ctx.http, |response| {
+                response.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                    .interaction_response_data(|data| data.ephemeral(true))
+            }).await?;
+            let results = minicact::admin::self_check(locale).await;
+            command.edit_original_interaction_response(&ctx.http, |response| {
+                response.content(results.join("\n"))
+            }).await?;
+            Ok(())
+        },
+        _ => send_interaction_response_message(&ctx, &command, tr(locale, "admin.unknown_subcommand"), true).await
+    }
+}
+
+fn get_sub_option<'a>(subcommand: &'a CommandDataOption, name: &str) -> Option<&'a CommandDataOptionValue> {
+    subcommand.options.iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.resolved.as_ref())
+}
@@ -1,23 +1,23 @@
-use crate::ADMIN_USERS;
+use crate::logging;
 use crate::generate_components::*;
-use crate::minicact;
-
-use chrono::Local;
+use crate::guild_config::{guild_config, update_guild_config};
+use crate::locale::*;
+use crate::registry::{all_commands, find_command, SlashCommand};
+use crate::settings::settings;
+use crate::shutdown::{request_shutdown, ShutdownMode};
+use crate::minicact::preferences::{update_preferences, user_preferences};
 
 use std::time::Instant;
 
-use serenity::builder::CreateApplicationCommands;
+use serenity::builder::{CreateApplicationCommand, CreateApplicationCommands};
 use serenity::model::application::interaction::InteractionResponseType;
-use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::application::interaction::application_command::{ApplicationCommandInteraction, CommandDataOptionValue};
+use serenity::model::id::UserId;
+use serenity::model::permissions::Permissions;
 use serenity::model::prelude::component::ButtonStyle;
 use serenity::prelude::*;
 
-// needed for shutdown command
-use lazy_static::lazy_static;
-use tokio::sync::mpsc::Sender;
-
-lazy_static! { pub static ref SHUTDOWN_SENDER: Mutex<Option<Sender<bool>>> = Mutex::new(None); }
-
 // for some reason if you don't specify the return type the compiler doesn't figure it out
 pub async fn send_interaction_response_message<D>(ctx: &Context, command: &ApplicationCommandInteraction, content: D, ephemeral: bool) -> Result<(), SerenityError> where D: ToString {
     command.create_interaction_response(&ctx.http, |response| {
@@ -28,43 +28,191 @@ pub async fn send_interaction_response_message<D>(ctx: &Context, command: &Appli
         .await
 }
 
+// Finds the value of a slash command option by name, if the user gave one.
+pub fn get_option<'a>(command: &'a ApplicationCommandInteraction, name: &str) -> Option<&'a CommandDataOptionValue> {
+    command.data.options.iter()
+        .find(|option| option.name == name)
+        .and_then(|option| option.resolved.as_ref())
+}
+
+// Who is allowed to use a command. Each level includes everyone above it in this list.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Permission {
+    // The `admins` in the config file.
+    BotOwner,
+    // Anyone who can manage the server or has one of its admin roles from /minicact_config.
+    GuildAdmin,
+    Anyone,
+}
+
+// Anything that isn't registered is treated as BotOwner, so nobody else can reach an unfinished command.
+pub fn command_permission(name: &str) -> Permission {
+    find_command(name).map_or(Permission::BotOwner, |command| command.permission)
+}
+
+// Bot admins are the `admins` in the config file, and can use anything anywhere.
+// If nobody is listed, nobody is one.
+pub fn is_bot_admin(user: UserId) -> bool {
+    settings().admins.contains(&user)
+}
+
+// Server admins can change how the bot works in their server: anyone who can manage the server or has one of its admin roles.
+// Bot admins count too.
+pub async fn is_guild_admin(command: &ApplicationCommandInteraction) -> bool {
+    if is_bot_admin(command.user.id) {
+        return true
+    }
+    let (guild, member) = match (command.guild_id, command.member.as_ref()) {
+        (Some(guild), Some(member)) => (guild, member),
+        _ => return false
+    };
+    // Discord includes the member's permissions in this channel with every interaction from a server.
+    if member.permissions.is_some_and(|permissions| permissions.manage_guild()) {
+        return true
+    }
+    let admin_roles = guild_config(guild).await.admin_roles;
+    member.roles.iter().any(|role| admin_roles.contains(role))
+}
+
+pub async fn has_permission(command: &ApplicationCommandInteraction, permission: Permission) -> bool {
+    match permission {
+        Permission::Anyone => true,
+        Permission::GuildAdmin => is_guild_admin(command).await,
+        Permission::BotOwner => is_bot_admin(command.user.id),
+    }
+}
+
+// Sets the name, and who Discord shows the command to by default. Servers can change that in their settings,
+// so this only keeps privileged commands out of everyone else's way. handle_command is what actually checks.
+// Discord doesn't know who the bot admins are, so bot admin commands are only shown to server administrators.
+// A bot admin who isn't one has to be allowed them in the server's Integrations settings.
+fn register_command<'a>(command: &'a mut CreateApplicationCommand, slash_command: &SlashCommand) -> &'a mut CreateApplicationCommand {
+    command.name(slash_command.name);
+    match slash_command.permission {
+        Permission::Anyone => command,
+        Permission::GuildAdmin => command.default_member_permissions(Permissions::MANAGE_GUILD),
+        Permission::BotOwner => command.default_member_permissions(Permissions::ADMINISTRATOR),
+    }
+}
+
+pub async fn no_permission_response(ctx: &Context, command: &ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    send_interaction_response_message(ctx, command, tr(command_locale(command).await, "no_permission"), true).await
+}
+
+// Every command in the registry, the way Discord wants them.
 pub fn create_commands(commands: &mut CreateApplicationCommands) -> &mut CreateApplicationCommands {
-    // DON'T FORGET to add your custom commands here!!
+    for slash_command in all_commands() {
+        commands.create_application_command(|command| (slash_command.create)(register_command(command, slash_command)));
+    }
     commands
-        .create_application_command(|command| {
-            command.name("help").description("Information on how to use the bot")
-        })
-        .create_application_command(|command| {
-            command.name("ping").description("A ping command")
-        })
-        .create_application_command(|command| {
-            command.name("shutdown").description("Shut down the bot")
-        });
-    minicact::commands::create_commands(commands)
 }
-// Any custom slash commands must be added both to create_commands ^^^ and to handle_command!!
+
+pub fn command_names() -> Vec<&'static str> {
+    all_commands().map(|command| command.name).collect()
+}
+
+// Custom slash commands go in a COMMANDS list like the one below, see src/registry.rs.
+pub const COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        name: "help",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.help")
+        },
+        handler: |ctx, command| Box::pin(help_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "ping",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.ping")
+        },
+        handler: |ctx, command| Box::pin(ping_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "shutdown",
+        permission: Permission::BotOwner,
+        create: |command| {
+            describe_command(command, "cmd.shutdown")
+                .create_option(|option| {
+                    describe_option(option.name("mode"), "opt.shutdown.mode")
+                        .kind(CommandOptionType::String)
+                        .required(false);
+                    for mode in ShutdownMode::ALL {
+                        add_choice(option, format!("shutdown.{}", mode.name()).as_str(), mode.name());
+                    }
+                    option
+                })
+        },
+        handler: |ctx, command| Box::pin(shutdown_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "language",
+        // Changing the server's language is checked separately, since it's just one option.
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.language")
+                .create_option(|option| {
+                    describe_option(option.name("language"), "opt.language.language")
+                        .kind(CommandOptionType::String)
+                        .required(true);
+                    add_choice(option, "language.automatic", "auto");
+                    for locale in Locale::ALL {
+                        option.add_string_choice(locale.name(), locale.code());
+                    }
+                    option
+                })
+                .create_option(|option| {
+                    describe_option(option.name("server"), "opt.language.server")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+        },
+        handler: |ctx, command| Box::pin(language_command(ctx, command)),
+    },
+];
+
 pub async fn handle_command(ctx: Context, command:ApplicationCommandInteraction) -> Result<(), SerenityError> {
-    // Add any custom commands here
-    match command.data.name.as_str() {
-        "help" => help_command(ctx, command).await,
-        "ping" => ping_command(ctx, command).await,
-        "shutdown" => shutdown_command(ctx, command).await,
-        s if s.starts_with("minicact") => minicact::commands::handle_command(ctx, command).await,
-        _ => nyi_command(ctx, command).await
+    let permission = command_permission(command.data.name.as_str());
+    // Servers can turn commands off with /minicact_config, but not the bot admins' own commands, or a server could lock them out of /shutdown.
+    if let Some(guild) = command.guild_id.filter(|_| permission != Permission::BotOwner) {
+        if !guild_config(guild).await.allows(command.data.name.as_str()) {
+            let locale = command_locale(&command).await;
+            return send_interaction_response_message(&ctx, &command, tr(locale, "config.command_disabled"), true).await
+        }
+    }
+    if !has_permission(&command, permission).await {
+        info!("User {} tried to use {} without permission", logging::user(&command.user), command.data.name);
+        if permission == Permission::GuildAdmin {
+            let locale = command_locale(&command).await;
+            return send_interaction_response_message(&ctx, &command, tr(locale, "config.needs_admin"), true).await
+        }
+        return no_permission_response(&ctx, &command).await
+    }
+    match find_command(command.data.name.as_str()) {
+        Some(slash_command) => (slash_command.handler)(ctx, command).await,
+        None => nyi_command(ctx, command).await
     }
 }
 
+// Which language to answer in. Everything that replies to a user should go through this.
+pub async fn command_locale(command: &ApplicationCommandInteraction) -> Locale {
+    resolve_locale(user_preferences(command.user.id).await.locale, command.guild_id, &command.locale).await
+}
+
 pub async fn nyi_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
-    send_interaction_response_message(&ctx, &command, "This command hasn't been implemented. Try /help", true).await
+    let locale = command_locale(&command).await;
+    send_interaction_response_message(&ctx, &command, tr(locale, "nyi"), true).await
 }
 
 async fn help_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
     // This is very bare-bones, you will want to improve it most likely
+    let locale = command_locale(&command).await;
     command.create_interaction_response(&ctx.http, |response| {
         response.kind(InteractionResponseType::ChannelMessageWithSource)
             .interaction_response_data(|data| {
                 data.ephemeral(true)
-                    .content("Currently available commands: `/ping`, `/shutdown`, `/help`, `/minicact_play`, `/minicact_simulate`.")
+                    .content(tr(locale, "help"))
             })
     }).await
     // for some reason you can't delete ephemeral interaction responses so I guess I'll just suffer
@@ -93,24 +241,57 @@ async fn ping_command(ctx: Context, command: ApplicationCommandInteraction) -> R
 }
 
 async fn shutdown_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
-    // Set your admin user list in your config file
-    let admins = ADMIN_USERS.lock().await;
-    if !admins.is_empty() && !admins.contains(&command.user.id) {
-        send_interaction_response_message(&ctx, &command, "You do not have permission.", true).await?;
-        return Ok(())
-    }
-    println!("{:?}\t Shutdown from user {} with Id {}", Local::now(), command.user.name, command.user.id);
+    // Only bot admins get this far, set your admin user list in your config file
+    let locale = command_locale(&command).await;
+    let mode = match get_option(&command, "mode") {
+        Some(CommandDataOptionValue::String(name)) => ShutdownMode::from_name(name).unwrap_or(ShutdownMode::Immediate),
+        _ => ShutdownMode::Immediate
+    };
+    info!("Shutdown ({}) from user {}", mode.name(), logging::user(&command.user));
+    let content = match mode {
+        ShutdownMode::Immediate => tr(locale, "shutting_down"),
+        ShutdownMode::Drain => tr(locale, "shutdown.draining"),
+        ShutdownMode::Restart => tr(locale, "shutdown.restarting"),
+    };
     // no ? here, we don't want to return early if this fails
-    _ = send_interaction_response_message(&ctx, &command, "Shutting down...", true).await;
-    // loosely based on https://stackoverflow.com/a/65456463
-    // keep the lock separate because you have to (which is neat)
-    let lock = SHUTDOWN_SENDER.lock().await;
-    // This error means that the shutdown channel is somehow not good, so we actually want to panic
-    let sender = lock.as_ref().expect("Shutdown command called before shutdown channel initialized??");
-    // If this errors, the receiver could not receive the message anyways, so we want to panic
-    sender.send(true).await.expect("Shutdown message send error");
-    println!("{:?}\t Passed shutdown message", Local::now());
-    // I'm pretty sure this is unnecessary but it makes me happier than not doing it
-    ctx.shard.shutdown_clean();
+    _ = send_interaction_response_message(&ctx, &command, content, true).await;
+    request_shutdown(mode).await;
     Ok(())
-}
\ No newline at end of file
+}
+
+// Picks a language for yourself, or for the whole server if you're one of its admins.
+// "auto" clears the choice, so it goes back to the server's language or Discord's.
+async fn language_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let choice = match get_option(&command, "language") {
+        Some(CommandDataOptionValue::String(code)) => Locale::from_code(code),
+        _ => return Err(SerenityError::Other("Language command didn't get a language??"))
+    };
+    let server = matches!(get_option(&command, "server"), Some(CommandDataOptionValue::Boolean(true)));
+    if !server {
+        update_preferences(command.user.id, |preferences| preferences.locale = choice).await;
+        info!("User {} set their language to {:?}", logging::user(&command.user), choice);
+        // Answers in the new language, so they can tell it worked.
+        let locale = command_locale(&command).await;
+        let content = match choice {
+            Some(choice) => trf(locale, "language.set_user", &[&choice.name()]),
+            None => tr(locale, "language.reset_user").to_string(),
+        };
+        return send_interaction_response_message(&ctx, &command, content, true).await
+    }
+    let locale = command_locale(&command).await;
+    let guild = match command.guild_id {
+        Some(guild) => guild,
+        None => return send_interaction_response_message(&ctx, &command, tr(locale, "language.guild_only"), true).await
+    };
+    if !is_guild_admin(&command).await {
+        return send_interaction_response_message(&ctx, &command, tr(locale, "config.needs_admin"), true).await
+    }
+    update_guild_config(guild, |config| config.locale = choice).await;
+    info!("User {} set the language of guild {} to {:?}", logging::user(&command.user), guild, choice);
+    let locale = command_locale(&command).await;
+    let content = match choice {
+        Some(choice) => trf(locale, "language.set_guild", &[&choice.name()]),
+        None => tr(locale, "language.reset_guild").to_string(),
+    };
+    send_interaction_response_message(&ctx, &command, content, true).await
+}
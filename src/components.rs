@@ -1,4 +1,4 @@
-use crate::minicact;
+use crate::registry::{find_component, ComponentRoute};
 
 use std::time::Instant;
 
@@ -6,12 +6,18 @@ use serenity::model::prelude::interaction::InteractionResponseType;
 use serenity::model::prelude::interaction::message_component::MessageComponentInteraction;
 use serenity::prelude::*;
 
+// Custom components go in a COMPONENTS list like this one, see src/registry.rs.
+pub const COMPONENTS: &[ComponentRoute] = &[
+    ComponentRoute {
+        prefix: "ping_refresh",
+        handler: |ctx, component| Box::pin(ping_refresh_component(ctx, component)),
+    },
+];
+
 pub async fn handle_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
-    // Add any custom components here
-    match component.data.custom_id.as_str() {
-        s if s.starts_with("minicact_") => minicact::components::handle_component(ctx, component).await,
-        "ping_refresh" => ping_refresh_component(ctx, component).await,
-        _ => nyi_component(ctx, component).await
+    match find_component(component.data.custom_id.as_str()) {
+        Some(route) => (route.handler)(ctx, component).await,
+        None => nyi_component(ctx, component).await
     }
 }
 
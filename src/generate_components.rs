@@ -11,8 +11,8 @@ pub fn make_button<'a, D: ToString>(action_row: &'a mut CreateActionRow, custom_
         }
         if let Some(s) = label {
             button.label(s);
-        } else if let None = emoji {
-            button.label("​"); // Zero-width character. For some reason, Discord's API changed to disallow buttons with a label of " ".
+        } else if emoji.is_none() {
+            button.label("\u{200b}"); // Zero-width character. For some reason, Discord's API changed to disallow buttons with a label of " ".
         }
         button.disabled(disabled);
         button
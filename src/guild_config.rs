@@ -0,0 +1,148 @@
+use crate::locale::Locale;
+use crate::settings::settings;
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serenity::model::id::{ChannelId, GuildId, RoleId};
+use serenity::prelude::*;
+
+use lazy_static::lazy_static;
+
+// Everything a server's admins can change with /minicact_config. Servers that never changed anything just get the default.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GuildConfig {
+    // Where results get announced and the daily puzzle gets posted. None announces in the same channel as the game.
+    pub announcement_channel: Option<ChannelId>,
+    // Whether everyone's daily puzzle results get posted when the day is over.
+    pub leaderboard: bool,
+    // Empty means every command is allowed.
+    pub allowed_commands: Vec<String>,
+    // Members with any of these count as server admins, on top of everyone who can manage the server.
+    pub admin_roles: Vec<RoleId>,
+    // Picked with /language. None follows each member's Discord, and anything posted to the whole server is in English.
+    pub locale: Option<Locale>,
+}
+
+impl Default for GuildConfig {
+    fn default() -> Self {
+        GuildConfig {
+            announcement_channel: None,
+            leaderboard: true,
+            allowed_commands: vec![],
+            admin_roles: vec![],
+            locale: None,
+        }
+    }
+}
+
+// Always allowed, otherwise a server could lock itself out of changing anything.
+pub const CONFIG_COMMAND: &str = "minicact_config";
+
+impl GuildConfig {
+    pub fn allows(&self, command: &str) -> bool {
+        command == CONFIG_COMMAND || self.allowed_commands.is_empty() || self.allowed_commands.iter().any(|allowed| allowed == command)
+    }
+}
+
+// One line of the guild config file, minus the guild. Same idea as the preferences file.
+impl Display for GuildConfig {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let list = |items: Vec<String>| if items.is_empty() { "none".to_string() } else { items.join(",") };
+        write!(
+            fmt,
+            "announce={} leaderboard={} commands={} roles={} locale={}",
+            self.announcement_channel.map_or("none".to_string(), |channel| channel.to_string()),
+            self.leaderboard,
+            list(self.allowed_commands.clone()),
+            list(self.admin_roles.iter().map(|role| role.to_string()).collect()),
+            self.locale.map_or("auto", |locale| locale.code())
+        )
+    }
+}
+
+impl FromStr for GuildConfig {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut config = GuildConfig::default();
+        for field in s.split_whitespace() {
+            let (key, value) = field.split_once('=').ok_or("guild config is missing an =")?;
+            let items = value.split(',').filter(|item| *item != "none" && !item.is_empty());
+            match key {
+                "announce" => config.announcement_channel = match value {
+                    "none" => None,
+                    id => Some(ChannelId(id.parse().map_err(|_| "announcement channel failed to parse")?)),
+                },
+                "leaderboard" => config.leaderboard = value.parse().map_err(|_| "leaderboard failed to parse")?,
+                "commands" => config.allowed_commands = items.map(|item| item.to_string()).collect(),
+                "roles" => config.admin_roles = items.map(|item| item.parse().map(RoleId)).collect::<Result<_, _>>().map_err(|_| "admin role failed to parse")?,
+                "locale" => config.locale = match value {
+                    "auto" => None,
+                    code => Some(Locale::from_code(code).ok_or("unknown locale")?),
+                },
+                _ => (),
+            }
+        }
+        Ok(config)
+    }
+}
+
+// Only servers that changed something are in here.
+lazy_static! {
+    static ref GUILD_CONFIGS: Mutex<HashMap<GuildId, GuildConfig>> = Mutex::new(HashMap::new());
+}
+
+pub async fn guild_config(guild: GuildId) -> GuildConfig {
+    GUILD_CONFIGS.lock().await.get(&guild).cloned().unwrap_or_default()
+}
+
+// Every server that changed something, for things that go through all of them like posting the daily puzzle.
+pub async fn all_guild_configs() -> Vec<(GuildId, GuildConfig)> {
+    GUILD_CONFIGS.lock().await.iter().map(|(guild, config)| (*guild, config.clone())).collect()
+}
+
+// Changes a server's config and saves it right away, returning what it ended up as.
+pub async fn update_guild_config(guild: GuildId, change: impl FnOnce(&mut GuildConfig)) -> GuildConfig {
+    let path = settings().guild_config_file.clone();
+    let mut all_configs = GUILD_CONFIGS.lock().await;
+    let mut config = all_configs.get(&guild).cloned().unwrap_or_default();
+    change(&mut config);
+    if config == GuildConfig::default() {
+        all_configs.remove(&guild);
+    } else {
+        all_configs.insert(guild, config.clone());
+    }
+    let contents = all_configs.iter()
+        .map(|(guild, config)| format!("{} {}\n", guild, config))
+        .collect::<String>();
+    // Same as the preferences file: write it somewhere else first, and keep the lock until it's in place.
+    let temp_path = format!("{}.tmp", path);
+    if let Err(why) = std::fs::write(&temp_path, contents).and_then(|_| std::fs::rename(&temp_path, &path)) {
+        error!("Failed to save guild config to {}: {}", path, why);
+    }
+    config
+}
+
+// Called once at startup. A missing file just means no server has changed anything yet.
+pub async fn load_guild_configs() {
+    let path = settings().guild_config_file.clone();
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(why) => {
+            info!("No guild config loaded from {}: {}", path, why);
+            return
+        }
+    };
+    let mut all_configs = GUILD_CONFIGS.lock().await;
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let (guild, config) = line.split_once(' ').unwrap_or((line, ""));
+        match (guild.parse::<u64>(), config.parse::<GuildConfig>()) {
+            (Ok(guild), Ok(config)) => { all_configs.insert(GuildId(guild), config); },
+            (Err(_), _) => warn!("Skipping guild config line with a bad guild: {}", line),
+            (_, Err(why)) => warn!("Skipping guild config for guild {}: {}", guild, why),
+        }
+    }
+    info!("Loaded config for {} guilds.", all_configs.len());
+}
@@ -0,0 +1,1366 @@
+// Every string the bot shows to users, in every language it speaks.
+// English is the source of truth: a key missing from another language just falls back to English, so a half-finished translation is still usable.
+// Placeholders are numbered ({0}, {1}, ...) so a translation can put them in whatever order reads naturally.
+
+use crate::guild_config::guild_config;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use serenity::builder::{CreateApplicationCommand, CreateApplicationCommandOption};
+use serenity::model::id::GuildId;
+
+use lazy_static::lazy_static;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Japanese,
+    German,
+    French,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 4] = [Locale::English, Locale::Japanese, Locale::German, Locale::French];
+
+    // The same codes Discord uses, minus the region.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Japanese => "ja",
+            Locale::German => "de",
+            Locale::French => "fr",
+        }
+    }
+
+    // Written in the language itself, so people can find theirs in the list.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::Japanese => "日本語",
+            Locale::German => "Deutsch",
+            Locale::French => "Français",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Locale> {
+        Locale::ALL.into_iter().find(|locale| locale.code() == code)
+    }
+
+    // Discord sends things like "en-US" or "ja", and we only care about the language part.
+    pub fn from_discord(locale: &str) -> Option<Locale> {
+        Locale::from_code(locale.split('-').next().unwrap_or_default())
+    }
+
+    // The thousands separator and the decimal point.
+    fn separators(&self) -> (&'static str, char) {
+        match self {
+            Locale::English | Locale::Japanese => (",", '.'),
+            Locale::German => (".", ','),
+            // A narrow no-break space, so big numbers don't get split across lines.
+            Locale::French => ("\u{202f}", ','),
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => ENGLISH,
+            Locale::Japanese => JAPANESE,
+            Locale::German => GERMAN,
+            Locale::French => FRENCH,
+        }
+    }
+}
+
+// Someone's own choice wins, then their server's, then whatever language their Discord is in.
+// What people pick for themselves goes in their minicact preferences, and what server managers pick goes in the guild config.
+pub async fn resolve_locale(choice: Option<Locale>, guild: Option<GuildId>, discord_locale: &str) -> Locale {
+    if let Some(locale) = choice {
+        return locale
+    }
+    if let Some(guild) = guild {
+        if let Some(locale) = guild_config(guild).await.locale {
+            return locale
+        }
+    }
+    Locale::from_discord(discord_locale).unwrap_or_default()
+}
+
+lazy_static! {
+    static ref CATALOG: HashMap<(Locale, &'static str), &'static str> = Locale::ALL.iter()
+        .flat_map(|&locale| locale.catalog().iter().map(move |&(key, text)| ((locale, key), text)))
+        .collect();
+}
+
+pub fn tr(locale: Locale, key: &str) -> &'static str {
+    CATALOG.get(&(locale, key))
+        .or_else(|| CATALOG.get(&(Locale::English, key)))
+        .copied()
+        .unwrap_or_else(|| {
+            debug_assert!(false, "Message {} is missing from the English catalog", key);
+            ""
+        })
+}
+
+// tr, then fills in the placeholders.
+pub fn trf(locale: Locale, key: &str, args: &[&dyn Display]) -> String {
+    let mut output = tr(locale, key).to_string();
+    for (i, arg) in args.iter().enumerate() {
+        output = output.replace(format!("{{{}}}", i).as_str(), arg.to_string().as_str());
+    }
+    output
+}
+
+// Like format!("{:.*}", decimals, value), but with the locale's separators.
+pub fn format_number(locale: Locale, value: f64, decimals: usize) -> String {
+    let (group, point) = locale.separators();
+    let formatted = format!("{:.*}", decimals, value.abs());
+    let (whole, fraction) = match formatted.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (formatted.as_str(), None),
+    };
+    let mut output = String::new();
+    if value < 0. && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        output.push('-');
+    }
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            output.push_str(group);
+        }
+        output.push(c);
+    }
+    if let Some(fraction) = fraction {
+        output.push(point);
+        output.push_str(fraction);
+    }
+    output
+}
+
+// Slash command descriptions have to be set up front for every language, since Discord picks which one to show.
+pub fn describe_command<'a>(command: &'a mut CreateApplicationCommand, key: &str) -> &'a mut CreateApplicationCommand {
+    command.description(tr(Locale::English, key));
+    for locale in Locale::ALL.into_iter().filter(|&locale| locale != Locale::English) {
+        if let Some(text) = CATALOG.get(&(locale, key)) {
+            command.description_localized(locale.code(), text);
+        }
+    }
+    command
+}
+
+pub fn describe_option<'a>(option: &'a mut CreateApplicationCommandOption, key: &str) -> &'a mut CreateApplicationCommandOption {
+    option.description(tr(Locale::English, key));
+    for locale in Locale::ALL.into_iter().filter(|&locale| locale != Locale::English) {
+        if let Some(text) = CATALOG.get(&(locale, key)) {
+            option.description_localized(locale.code(), text);
+        }
+    }
+    option
+}
+
+// A string choice whose name is translated the same way.
+pub fn add_choice<'a>(option: &'a mut CreateApplicationCommandOption, key: &str, value: &str) -> &'a mut CreateApplicationCommandOption {
+    option.add_string_choice_localized(
+        tr(Locale::English, key),
+        value,
+        Locale::ALL.into_iter()
+            .filter(|&locale| locale != Locale::English)
+            .filter_map(|locale| CATALOG.get(&(locale, key)).map(|&text| (locale.code(), text)))
+    )
+}
+
+const ENGLISH: &[(&str, &str)] = &[
+    ("nyi", "This command hasn't been implemented. Try /help"),
+    ("help", "Currently available commands: `/ping`, `/shutdown`, `/help`, `/language`, `/minicact_play`, `/minicact_simulate`, `/minicact_daily`, `/minicact_quiz`, `/minicact_solve`, `/minicact_import`, `/minicact_export`, `/minicact_images`, `/minicact_accessibility`, `/minicact_settings`, `/minicact_config`."),
+    ("no_permission", "You do not have permission."),
+    ("shutting_down", "Shutting down..."),
+    ("shutdown.draining", "No new games can be started now. Shutting down once everyone's games are finished, or nobody has used the bot for a bit..."),
+    ("shutdown.restarting", "Restarting..."),
+    ("shutdown.immediate", "Right away"),
+    ("shutdown.drain", "Once nobody is playing"),
+    ("shutdown.restart", "Restart"),
+    ("shutdown.no_new_games", "The bot is about to shut down, so no new games can be started right now. Try again once it's back!"),
+    ("shutdown.notice", "⚠️ The bot is shutting down. Your game is saved, so you can pick up where you left off once it's back."),
+    ("shutdown.notice_restart", "⚠️ The bot is restarting. Your game is saved, so you can pick up where you left off in a moment."),
+    ("language.automatic", "Automatic"),
+    ("language.set_user", "Messages will be in {0} from now on."),
+    ("language.set_guild", "Messages in this server will be in {0} from now on, unless someone picks their own language."),
+    ("language.reset_user", "Messages will follow your Discord language from now on."),
+    ("language.reset_guild", "This server no longer has its own language."),
+    ("language.guild_only", "Only servers can have their own language."),
+
+    ("cmd.help", "Information on how to use the bot"),
+    ("cmd.ping", "A ping command"),
+    ("cmd.shutdown", "Shut down the bot"),
+    ("opt.shutdown.mode", "How to shut down"),
+    ("cmd.language", "Choose which language the bot uses"),
+    ("opt.language.language", "The language to use, or Automatic to follow Discord"),
+    ("opt.language.server", "Set it for the whole server instead of just you"),
+    ("cmd.minicact_play", "Play the game!"),
+    ("cmd.minicact_simulate", "Play a simulated game!"),
+    ("opt.minicact_simulate.seed", "Play the same tickets as a previous simulation"),
+    ("cmd.minicact_daily", "Play today's puzzle, the same for everyone in this server!"),
+    ("cmd.minicact_quiz", "Practice choosing tiles and lines!"),
+    ("cmd.minicact_solve", "Get a recommendation for a ticket written like `53 17 92`"),
+    ("opt.minicact_solve.ticket", "Each reveal is the tile (1-9, reading order) then the number, e.g. `53 17 92`"),
+    ("cmd.minicact_import", "Continue a game from its text version"),
+    ("opt.minicact_import.game", "The text version of a game, from /minicact_export or the end of a game"),
+    ("cmd.minicact_export", "Get the text version of your current game, to share or import later"),
+    ("cmd.minicact_images", "Get recommendations as a picture instead of a text graph"),
+    ("opt.minicact_images.enabled", "Whether to send pictures"),
+    ("cmd.minicact_accessibility", "Use written labels instead of emoji, colors and graphs"),
+    ("opt.minicact_accessibility.enabled", "Whether to use written labels"),
+    ("cmd.minicact_config", "View or change how the bot works in this server (server admins only)"),
+    ("opt.minicact_config.announcement_channel", "Where results get announced and the daily puzzle gets posted"),
+    ("opt.minicact_config.clear_announcement_channel", "Stop using an announcement channel"),
+    ("opt.minicact_config.leaderboard", "Whether to post everyone's daily puzzle results"),
+    ("opt.minicact_config.allowed_commands", "The only commands people can use here, separated by spaces, or \"all\""),
+    ("opt.minicact_config.add_admin_role", "Let members with this role change the configuration"),
+    ("opt.minicact_config.remove_admin_role", "Stop treating this role as an admin role"),
+    ("cmd.minicact_settings", "View or change your Mini Cactpot settings"),
+    ("opt.minicact_settings.objective", "What recommendations should aim for"),
+    ("opt.minicact_settings.multiplier", "Multiply MGP amounts by this, e.g. 1.5 for a 50% bonus"),
+    ("opt.minicact_settings.graph", "How payout chances are shown"),
+    ("opt.minicact_settings.language", "The language to use, or Automatic to follow the server and Discord"),
+    ("opt.minicact_settings.accessibility", "Whether to use written labels instead of emoji, colors and graphs"),
+    ("opt.minicact_settings.announce", "Announce your results as soon as you finish a game"),
+    ("opt.minicact_settings.public", "Show your games and quizzes to everyone in the channel"),
+    ("cmd.minicact_benchmark", "Play lots of tickets with a strategy (admin only)"),
+    ("opt.minicact_benchmark.strategy", "How to play each ticket"),
+    ("opt.minicact_benchmark.tickets", "How many tickets to play (default 100000)"),
+    ("opt.minicact_benchmark.rule", "For the custom rule, e.g. `tiles=5,1,3 lines=4,8`"),
+    ("opt.minicact_benchmark.exact", "Play every possible ticket once instead of random ones"),
+    ("cmd.minicact_stats", "How the bot has been used since it started (admin only)"),
+    ("cmd.admin", "Bot admin tools (admin only)"),
+    ("opt.admin.games", "List everyone's games in progress, oldest first"),
+    ("opt.admin.expire", "Throw away someone's game in progress"),
+    ("opt.admin.expire.user", "Whose game to throw away"),
+    ("opt.admin.reload", "Read the config file again, without restarting"),
+    ("opt.admin.presence", "Change what the bot is shown as playing"),
+    ("opt.admin.presence.text", "Leave it out to go back to the default"),
+    ("opt.admin.selfcheck", "Double-check the solver and every game in progress"),
+
+    ("position.0", "Top left"),
+    ("position.1", "Top middle"),
+    ("position.2", "Top right"),
+    ("position.3", "Middle left"),
+    ("position.4", "Center"),
+    ("position.5", "Middle right"),
+    ("position.6", "Bottom left"),
+    ("position.7", "Bottom middle"),
+    ("position.8", "Bottom right"),
+    // The same order as POSITION_LINE_TABLE. The inline versions go in the middle of a sentence.
+    ("line.0", "Bottom row"),
+    ("line.1", "Middle row"),
+    ("line.2", "Top row"),
+    ("line.3", "\\ diagonal"),
+    ("line.4", "Left column"),
+    ("line.5", "Middle column"),
+    ("line.6", "Right column"),
+    ("line.7", "/ diagonal"),
+    ("line.0.inline", "bottom row"),
+    ("line.1.inline", "middle row"),
+    ("line.2.inline", "top row"),
+    ("line.3.inline", "\\ diagonal"),
+    ("line.4.inline", "left column"),
+    ("line.5.inline", "middle column"),
+    ("line.6.inline", "right column"),
+    ("line.7.inline", "/ diagonal"),
+    ("board.covered", "covered"),
+    ("board.row", "{0}: {1}"),
+    ("the_tile", "the {0} tile"),
+    ("the_line", "the {0}"),
+
+    ("button.restore", "Restore"),
+    ("button.discard", "Discard"),
+    ("button.continue", "Continue"),
+    ("button.announce", "Announce your results!"),
+    ("button.play_again", "Play again?"),
+    ("button.next_question", "Next question"),
+    ("button.reveal_board", "Enter the rest of the board"),
+    ("button.replay", "Replay this ticket"),
+    ("button.undo", "Undo"),
+    ("button.reset", "Reset"),
+    ("button.reveal_number", "Reveal {0}"),
+    ("button.last", "Last: {0}"),
+    ("button.nothing_yet", "Nothing yet"),
+    ("note.recommended", "recommended"),
+    ("note.recommended_line", "recommended line"),
+    ("note.best", "best"),
+    ("note.best_line", "best line"),
+    ("note.your_pick", "your pick"),
+    ("menu.payout", "Enter your payout!"),
+    ("menu.line", "Choose your line!"),
+
+    ("game.already_started", "{0} you already have a game started.\nWould you like to:\n> ↩ Restore your previous game\n> 🔄 Discard it and start from scratch"),
+    ("game.enter_first_tile", "Enter the already revealed tile:"),
+    ("game.enter_board_number", "Enter the number under {0}:"),
+    ("game.disabled", "That button is currently disabled. If you made a mistake, press `Undo` ↩ / `Reset` 🔄"),
+    ("game.desync", "desync detected and fixed. Please double-check that everything is correct."),
+    ("game.finished", "Thanks for using this bot! Feel free to dismiss this message.\nYour total payout is {0} MGP, which is {1} percentile."),
+    ("game.share", "Share this game with `{0}`"),
+    ("game.daily_note", "Your results will be compared with everyone else's when the next puzzle goes up."),
+    ("game.seed_note", "These tickets had seed `{0}`, use `/minicact_simulate seed:{0}` to play them again."),
+    ("game.ticket_paid", "Ticket {0}: the {1} paid {2} MGP"),
+    ("game.announce", "{0} earned {1} MGP from Mini Cactpot today, which is {2} percentile!"),
+    ("game.stale", "{0} This message is from a game you have since replaced. Your current game is the one you started {1}.\nPress ↩ to continue your current game here instead."),
+    ("game.removed", "{0} Your game is no longer being tracked, meaning that either you completed it elsewhere or the bot restarted.\nFeel free to dismiss this message. Use /minicact_play to start a new game."),
+    ("daily.server_only", "The daily puzzle is shared by everyone in a server, so it can only be played in one."),
+    ("daily.already_played", "You already played today's puzzle and earned {0} MGP! The next one goes up {1}."),
+    ("daily.already_started", "You already started today's puzzle, and it can only be played once. The next one goes up {0}."),
+    ("daily.summary_title", "📅 Results from the {0} daily puzzle:"),
+    ("daily.summary_ticket", "Ticket {0}:"),
+    ("daily.summary_solver", "The solver would have earned {0} MGP ({1} / {2} / {3})."),
+    ("daily.summary_line", "{0}. {1}: {2} MGP from the {3}, {4}, and {5}, with {6}/12 choices as good as the solver's"),
+    ("daily.summary_more", "...and {0} more."),
+    ("daily.post", "🎟 Today's Mini Cactpot puzzle is up! Everyone in this server gets the same three tickets, and every choice is final.\nEveryone's results get compared here {0}."),
+    ("button.play_daily", "Play today's puzzle"),
+    ("strategy.optimal", "Optimal"),
+    ("strategy.random", "Random"),
+    ("strategy.diagonals", "Diagonals"),
+    ("strategy.rule", "Custom rule"),
+    ("benchmark.no_strategy", "Please choose a strategy."),
+    ("benchmark.not_deterministic", "That strategy doesn't always make the same choice, so it can't be evaluated exactly."),
+    ("benchmark.exact", "Played all {0} possible tickets with the {1} strategy:\n{2} Average Payout: exactly {3} MGP\nStandard deviation: {4} MGP\nThe optimal strategy averages exactly {5} MGP, so this strategy loses {6} MGP per ticket."),
+    ("benchmark.result", "Played {0} tickets with the {1} strategy:\n{2} Average Payout: {3} MGP (± {4})\nStandard deviation: {5} MGP\nThe optimal strategy averages exactly {6} MGP"),
+    ("benchmark.optimal_error", ", which is {0} standard errors away."),
+    ("benchmark.loss", ", so this strategy loses about {0} MGP per ticket."),
+    ("rule.bad_part", "`{0}` should look like `tiles=5,1,3` or `lines=4,8`."),
+    ("rule.bad_key", "`{0}` should be either `tiles` or `lines`."),
+    ("rule.bad_number", "`{0}` should be a number from 1 to {1}."),
+    ("stats.since", "📊 Since {0}:"),
+    ("stats.by_mode", "{0} ({1} played, {2} simulated, {3} daily)"),
+    ("stats.started", "Games started: {0}"),
+    ("stats.finished", "Games finished: {0}"),
+    ("stats.desyncs", "Desyncs: {0} · Revealed tiles pressed: {1}"),
+    ("stats.errors", "Errors: {0} commands, {1} components"),
+    ("stats.commands", "{0} commands"),
+    ("stats.components", "{0} components"),
+    ("stats.latency", "{0}, averaging {1} ms, 95% under {2} ms"),
+    ("stats.latency_slow", "{0}, averaging {1} ms, but more than 5% took over 5 seconds"),
+    ("stats.responded", "Responded to {0} and {1}"),
+
+    ("rec.average", "Average Payout: {0} MGP"),
+    ("rec.uncover", "Recommended: uncover the {0} tile."),
+    ("rec.pick", "Recommended: pick the {0}."),
+    ("rec.table", "Chance of each payout:"),
+    ("rec.table_row", "{0} MGP: {1}%"),
+    ("summary.ticket", "Ticket {0}:"),
+    ("summary.line", "{0}: {1} MGP"),
+    ("replay.title", "Replay of ticket {0}:"),
+    ("replay.tile", "You uncovered {0} (average {1} MGP), the solver would have uncovered {2} (average {3} MGP)."),
+    ("replay.line", "You picked the {0} (average {1} MGP), the solver would have picked the {2} (average {3} MGP)."),
+    ("replay.every_line", "Here's what every line actually paid:"),
+
+    ("solve.pick", "Pick the {0}."),
+    ("solve.uncover", "Uncover the ❔ tile next."),
+    ("solve.nothing", "There's nothing to decide yet. Add the tile the ticket came with, like `53` for a 3 under tile 5."),
+    ("import.done", "Imported your game! Press ↩ to continue it."),
+    ("import.replaced", "This replaced the game you started {0}."),
+    ("import.daily_in_progress", "You still have today's daily puzzle going, and it only gets one try. Finish it first, then import your game."),
+    ("export.done", "Here's your current game, paste it into `/minicact_import` to pick it back up:\n`{0}`"),
+    ("export.empty", "Your game doesn't have anything in it yet."),
+    ("export.none", "You don't have a game started. Use /minicact_play to start one."),
+    ("images.on", "🖼 Recommendations will come with a picture of the board and payouts from now on."),
+    ("images.off", "📝 Recommendations will use the text graph from now on."),
+    ("accessibility.on", "Accessibility mode is on. Buttons will have written labels, recommendations will be written out, and payout chances will be a plain list."),
+    ("accessibility.off", "Accessibility mode is off."),
+    ("config.guild_only", "Only servers have a configuration."),
+    ("config.needs_admin", "Only server admins can do that. That's anyone who can manage the server or has one of its admin roles."),
+    ("config.command_disabled", "That command is turned off in this server."),
+    ("config.unknown_command", "{0} is not one of this bot's commands."),
+    ("config.title", "This server's configuration:"),
+    ("config.announcement_channel", "Announcement channel: {0}"),
+    ("config.no_channel", "None, results are announced where the game is"),
+    ("config.leaderboard", "Daily puzzle leaderboard: {0}"),
+    ("config.allowed_commands", "Allowed commands: {0}"),
+    ("config.all_commands", "All"),
+    ("config.admin_roles", "Admin roles: {0}"),
+    ("config.no_roles", "None, only members who can manage the server"),
+
+    ("settings.title", "Your settings:"),
+    ("settings.objective", "Objective: {0}"),
+    ("settings.multiplier", "MGP multiplier: ×{0}"),
+    ("settings.graph", "Graph: {0}"),
+    ("settings.language", "Language: {0}"),
+    ("settings.accessibility", "Accessibility mode: {0}"),
+    ("settings.announce", "Announce results automatically: {0}"),
+    ("settings.public", "Public games: {0}"),
+    ("settings.on", "On"),
+    ("settings.off", "Off"),
+    ("objective.average", "Best average payout"),
+    ("objective.jackpot", "Best chance at 10,000 MGP"),
+    ("graph.text", "Text"),
+    ("graph.image", "Picture"),
+    ("rec.jackpot", "Chance of 10,000 MGP: {0}%"),
+    ("game.not_yours", "That's someone else's game. Use /minicact_play to start your own!"),
+
+    ("quiz.tile_question", "Which tile would you uncover next?"),
+    ("quiz.line_question", "Which line would you choose?"),
+    ("quiz.correct", "✅ Correct! Picking {0} averages {1} MGP, which is as good as it gets."),
+    ("quiz.wrong", "❌ Not quite. You picked {0} (average {1} MGP), the best choice is {2} (average {3} MGP).\nThat costs {4} MGP per ticket."),
+    ("quiz.score", "Score: {0}/{1} · Streak: {2} (best {3}) · MGP given up: {4}"),
+    ("quiz.answered", "{0} This question has already been answered, or you've started another one since.\nPress ➡ for a new question."),
+
+    ("error.duplicate_number", "{0} has already been revealed on this ticket."),
+    ("error.duplicate_position", "That tile has already been revealed on this ticket."),
+    ("error.invalid_number", "{0} is not a number on the ticket."),
+    ("error.invalid_position", "{0} is not a tile on the ticket."),
+    ("error.invalid_payout", "That is not a possible payout."),
+    ("error.payout_before_reveals", "You need to reveal 4 tiles before entering a payout."),
+    ("error.game_over", "All 3 tickets have already been played."),
+    ("error.expected_position", "The game was expecting you to choose a tile."),
+    ("error.expected_number", "The game was expecting you to enter a number."),
+    ("error.expected_payout", "The game was expecting you to enter your payout."),
+    ("error.expected_board", "The game was expecting you to enter the rest of the board."),
+    ("error.unexpected", "The game was not expecting {0}."),
+    ("error.invalid_line", "{0} is not a line on the ticket."),
+    ("error.simulated", "This is a simulated game, so the bot fills that in for you."),
+    ("error.not_simulated", "Only simulated games can choose a line directly."),
+    ("error.board_already_revealed", "The rest of that board has already been entered."),
+    ("error.payout_mismatch", "None of the lines on that board pay out {0} MGP. Double-check the numbers you entered, or undo to fix your payout."),
+    ("error.broken_invariant", "The game is in an impossible state: {0}"),
+    ("notation.empty", "There's nothing to read. A ticket looks like `53 17 92 34 $1800`."),
+    ("notation.too_many_tickets", "There are only 3 tickets a day, but this has {0}."),
+    ("notation.bad_token", "Ticket {0}: couldn't read `{1}`. Reveals look like `53` (a 3 under tile 5), payouts like `$252`, boards like `B218596347`, and lines like `L3`."),
+    ("notation.too_many_reveals", "Ticket {0}: only 4 tiles can be revealed on a ticket."),
+    ("notation.out_of_order", "Ticket {0}: `{1}` is out of order. Each ticket goes reveals, then the payout, then the board, then the line."),
+    ("notation.unfinished_ticket", "Ticket {0}: every ticket before the last one needs a payout."),
+    ("notation.incomplete_board", "Ticket {0}: the board needs a number on every tile."),
+    ("notation.board_mismatch", "Ticket {0}: the board doesn't match the number revealed under tile {1}."),
+    ("notation.board_without_payout", "Ticket {0}: the board can only be entered after the payout."),
+    ("notation.line_without_board", "Ticket {0}: a line can only be given along with the whole board."),
+    ("notation.line_mismatch", "Ticket {0}: line {1} doesn't pay out what the ticket says it did."),
+    ("notation.refused", "Ticket {0}, `{1}`: {2}"),
+    ("mode.play", "regular"),
+    ("mode.simulate", "simulated"),
+    ("mode.daily", "daily"),
+    ("admin.expired", "Threw away {0}'s {1} game."),
+    ("admin.no_game", "{0} doesn't have a game going."),
+    ("admin.choose_user", "Please choose a user."),
+    ("admin.presence", "Now playing {0}."),
+    ("admin.unknown_subcommand", "That subcommand doesn't exist yet."),
+    ("admin.no_games", "Nobody has a game going."),
+    ("admin.games", "{0} games going:"),
+    ("admin.game", "{0}: {1}, started {2}, {3}/12 tiles revealed"),
+    ("admin.more", "...and {0} more."),
+    ("check.payout_distribution", "Payout distribution"),
+    ("check.payout_distribution_detail", "adds up to {0}"),
+    ("check.daily_percentiles", "Daily percentiles"),
+    ("check.daily_percentiles_detail", "the best day is at {0}%"),
+    ("check.optimal_play", "Optimal play"),
+    ("check.optimal_play_detail", "played {0} of {1} tickets for {2} MGP on average, the solver expects {3}"),
+    ("check.panicked", "panicked: {0}"),
+    ("check.games", "Games in progress"),
+    ("check.games_ok", "all {0} follow the rules"),
+    ("check.games_broken", "{0} of {1} are broken: {2}"),
+    ("check.saving", "Saving games"),
+    ("check.saving_ok", "all {0} would survive a restart"),
+    ("check.saving_broken", "{0} wouldn't survive a restart: {1}"),
+    ("reload.unchanged", "Nothing in the config file changed."),
+    ("reload.changed", "Reloaded the config, changed {0}."),
+    ("reload.restart_only", " {0} only take effect after a restart."),
+    ("reload.bad_config", "The config file has a problem, so nothing was changed:\n{0}"),
+    ("reload.no_admins", "The new config doesn't have any admins, so nobody could use admin commands anymore. Nothing was changed."),
+];
+
+const JAPANESE: &[(&str, &str)] = &[
+    ("nyi", "このコマンドはまだ実装されていません。/help をお試しください。"),
+    ("help", "使えるコマンド：`/ping`、`/shutdown`、`/help`、`/language`、`/minicact_play`、`/minicact_simulate`、`/minicact_daily`、`/minicact_quiz`、`/minicact_solve`、`/minicact_import`、`/minicact_export`、`/minicact_images`、`/minicact_accessibility`、`/minicact_settings`、`/minicact_config`"),
+    ("no_permission", "権限がありません。"),
+    ("shutting_down", "シャットダウンしています…"),
+    ("shutdown.draining", "新しいゲームはもう始められません。全員のゲームが終わるか、しばらく誰も使わなくなったらシャットダウンします…"),
+    ("shutdown.restarting", "再起動しています…"),
+    ("shutdown.immediate", "今すぐ"),
+    ("shutdown.drain", "誰も遊んでいなくなったら"),
+    ("shutdown.restart", "再起動"),
+    ("shutdown.no_new_games", "ボットがまもなくシャットダウンするため、今は新しいゲームを始められません。戻ってきたらもう一度お試しください！"),
+    ("shutdown.notice", "⚠️ ボットがシャットダウンします。ゲームは保存されているので、戻ってきたら続きから遊べます。"),
+    ("shutdown.notice_restart", "⚠️ ボットが再起動します。ゲームは保存されているので、すぐに続きから遊べます。"),
+    ("language.automatic", "自動"),
+    ("language.set_user", "これからは{0}で表示します。"),
+    ("language.set_guild", "このサーバーでは、これから{0}で表示します（自分で言語を選んだ人を除く）。"),
+    ("language.reset_user", "これからはDiscordの言語設定に合わせて表示します。"),
+    ("language.reset_guild", "このサーバーの言語設定を解除しました。"),
+    ("language.guild_only", "言語を設定できるのはサーバーだけです。"),
+
+    ("cmd.help", "ボットの使い方"),
+    ("cmd.ping", "応答速度を確認する"),
+    ("cmd.shutdown", "ボットを停止する"),
+    ("opt.shutdown.mode", "停止のしかた"),
+    ("cmd.language", "ボットの言語を選ぶ"),
+    ("opt.language.language", "使う言語（「自動」ならDiscordの設定に合わせる）"),
+    ("opt.language.server", "自分だけでなく、サーバー全体に設定する"),
+    ("cmd.minicact_play", "ミニくじテンダーを遊ぶ！"),
+    ("cmd.minicact_simulate", "シミュレーションで遊ぶ！"),
+    ("opt.minicact_simulate.seed", "以前のシミュレーションと同じくじで遊ぶ"),
+    ("cmd.minicact_daily", "サーバーの全員で共通の、今日のパズルを遊ぶ！"),
+    ("cmd.minicact_quiz", "マスとラインの選び方を練習する！"),
+    ("cmd.minicact_solve", "`53 17 92` のように書いたくじのおすすめを見る"),
+    ("opt.minicact_solve.ticket", "めくったマス（1〜9、左上から順）と数字を続けて書く。例：`53 17 92`"),
+    ("cmd.minicact_import", "テキスト版からゲームを再開する"),
+    ("opt.minicact_import.game", "/minicact_export やゲーム終了時に表示されるテキスト"),
+    ("cmd.minicact_export", "今のゲームをテキストにして、共有したり後で再開したりする"),
+    ("cmd.minicact_images", "おすすめをテキストのグラフではなく画像で表示する"),
+    ("opt.minicact_images.enabled", "画像を送るかどうか"),
+    ("cmd.minicact_accessibility", "絵文字・色・グラフの代わりに文字で表示する"),
+    ("opt.minicact_accessibility.enabled", "文字で表示するかどうか"),
+    ("cmd.minicact_config", "このサーバーでのボットの設定を表示・変更します（サーバー管理者のみ）"),
+    ("opt.minicact_config.announcement_channel", "結果の発表とデイリーパズルの投稿をするチャンネル"),
+    ("opt.minicact_config.clear_announcement_channel", "発表チャンネルを使わないようにします"),
+    ("opt.minicact_config.leaderboard", "デイリーパズルの全員の結果を投稿するかどうか"),
+    ("opt.minicact_config.allowed_commands", "ここで使えるコマンドをスペース区切りで（すべて許可するなら「all」）"),
+    ("opt.minicact_config.add_admin_role", "このロールのメンバーも設定を変更できるようにします"),
+    ("opt.minicact_config.remove_admin_role", "このロールを管理者ロールから外します"),
+    ("cmd.minicact_settings", "ミニくじテンダーの設定を表示・変更します"),
+    ("opt.minicact_settings.objective", "おすすめで何を重視するか"),
+    ("opt.minicact_settings.multiplier", "MGPをこの倍率で表示します（50%ボーナスなら1.5）"),
+    ("opt.minicact_settings.graph", "獲得確率の表示方法"),
+    ("opt.minicact_settings.language", "使用する言語。「自動」ならサーバーとDiscordの設定に合わせます"),
+    ("opt.minicact_settings.accessibility", "絵文字・色・グラフの代わりに文字で表示するかどうか"),
+    ("opt.minicact_settings.announce", "ゲームが終わったらすぐに結果を発表します"),
+    ("opt.minicact_settings.public", "ゲームやクイズをチャンネルの全員に見えるようにします"),
+    ("cmd.minicact_benchmark", "戦略に沿ってたくさんのくじを遊びます（管理者のみ）"),
+    ("opt.minicact_benchmark.strategy", "それぞれのくじの遊び方"),
+    ("opt.minicact_benchmark.tickets", "遊ぶくじの枚数（初期値 100000）"),
+    ("opt.minicact_benchmark.rule", "カスタムルール用。例: `tiles=5,1,3 lines=4,8`"),
+    ("opt.minicact_benchmark.exact", "ランダムなくじの代わりに、ありえるくじをすべて1回ずつ遊ぶ"),
+    ("cmd.minicact_stats", "起動してからのボットの使われ方（管理者のみ）"),
+    ("cmd.admin", "ボット管理者用のツール（管理者のみ）"),
+    ("opt.admin.games", "進行中のゲームを古い順に一覧表示"),
+    ("opt.admin.expire", "誰かの進行中のゲームを破棄"),
+    ("opt.admin.expire.user", "ゲームを破棄するユーザー"),
+    ("opt.admin.reload", "再起動せずに設定ファイルを読み直す"),
+    ("opt.admin.presence", "ボットがプレイ中として表示する内容を変更"),
+    ("opt.admin.presence.text", "省略すると初期値に戻ります"),
+    ("opt.admin.selfcheck", "ソルバーと進行中のすべてのゲームを確認"),
+
+    ("position.0", "左上"),
+    ("position.1", "上"),
+    ("position.2", "右上"),
+    ("position.3", "左"),
+    ("position.4", "中央"),
+    ("position.5", "右"),
+    ("position.6", "左下"),
+    ("position.7", "下"),
+    ("position.8", "右下"),
+    ("line.0", "下の横列"),
+    ("line.1", "中央の横列"),
+    ("line.2", "上の横列"),
+    ("line.3", "＼の斜め"),
+    ("line.4", "左の縦列"),
+    ("line.5", "中央の縦列"),
+    ("line.6", "右の縦列"),
+    ("line.7", "／の斜め"),
+    ("line.0.inline", "下の横列"),
+    ("line.1.inline", "中央の横列"),
+    ("line.2.inline", "上の横列"),
+    ("line.3.inline", "＼の斜め"),
+    ("line.4.inline", "左の縦列"),
+    ("line.5.inline", "中央の縦列"),
+    ("line.6.inline", "右の縦列"),
+    ("line.7.inline", "／の斜め"),
+    ("board.covered", "未開封"),
+    ("board.row", "{0}：{1}"),
+    ("the_tile", "{0}のマス"),
+    ("the_line", "{0}"),
+
+    ("button.restore", "再開"),
+    ("button.discard", "破棄"),
+    ("button.continue", "続ける"),
+    ("button.announce", "結果を発表する！"),
+    ("button.play_again", "もう一度遊ぶ？"),
+    ("button.next_question", "次の問題"),
+    ("button.reveal_board", "残りのマスを入力する"),
+    ("button.replay", "このくじを振り返る"),
+    ("button.undo", "元に戻す"),
+    ("button.reset", "リセット"),
+    ("button.reveal_number", "{0}を入力"),
+    ("button.last", "直前：{0}"),
+    ("button.nothing_yet", "まだなし"),
+    ("note.recommended", "おすすめ"),
+    ("note.recommended_line", "おすすめのライン"),
+    ("note.best", "最善"),
+    ("note.best_line", "最善のライン"),
+    ("note.your_pick", "あなたの選択"),
+    ("menu.payout", "獲得MGPを入力してください！"),
+    ("menu.line", "ラインを選んでください！"),
+
+    ("game.already_started", "{0} すでにゲームを始めています。\nどうしますか？\n> ↩ 前のゲームを再開する\n> 🔄 破棄して最初からやり直す"),
+    ("game.enter_first_tile", "最初から開いているマスを入力してください："),
+    ("game.enter_board_number", "{0}の数字を入力してください："),
+    ("game.disabled", "そのボタンは今は使えません。間違えた場合は `元に戻す` ↩ / `リセット` 🔄 を押してください。"),
+    ("game.desync", "ずれを検出して修正しました。内容が正しいか確認してください。"),
+    ("game.finished", "ご利用ありがとうございました！このメッセージは閉じて大丈夫です。\n合計獲得MGPは {0} MGPで、上位から見て {1} パーセンタイルです。"),
+    ("game.share", "このゲームを共有：`{0}`"),
+    ("game.daily_note", "次のパズルが公開されると、みんなの結果と比べられます。"),
+    ("game.seed_note", "このくじのシードは `{0}` です。`/minicact_simulate seed:{0}` でもう一度遊べます。"),
+    ("game.ticket_paid", "くじ{0}：{1}で {2} MGP"),
+    ("game.announce", "{0} は今日のミニくじテンダーで {1} MGPを獲得しました。{2} パーセンタイルです！"),
+    ("game.stale", "{0} このメッセージは、置き換えられた前のゲームのものです。今のゲームは{1}に始めたものです。\n↩ を押すと、ここで今のゲームを続けられます。"),
+    ("game.removed", "{0} このゲームはもう記録されていません。別の場所で終わらせたか、ボットが再起動しました。\nこのメッセージは閉じて大丈夫です。新しいゲームは /minicact_play で始められます。"),
+    ("daily.server_only", "デイリーパズルはサーバーのみんなで共有するものなので、サーバー内でしか遊べません。"),
+    ("daily.already_played", "今日のパズルはもう遊んで、{0} MGP獲得しました！次のパズルは{1}に公開されます。"),
+    ("daily.already_started", "今日のパズルはもう始めています。遊べるのは1回だけです。次のパズルは{0}に公開されます。"),
+    ("daily.summary_title", "📅 {0}のデイリーパズルの結果:"),
+    ("daily.summary_ticket", "{0}枚目:"),
+    ("daily.summary_solver", "ソルバーなら {0} MGP（{1} / {2} / {3}）獲得していました。"),
+    ("daily.summary_line", "{0}. {1}: {3}、{4}、{5}で {2} MGP、ソルバーと同じくらい良い選択が12回中{6}回"),
+    ("daily.summary_more", "…ほか{0}人。"),
+    ("daily.post", "🎟 今日のミニくじテンダーのパズルが公開されました！このサーバーのみんなが同じ3枚のくじを遊び、選択はやり直せません。\nみんなの結果は{0}にここで比べられます。"),
+    ("button.play_daily", "今日のパズルを遊ぶ"),
+    ("strategy.optimal", "最適"),
+    ("strategy.random", "ランダム"),
+    ("strategy.diagonals", "斜め"),
+    ("strategy.rule", "カスタムルール"),
+    ("benchmark.no_strategy", "戦略を選んでください。"),
+    ("benchmark.not_deterministic", "その戦略はいつも同じ選択をするとは限らないので、正確には評価できません。"),
+    ("benchmark.exact", "ありえる {0} 枚のくじをすべて{1}戦略で遊びました:\n{2} 平均獲得MGP: ちょうど {3} MGP\n標準偏差: {4} MGP\n最適な戦略の平均はちょうど {5} MGPなので、この戦略はくじ1枚あたり {6} MGP損しています。"),
+    ("benchmark.result", "{0} 枚のくじを{1}戦略で遊びました:\n{2} 平均獲得MGP: {3} MGP（± {4}）\n標準偏差: {5} MGP\n最適な戦略の平均はちょうど {6} MGP"),
+    ("benchmark.optimal_error", "で、標準誤差{0}個分離れています。"),
+    ("benchmark.loss", "なので、この戦略はくじ1枚あたり約 {0} MGP損しています。"),
+    ("rule.bad_part", "`{0}` は `tiles=5,1,3` や `lines=4,8` のように書いてください。"),
+    ("rule.bad_key", "`{0}` は `tiles` か `lines` のどちらかにしてください。"),
+    ("rule.bad_number", "`{0}` は1から{1}までの数字にしてください。"),
+    ("stats.since", "📊 {0}から:"),
+    ("stats.by_mode", "{0}（通常 {1}、シミュレーション {2}、デイリー {3}）"),
+    ("stats.started", "始まったゲーム: {0}"),
+    ("stats.finished", "終わったゲーム: {0}"),
+    ("stats.desyncs", "ずれ: {0} · めくり済みのマスを押した回数: {1}"),
+    ("stats.errors", "エラー: コマンド {0}、コンポーネント {1}"),
+    ("stats.commands", "コマンド {0}件"),
+    ("stats.components", "コンポーネント {0}件"),
+    ("stats.latency", "{0}、平均 {1} ms、95%が {2} ms以内"),
+    ("stats.latency_slow", "{0}、平均 {1} ms、ただし5%以上が5秒を超えました"),
+    ("stats.responded", "応答: {0}、{1}"),
+
+    ("rec.average", "平均獲得：{0} MGP"),
+    ("rec.uncover", "おすすめ：{0}のマスをめくる。"),
+    ("rec.pick", "おすすめ：{0}を選ぶ。"),
+    ("rec.table", "獲得MGPごとの確率："),
+    ("rec.table_row", "{0} MGP：{1}%"),
+    ("summary.ticket", "くじ{0}："),
+    ("summary.line", "{0}：{1} MGP"),
+    ("replay.title", "くじ{0}の振り返り："),
+    ("replay.tile", "あなたは{0}をめくりました（平均 {1} MGP）。ソルバーなら{2}をめくります（平均 {3} MGP）。"),
+    ("replay.line", "あなたは{0}を選びました（平均 {1} MGP）。ソルバーなら{2}を選びます（平均 {3} MGP）。"),
+    ("replay.every_line", "各ラインの実際の獲得MGP："),
+
+    ("solve.pick", "{0}を選びましょう。"),
+    ("solve.uncover", "次は ❔ のマスをめくりましょう。"),
+    ("solve.nothing", "まだ選ぶものがありません。最初から開いているマスを、5番のマスが3なら `53` のように書き足してください。"),
+    ("import.done", "ゲームを読み込みました！↩ を押して続けてください。"),
+    ("import.replaced", "{0}に始めたゲームと置き換えました。"),
+    ("import.daily_in_progress", "今日のデイリーパズルがまだ進行中で、挑戦できるのは一度だけです。先にそちらを終えてから読み込んでください。"),
+    ("export.done", "今のゲームです。`/minicact_import` に貼り付けると再開できます：\n`{0}`"),
+    ("export.empty", "ゲームにまだ何も入力されていません。"),
+    ("export.none", "ゲームを始めていません。/minicact_play で始められます。"),
+    ("images.on", "🖼 これからはおすすめにマスと獲得MGPの画像を付けます。"),
+    ("images.off", "📝 これからはおすすめをテキストのグラフで表示します。"),
+    ("accessibility.on", "アクセシビリティモードをオンにしました。ボタンに文字のラベルが付き、おすすめは文章で、確率は一覧で表示します。"),
+    ("accessibility.off", "アクセシビリティモードをオフにしました。"),
+    ("config.guild_only", "設定があるのはサーバーだけです。"),
+    ("config.needs_admin", "それができるのはサーバー管理者だけです（サーバー管理の権限か、管理者ロールを持つメンバー）。"),
+    ("config.command_disabled", "このサーバーではそのコマンドは使えません。"),
+    ("config.unknown_command", "{0}はこのボットのコマンドではありません。"),
+    ("config.title", "このサーバーの設定："),
+    ("config.announcement_channel", "発表チャンネル：{0}"),
+    ("config.no_channel", "なし（ゲームと同じ場所で発表）"),
+    ("config.leaderboard", "デイリーパズルのランキング：{0}"),
+    ("config.allowed_commands", "使えるコマンド：{0}"),
+    ("config.all_commands", "すべて"),
+    ("config.admin_roles", "管理者ロール：{0}"),
+    ("config.no_roles", "なし（サーバー管理の権限を持つメンバーのみ）"),
+
+    ("settings.title", "あなたの設定："),
+    ("settings.objective", "目標：{0}"),
+    ("settings.multiplier", "MGP倍率：×{0}"),
+    ("settings.graph", "グラフ：{0}"),
+    ("settings.language", "言語：{0}"),
+    ("settings.accessibility", "アクセシビリティモード：{0}"),
+    ("settings.announce", "結果を自動で発表：{0}"),
+    ("settings.public", "ゲームを公開：{0}"),
+    ("settings.on", "オン"),
+    ("settings.off", "オフ"),
+    ("objective.average", "平均獲得MGPを最大に"),
+    ("objective.jackpot", "10,000 MGPの確率を最大に"),
+    ("graph.text", "テキスト"),
+    ("graph.image", "画像"),
+    ("rec.jackpot", "10,000 MGPの確率：{0}%"),
+    ("game.not_yours", "これは他の人のゲームです。/minicact_play で自分のゲームを始めましょう！"),
+
+    ("quiz.tile_question", "次はどのマスをめくりますか？"),
+    ("quiz.line_question", "どのラインを選びますか？"),
+    ("quiz.correct", "✅ 正解！{0}を選ぶと平均 {1} MGPで、これが最善です。"),
+    ("quiz.wrong", "❌ 惜しい！あなたの選択は{0}（平均 {1} MGP）、最善は{2}（平均 {3} MGP）です。\nくじ1枚あたり {4} MGPの損です。"),
+    ("quiz.score", "スコア：{0}/{1} · 連続正解：{2}（最高 {3}） · 失ったMGP：{4}"),
+    ("quiz.answered", "{0} この問題はもう回答済みか、別の問題を始めています。\n➡ を押すと新しい問題が出ます。"),
+
+    ("error.duplicate_number", "{0}はこのくじですでに出ています。"),
+    ("error.duplicate_position", "そのマスはこのくじですでにめくっています。"),
+    ("error.invalid_number", "{0}はくじにない数字です。"),
+    ("error.invalid_position", "{0}はくじにないマスです。"),
+    ("error.invalid_payout", "その獲得MGPはありえません。"),
+    ("error.payout_before_reveals", "獲得MGPを入力する前に、マスを4つめくってください。"),
+    ("error.game_over", "3枚のくじはすべて終わっています。"),
+    ("error.expected_position", "マスを選ぶ場面でした。"),
+    ("error.expected_number", "数字を入力する場面でした。"),
+    ("error.expected_payout", "獲得MGPを入力する場面でした。"),
+    ("error.expected_board", "残りのマスを入力する場面でした。"),
+    ("error.invalid_line", "{0}はくじにないラインです。"),
+    ("error.simulated", "シミュレーションなので、そこはボットが入力します。"),
+    ("error.not_simulated", "ラインを直接選べるのはシミュレーションだけです。"),
+    ("error.board_already_revealed", "そのくじの残りのマスはすでに入力されています。"),
+    ("error.payout_mismatch", "そのくじに {0} MGPになるラインはありません。入力した数字を確かめるか、元に戻して獲得MGPを直してください。"),
+    ("error.unexpected", "ゲームは{0}を想定していませんでした。"),
+    ("error.broken_invariant", "ゲームがありえない状態になっています: {0}"),
+    ("notation.empty", "読み込むものがありません。くじは `53 17 92 34 $1800` のように書きます。"),
+    ("notation.too_many_tickets", "くじは1日3枚までですが、{0}枚あります。"),
+    ("notation.bad_token", "{0}枚目のくじ: `{1}` が読み込めません。めくったマスは `53`（5番のマスが3）、獲得MGPは `$252`、くじ全体は `B218596347`、ラインは `L3` のように書きます。"),
+    ("notation.too_many_reveals", "{0}枚目のくじ: めくれるマスは4つまでです。"),
+    ("notation.out_of_order", "{0}枚目のくじ: `{1}` の順番が違います。めくったマス、獲得MGP、くじ全体、ラインの順に書いてください。"),
+    ("notation.unfinished_ticket", "{0}枚目のくじ: 最後のくじ以外には獲得MGPが必要です。"),
+    ("notation.incomplete_board", "{0}枚目のくじ: くじ全体はすべてのマスに数字が必要です。"),
+    ("notation.board_mismatch", "{0}枚目のくじ: くじ全体が{1}番のマスでめくった数字と合いません。"),
+    ("notation.board_without_payout", "{0}枚目のくじ: くじ全体は獲得MGPの後にしか入力できません。"),
+    ("notation.line_without_board", "{0}枚目のくじ: ラインはくじ全体と一緒にしか書けません。"),
+    ("notation.line_mismatch", "{0}枚目のくじ: ライン{1}はこのくじの獲得MGPと合いません。"),
+    ("notation.refused", "{0}枚目のくじ、`{1}`: {2}"),
+    ("mode.play", "通常"),
+    ("mode.simulate", "シミュレーション"),
+    ("mode.daily", "デイリー"),
+    ("admin.expired", "{0}の{1}ゲームを破棄しました。"),
+    ("admin.no_game", "{0}は進行中のゲームがありません。"),
+    ("admin.choose_user", "ユーザーを選んでください。"),
+    ("admin.presence", "{0}をプレイ中にしました。"),
+    ("admin.unknown_subcommand", "そのサブコマンドはまだありません。"),
+    ("admin.no_games", "進行中のゲームはありません。"),
+    ("admin.games", "進行中のゲーム: {0}件"),
+    ("admin.game", "{0}: {1}、{2}に開始、{3}/12マスめくり済み"),
+    ("admin.more", "…ほか{0}件。"),
+    ("check.payout_distribution", "獲得MGPの分布"),
+    ("check.payout_distribution_detail", "合計 {0}"),
+    ("check.daily_percentiles", "デイリーのパーセンタイル"),
+    ("check.daily_percentiles_detail", "一番良い日が {0}%"),
+    ("check.optimal_play", "最適なプレイ"),
+    ("check.optimal_play_detail", "{1}枚中{0}枚を遊んで平均 {2} MGP、ソルバーの期待値は {3}"),
+    ("check.panicked", "パニックしました: {0}"),
+    ("check.games", "進行中のゲーム"),
+    ("check.games_ok", "{0}件すべてルール通りです"),
+    ("check.games_broken", "{1}件中{0}件が壊れています: {2}"),
+    ("check.saving", "ゲームの保存"),
+    ("check.saving_ok", "{0}件すべて再起動後も残ります"),
+    ("check.saving_broken", "{0}件が再起動後に残りません: {1}"),
+    ("reload.unchanged", "設定ファイルに変更はありません。"),
+    ("reload.changed", "設定を読み直しました。変更: {0}。"),
+    ("reload.restart_only", "{0}は再起動後に反映されます。"),
+    ("reload.bad_config", "設定ファイルに問題があるため、何も変更していません:\n{0}"),
+    ("reload.no_admins", "新しい設定には管理者がいないため、誰も管理者コマンドを使えなくなります。何も変更していません。"),
+];
+
+const GERMAN: &[(&str, &str)] = &[
+    ("nyi", "Dieser Befehl ist noch nicht umgesetzt. Versuch es mit /help"),
+    ("help", "Verfügbare Befehle: `/ping`, `/shutdown`, `/help`, `/language`, `/minicact_play`, `/minicact_simulate`, `/minicact_daily`, `/minicact_quiz`, `/minicact_solve`, `/minicact_import`, `/minicact_export`, `/minicact_images`, `/minicact_accessibility`, `/minicact_settings`, `/minicact_config`."),
+    ("no_permission", "Dazu hast du keine Berechtigung."),
+    ("shutting_down", "Wird heruntergefahren..."),
+    ("shutdown.draining", "Es können keine neuen Spiele mehr gestartet werden. Der Bot fährt herunter, sobald alle Spiele beendet sind oder ihn eine Weile niemand benutzt hat..."),
+    ("shutdown.restarting", "Wird neu gestartet..."),
+    ("shutdown.immediate", "Sofort"),
+    ("shutdown.drain", "Sobald niemand mehr spielt"),
+    ("shutdown.restart", "Neu starten"),
+    ("shutdown.no_new_games", "Der Bot fährt gleich herunter, deshalb können gerade keine neuen Spiele gestartet werden. Versuch es noch einmal, wenn er wieder da ist!"),
+    ("shutdown.notice", "⚠️ Der Bot fährt herunter. Dein Spiel ist gespeichert, du kannst also weitermachen, sobald er wieder da ist."),
+    ("shutdown.notice_restart", "⚠️ Der Bot startet neu. Dein Spiel ist gespeichert, du kannst also gleich weitermachen."),
+    ("language.automatic", "Automatisch"),
+    ("language.set_user", "Nachrichten sind ab jetzt auf {0}."),
+    ("language.set_guild", "Nachrichten auf diesem Server sind ab jetzt auf {0}, außer jemand wählt eine eigene Sprache."),
+    ("language.reset_user", "Nachrichten folgen ab jetzt deiner Discord-Sprache."),
+    ("language.reset_guild", "Dieser Server hat keine eigene Sprache mehr."),
+    ("language.guild_only", "Nur Server können eine eigene Sprache haben."),
+
+    ("cmd.help", "Informationen zur Benutzung des Bots"),
+    ("cmd.ping", "Ein Ping-Befehl"),
+    ("cmd.shutdown", "Den Bot herunterfahren"),
+    ("opt.shutdown.mode", "Wie der Bot herunterfahren soll"),
+    ("cmd.language", "Wähle die Sprache des Bots"),
+    ("opt.language.language", "Die Sprache, oder Automatisch, um Discord zu folgen"),
+    ("opt.language.server", "Für den ganzen Server statt nur für dich festlegen"),
+    ("cmd.minicact_play", "Spiel das Spiel!"),
+    ("cmd.minicact_simulate", "Spiel ein simuliertes Spiel!"),
+    ("opt.minicact_simulate.seed", "Dieselben Lose wie bei einer früheren Simulation spielen"),
+    ("cmd.minicact_daily", "Spiel das heutige Rätsel, für alle auf diesem Server gleich!"),
+    ("cmd.minicact_quiz", "Übe, Felder und Linien zu wählen!"),
+    ("cmd.minicact_solve", "Eine Empfehlung für ein Los wie `53 17 92` bekommen"),
+    ("opt.minicact_solve.ticket", "Jedes aufgedeckte Feld (1-9, in Leserichtung), dann die Zahl, z. B. `53 17 92`"),
+    ("cmd.minicact_import", "Ein Spiel aus seiner Textversion fortsetzen"),
+    ("opt.minicact_import.game", "Die Textversion eines Spiels, aus /minicact_export oder vom Ende eines Spiels"),
+    ("cmd.minicact_export", "Die Textversion deines aktuellen Spiels zum Teilen oder späteren Importieren"),
+    ("cmd.minicact_images", "Empfehlungen als Bild statt als Textgrafik bekommen"),
+    ("opt.minicact_images.enabled", "Ob Bilder geschickt werden sollen"),
+    ("cmd.minicact_accessibility", "Beschriftungen statt Emoji, Farben und Grafiken verwenden"),
+    ("opt.minicact_accessibility.enabled", "Ob Beschriftungen verwendet werden sollen"),
+    ("cmd.minicact_config", "Ansehen oder ändern, wie der Bot auf diesem Server funktioniert (nur Server-Admins)"),
+    ("opt.minicact_config.announcement_channel", "Wo Ergebnisse angekündigt und das Tagesrätsel gepostet werden"),
+    ("opt.minicact_config.clear_announcement_channel", "Keinen Ankündigungskanal mehr verwenden"),
+    ("opt.minicact_config.leaderboard", "Ob die Ergebnisse aller beim Tagesrätsel gepostet werden"),
+    ("opt.minicact_config.allowed_commands", "Die einzigen hier erlaubten Befehle, durch Leerzeichen getrennt, oder \"all\""),
+    ("opt.minicact_config.add_admin_role", "Mitglieder mit dieser Rolle dürfen die Konfiguration ändern"),
+    ("opt.minicact_config.remove_admin_role", "Diese Rolle nicht mehr als Admin-Rolle behandeln"),
+    ("cmd.minicact_settings", "Deine Mini-Cactpot-Einstellungen ansehen oder ändern"),
+    ("opt.minicact_settings.objective", "Worauf die Empfehlungen abzielen sollen"),
+    ("opt.minicact_settings.multiplier", "MGP-Beträge damit multiplizieren, z. B. 1,5 für 50 % Bonus"),
+    ("opt.minicact_settings.graph", "Wie die Gewinnchancen angezeigt werden"),
+    ("opt.minicact_settings.language", "Die Sprache, oder Automatisch, um Server und Discord zu folgen"),
+    ("opt.minicact_settings.accessibility", "Ob Text statt Emoji, Farben und Diagrammen verwendet wird"),
+    ("opt.minicact_settings.announce", "Ergebnisse sofort nach Spielende bekanntgeben"),
+    ("opt.minicact_settings.public", "Spiele und Quizze für alle im Kanal sichtbar machen"),
+    ("cmd.minicact_benchmark", "Spiel viele Lose mit einer Strategie (nur für Admins)"),
+    ("opt.minicact_benchmark.strategy", "Wie jedes Los gespielt wird"),
+    ("opt.minicact_benchmark.tickets", "Wie viele Lose gespielt werden (Standard 100000)"),
+    ("opt.minicact_benchmark.rule", "Für die eigene Regel, z. B. `tiles=5,1,3 lines=4,8`"),
+    ("opt.minicact_benchmark.exact", "Jedes mögliche Los einmal spielen statt zufälliger"),
+    ("cmd.minicact_stats", "Wie der Bot seit dem Start benutzt wurde (nur für Admins)"),
+    ("cmd.admin", "Werkzeuge für Bot-Admins (nur für Admins)"),
+    ("opt.admin.games", "Alle laufenden Spiele auflisten, die ältesten zuerst"),
+    ("opt.admin.expire", "Das laufende Spiel von jemandem verwerfen"),
+    ("opt.admin.expire.user", "Wessen Spiel verworfen wird"),
+    ("opt.admin.reload", "Die Konfigurationsdatei ohne Neustart neu einlesen"),
+    ("opt.admin.presence", "Ändern, was der Bot gerade spielt"),
+    ("opt.admin.presence.text", "Weglassen, um zum Standard zurückzukehren"),
+    ("opt.admin.selfcheck", "Den Löser und alle laufenden Spiele überprüfen"),
+
+    ("position.0", "Oben links"),
+    ("position.1", "Oben Mitte"),
+    ("position.2", "Oben rechts"),
+    ("position.3", "Mitte links"),
+    ("position.4", "Mitte"),
+    ("position.5", "Mitte rechts"),
+    ("position.6", "Unten links"),
+    ("position.7", "Unten Mitte"),
+    ("position.8", "Unten rechts"),
+    ("line.0", "Untere Reihe"),
+    ("line.1", "Mittlere Reihe"),
+    ("line.2", "Obere Reihe"),
+    ("line.3", "\\ Diagonale"),
+    ("line.4", "Linke Spalte"),
+    ("line.5", "Mittlere Spalte"),
+    ("line.6", "Rechte Spalte"),
+    ("line.7", "/ Diagonale"),
+    ("line.0.inline", "untere Reihe"),
+    ("line.1.inline", "mittlere Reihe"),
+    ("line.2.inline", "obere Reihe"),
+    ("line.3.inline", "\\ Diagonale"),
+    ("line.4.inline", "linke Spalte"),
+    ("line.5.inline", "mittlere Spalte"),
+    ("line.6.inline", "rechte Spalte"),
+    ("line.7.inline", "/ Diagonale"),
+    ("board.covered", "verdeckt"),
+    ("board.row", "{0}: {1}"),
+    ("the_tile", "das Feld {0}"),
+    ("the_line", "die {0}"),
+
+    ("button.restore", "Fortsetzen"),
+    ("button.discard", "Verwerfen"),
+    ("button.continue", "Weiter"),
+    ("button.announce", "Ergebnis verkünden!"),
+    ("button.play_again", "Nochmal spielen?"),
+    ("button.next_question", "Nächste Frage"),
+    ("button.reveal_board", "Den Rest des Loses eingeben"),
+    ("button.replay", "Dieses Los nachspielen"),
+    ("button.undo", "Rückgängig"),
+    ("button.reset", "Zurücksetzen"),
+    ("button.reveal_number", "{0} eingeben"),
+    ("button.last", "Zuletzt: {0}"),
+    ("button.nothing_yet", "Noch nichts"),
+    ("note.recommended", "empfohlen"),
+    ("note.recommended_line", "empfohlene Linie"),
+    ("note.best", "am besten"),
+    ("note.best_line", "beste Linie"),
+    ("note.your_pick", "deine Wahl"),
+    ("menu.payout", "Gib deinen Gewinn ein!"),
+    ("menu.line", "Wähle deine Linie!"),
+
+    ("game.already_started", "{0} du hast schon ein Spiel angefangen.\nMöchtest du:\n> ↩ Dein vorheriges Spiel fortsetzen\n> 🔄 Es verwerfen und neu anfangen"),
+    ("game.enter_first_tile", "Gib das bereits aufgedeckte Feld ein:"),
+    ("game.enter_board_number", "Gib die Zahl unter {0} ein:"),
+    ("game.disabled", "Dieser Knopf ist gerade deaktiviert. Wenn du dich vertan hast, drück `Rückgängig` ↩ / `Zurücksetzen` 🔄"),
+    ("game.desync", "Abweichung erkannt und behoben. Bitte überprüfe, ob alles stimmt."),
+    ("game.finished", "Danke, dass du diesen Bot benutzt! Du kannst diese Nachricht schließen.\nDein Gesamtgewinn beträgt {0} MGP, das ist Perzentil {1}."),
+    ("game.share", "Teile dieses Spiel mit `{0}`"),
+    ("game.daily_note", "Deine Ergebnisse werden mit denen der anderen verglichen, sobald das nächste Rätsel erscheint."),
+    ("game.seed_note", "Diese Lose hatten den Seed `{0}`, mit `/minicact_simulate seed:{0}` kannst du sie nochmal spielen."),
+    ("game.ticket_paid", "Los {0}: die {1} brachte {2} MGP"),
+    ("game.announce", "{0} hat heute beim Mini Cactpot {1} MGP gewonnen, das ist Perzentil {2}!"),
+    ("game.stale", "{0} Diese Nachricht gehört zu einem Spiel, das du inzwischen ersetzt hast. Dein aktuelles Spiel hast du {1} angefangen.\nDrück ↩, um dein aktuelles Spiel hier fortzusetzen."),
+    ("game.removed", "{0} Dein Spiel wird nicht mehr verfolgt, entweder hast du es woanders beendet oder der Bot wurde neu gestartet.\nDu kannst diese Nachricht schließen. Mit /minicact_play startest du ein neues Spiel."),
+    ("daily.server_only", "Das Tagesrätsel teilen sich alle auf einem Server, deshalb kann es nur auf einem Server gespielt werden."),
+    ("daily.already_played", "Du hast das heutige Rätsel schon gespielt und {0} MGP gewonnen! Das nächste erscheint {1}."),
+    ("daily.already_started", "Du hast das heutige Rätsel schon angefangen, und es kann nur einmal gespielt werden. Das nächste erscheint {0}."),
+    ("daily.summary_title", "📅 Ergebnisse des Tagesrätsels vom {0}:"),
+    ("daily.summary_ticket", "Los {0}:"),
+    ("daily.summary_solver", "Der Löser hätte {0} MGP gewonnen ({1} / {2} / {3})."),
+    ("daily.summary_line", "{0}. {1}: {2} MGP mit {3}, {4} und {5}, mit {6}/12 Entscheidungen so gut wie die des Lösers"),
+    ("daily.summary_more", "…und {0} weitere."),
+    ("daily.post", "🎟 Das heutige Mini-Cactpot-Rätsel ist da! Alle auf diesem Server bekommen dieselben drei Lose, und jede Entscheidung ist endgültig.\nDie Ergebnisse aller werden {0} hier verglichen."),
+    ("button.play_daily", "Heutiges Rätsel spielen"),
+    ("strategy.optimal", "Optimal"),
+    ("strategy.random", "Zufällig"),
+    ("strategy.diagonals", "Diagonalen"),
+    ("strategy.rule", "Eigene Regel"),
+    ("benchmark.no_strategy", "Bitte wähl eine Strategie."),
+    ("benchmark.not_deterministic", "Diese Strategie trifft nicht immer dieselbe Entscheidung, deshalb kann sie nicht exakt bewertet werden."),
+    ("benchmark.exact", "Alle {0} möglichen Lose mit der Strategie {1} gespielt:\n{2} Durchschnittlicher Gewinn: genau {3} MGP\nStandardabweichung: {4} MGP\nDie optimale Strategie bringt im Schnitt genau {5} MGP, diese Strategie verliert also {6} MGP pro Los."),
+    ("benchmark.result", "{0} Lose mit der Strategie {1} gespielt:\n{2} Durchschnittlicher Gewinn: {3} MGP (± {4})\nStandardabweichung: {5} MGP\nDie optimale Strategie bringt im Schnitt genau {6} MGP"),
+    ("benchmark.optimal_error", ", das sind {0} Standardfehler Abstand."),
+    ("benchmark.loss", ", diese Strategie verliert also etwa {0} MGP pro Los."),
+    ("rule.bad_part", "`{0}` sollte aussehen wie `tiles=5,1,3` oder `lines=4,8`."),
+    ("rule.bad_key", "`{0}` sollte entweder `tiles` oder `lines` sein."),
+    ("rule.bad_number", "`{0}` sollte eine Zahl von 1 bis {1} sein."),
+    ("stats.since", "📊 Seit {0}:"),
+    ("stats.by_mode", "{0} ({1} gespielt, {2} simuliert, {3} täglich)"),
+    ("stats.started", "Gestartete Spiele: {0}"),
+    ("stats.finished", "Beendete Spiele: {0}"),
+    ("stats.desyncs", "Desyncs: {0} · Aufgedeckte Felder gedrückt: {1}"),
+    ("stats.errors", "Fehler: {0} Befehle, {1} Komponenten"),
+    ("stats.commands", "{0} Befehle"),
+    ("stats.components", "{0} Komponenten"),
+    ("stats.latency", "{0}, im Schnitt {1} ms, 95 % unter {2} ms"),
+    ("stats.latency_slow", "{0}, im Schnitt {1} ms, aber mehr als 5 % dauerten über 5 Sekunden"),
+    ("stats.responded", "Geantwortet auf {0} und {1}"),
+
+    ("rec.average", "Durchschnittlicher Gewinn: {0} MGP"),
+    ("rec.uncover", "Empfehlung: das Feld {0} aufdecken."),
+    ("rec.pick", "Empfehlung: die {0} wählen."),
+    ("rec.table", "Wahrscheinlichkeit jedes Gewinns:"),
+    ("rec.table_row", "{0} MGP: {1} %"),
+    ("summary.ticket", "Los {0}:"),
+    ("summary.line", "{0}: {1} MGP"),
+    ("replay.title", "Nachspiel von Los {0}:"),
+    ("replay.tile", "Du hast {0} aufgedeckt (im Schnitt {1} MGP), der Löser hätte {2} aufgedeckt (im Schnitt {3} MGP)."),
+    ("replay.line", "Du hast die {0} gewählt (im Schnitt {1} MGP), der Löser hätte die {2} gewählt (im Schnitt {3} MGP)."),
+    ("replay.every_line", "So viel hat jede Linie tatsächlich gebracht:"),
+
+    ("solve.pick", "Wähle die {0}."),
+    ("solve.uncover", "Deck als Nächstes das ❔-Feld auf."),
+    ("solve.nothing", "Es gibt noch nichts zu entscheiden. Füg das Feld hinzu, mit dem das Los kam, z. B. `53` für eine 3 unter Feld 5."),
+    ("import.done", "Dein Spiel wurde importiert! Drück ↩, um es fortzusetzen."),
+    ("import.replaced", "Es hat das Spiel ersetzt, das du {0} angefangen hast."),
+    ("import.daily_in_progress", "Du spielst noch das heutige Tagesrätsel, und dafür gibt es nur einen Versuch. Beende es zuerst und importiere dein Spiel danach."),
+    ("export.done", "Hier ist dein aktuelles Spiel, füg es in `/minicact_import` ein, um es fortzusetzen:\n`{0}`"),
+    ("export.empty", "In deinem Spiel steht noch nichts."),
+    ("export.none", "Du hast kein Spiel angefangen. Mit /minicact_play startest du eins."),
+    ("images.on", "🖼 Empfehlungen kommen ab jetzt mit einem Bild des Loses und der Gewinne."),
+    ("images.off", "📝 Empfehlungen verwenden ab jetzt die Textgrafik."),
+    ("accessibility.on", "Der Barrierefreiheitsmodus ist an. Knöpfe sind beschriftet, Empfehlungen werden ausgeschrieben und Wahrscheinlichkeiten als einfache Liste gezeigt."),
+    ("accessibility.off", "Der Barrierefreiheitsmodus ist aus."),
+    ("config.guild_only", "Nur Server haben eine Konfiguration."),
+    ("config.needs_admin", "Das können nur Server-Admins, also alle, die den Server verwalten dürfen oder eine seiner Admin-Rollen haben."),
+    ("config.command_disabled", "Dieser Befehl ist auf diesem Server ausgeschaltet."),
+    ("config.unknown_command", "{0} ist kein Befehl dieses Bots."),
+    ("config.title", "Konfiguration dieses Servers:"),
+    ("config.announcement_channel", "Ankündigungskanal: {0}"),
+    ("config.no_channel", "Keiner, Ergebnisse werden beim Spiel angekündigt"),
+    ("config.leaderboard", "Rangliste des Tagesrätsels: {0}"),
+    ("config.allowed_commands", "Erlaubte Befehle: {0}"),
+    ("config.all_commands", "Alle"),
+    ("config.admin_roles", "Admin-Rollen: {0}"),
+    ("config.no_roles", "Keine, nur Mitglieder, die den Server verwalten dürfen"),
+
+    ("settings.title", "Deine Einstellungen:"),
+    ("settings.objective", "Ziel: {0}"),
+    ("settings.multiplier", "MGP-Multiplikator: ×{0}"),
+    ("settings.graph", "Diagramm: {0}"),
+    ("settings.language", "Sprache: {0}"),
+    ("settings.accessibility", "Barrierefreier Modus: {0}"),
+    ("settings.announce", "Ergebnisse automatisch bekanntgeben: {0}"),
+    ("settings.public", "Öffentliche Spiele: {0}"),
+    ("settings.on", "An"),
+    ("settings.off", "Aus"),
+    ("objective.average", "Bester durchschnittlicher Gewinn"),
+    ("objective.jackpot", "Beste Chance auf 10.000 MGP"),
+    ("graph.text", "Text"),
+    ("graph.image", "Bild"),
+    ("rec.jackpot", "Chance auf 10.000 MGP: {0} %"),
+    ("game.not_yours", "Das ist das Spiel von jemand anderem. Starte mit /minicact_play dein eigenes!"),
+
+    ("quiz.tile_question", "Welches Feld würdest du als Nächstes aufdecken?"),
+    ("quiz.line_question", "Welche Linie würdest du wählen?"),
+    ("quiz.correct", "✅ Richtig! {0} bringt im Schnitt {1} MGP, besser geht es nicht."),
+    ("quiz.wrong", "❌ Nicht ganz. Du hast {0} gewählt (im Schnitt {1} MGP), am besten ist {2} (im Schnitt {3} MGP).\nDas kostet {4} MGP pro Los."),
+    ("quiz.score", "Punkte: {0}/{1} · Serie: {2} (beste {3}) · Verschenkte MGP: {4}"),
+    ("quiz.answered", "{0} Diese Frage wurde schon beantwortet, oder du hast inzwischen eine neue angefangen.\nDrück ➡ für eine neue Frage."),
+
+    ("error.duplicate_number", "{0} wurde auf diesem Los schon aufgedeckt."),
+    ("error.duplicate_position", "Dieses Feld wurde auf diesem Los schon aufgedeckt."),
+    ("error.invalid_number", "{0} ist keine Zahl auf dem Los."),
+    ("error.invalid_position", "{0} ist kein Feld auf dem Los."),
+    ("error.invalid_payout", "Diesen Gewinn gibt es nicht."),
+    ("error.payout_before_reveals", "Du musst 4 Felder aufdecken, bevor du einen Gewinn eingibst."),
+    ("error.game_over", "Alle 3 Lose wurden schon gespielt."),
+    ("error.expected_position", "Das Spiel hat erwartet, dass du ein Feld wählst."),
+    ("error.expected_number", "Das Spiel hat erwartet, dass du eine Zahl eingibst."),
+    ("error.expected_payout", "Das Spiel hat erwartet, dass du deinen Gewinn eingibst."),
+    ("error.expected_board", "Das Spiel hat erwartet, dass du den Rest des Loses eingibst."),
+    ("error.invalid_line", "{0} ist keine Linie auf dem Los."),
+    ("error.simulated", "Das ist ein simuliertes Spiel, das füllt der Bot für dich aus."),
+    ("error.not_simulated", "Nur in simulierten Spielen kann man direkt eine Linie wählen."),
+    ("error.board_already_revealed", "Der Rest dieses Loses wurde schon eingegeben."),
+    ("error.payout_mismatch", "Keine Linie auf diesem Los bringt {0} MGP. Überprüf die eingegebenen Zahlen oder mach den Gewinn rückgängig, um ihn zu korrigieren."),
+    ("error.unexpected", "Das Spiel hat {0} nicht erwartet."),
+    ("error.broken_invariant", "Das Spiel ist in einem unmöglichen Zustand: {0}"),
+    ("notation.empty", "Es gibt nichts zu lesen. Ein Los sieht so aus: `53 17 92 34 $1800`."),
+    ("notation.too_many_tickets", "Es gibt nur 3 Lose am Tag, aber hier sind es {0}."),
+    ("notation.bad_token", "Los {0}: `{1}` konnte nicht gelesen werden. Aufgedeckte Felder sehen aus wie `53` (eine 3 unter Feld 5), Gewinne wie `$252`, ganze Lose wie `B218596347` und Linien wie `L3`."),
+    ("notation.too_many_reveals", "Los {0}: Auf einem Los können nur 4 Felder aufgedeckt werden."),
+    ("notation.out_of_order", "Los {0}: `{1}` steht an der falschen Stelle. Erst kommen die aufgedeckten Felder, dann der Gewinn, dann das ganze Los, dann die Linie."),
+    ("notation.unfinished_ticket", "Los {0}: Jedes Los vor dem letzten braucht einen Gewinn."),
+    ("notation.incomplete_board", "Los {0}: Das ganze Los braucht auf jedem Feld eine Zahl."),
+    ("notation.board_mismatch", "Los {0}: Das Los passt nicht zu der Zahl, die unter Feld {1} aufgedeckt wurde."),
+    ("notation.board_without_payout", "Los {0}: Das ganze Los kann erst nach dem Gewinn eingegeben werden."),
+    ("notation.line_without_board", "Los {0}: Eine Linie geht nur zusammen mit dem ganzen Los."),
+    ("notation.line_mismatch", "Los {0}: Linie {1} bringt nicht den Gewinn, den das Los angibt."),
+    ("notation.refused", "Los {0}, `{1}`: {2}"),
+    ("mode.play", "normales"),
+    ("mode.simulate", "simuliertes"),
+    ("mode.daily", "tägliches"),
+    ("admin.expired", "{1} Spiel von {0} verworfen."),
+    ("admin.no_game", "{0} hat kein laufendes Spiel."),
+    ("admin.choose_user", "Bitte wähl einen Benutzer."),
+    ("admin.presence", "Spielt jetzt {0}."),
+    ("admin.unknown_subcommand", "Diesen Unterbefehl gibt es noch nicht."),
+    ("admin.no_games", "Niemand hat ein laufendes Spiel."),
+    ("admin.games", "{0} laufende Spiele:"),
+    ("admin.game", "{0}: {1}, gestartet {2}, {3}/12 Felder aufgedeckt"),
+    ("admin.more", "…und {0} weitere."),
+    ("check.payout_distribution", "Gewinnverteilung"),
+    ("check.payout_distribution_detail", "ergibt zusammen {0}"),
+    ("check.daily_percentiles", "Tägliche Perzentile"),
+    ("check.daily_percentiles_detail", "der beste Tag liegt bei {0} %"),
+    ("check.optimal_play", "Optimales Spiel"),
+    ("check.optimal_play_detail", "{0} von {1} Losen gespielt, im Schnitt {2} MGP, der Löser erwartet {3}"),
+    ("check.panicked", "abgestürzt: {0}"),
+    ("check.games", "Laufende Spiele"),
+    ("check.games_ok", "alle {0} halten sich an die Regeln"),
+    ("check.games_broken", "{0} von {1} sind kaputt: {2}"),
+    ("check.saving", "Spiele speichern"),
+    ("check.saving_ok", "alle {0} würden einen Neustart überstehen"),
+    ("check.saving_broken", "{0} würden einen Neustart nicht überstehen: {1}"),
+    ("reload.unchanged", "In der Konfigurationsdatei hat sich nichts geändert."),
+    ("reload.changed", "Konfiguration neu geladen, geändert: {0}."),
+    ("reload.restart_only", " {0} wirkt erst nach einem Neustart."),
+    ("reload.bad_config", "Die Konfigurationsdatei hat ein Problem, deshalb wurde nichts geändert:\n{0}"),
+    ("reload.no_admins", "Die neue Konfiguration hat keine Admins, dann könnte niemand mehr Admin-Befehle benutzen. Es wurde nichts geändert."),
+];
+
+const FRENCH: &[(&str, &str)] = &[
+    ("nyi", "Cette commande n'est pas encore disponible. Essayez /help"),
+    ("help", "Commandes disponibles : `/ping`, `/shutdown`, `/help`, `/language`, `/minicact_play`, `/minicact_simulate`, `/minicact_daily`, `/minicact_quiz`, `/minicact_solve`, `/minicact_import`, `/minicact_export`, `/minicact_images`, `/minicact_accessibility`, `/minicact_settings`, `/minicact_config`."),
+    ("no_permission", "Vous n'avez pas la permission."),
+    ("shutting_down", "Arrêt en cours..."),
+    ("shutdown.draining", "Plus aucune partie ne peut être lancée. Le bot s'arrêtera dès que toutes les parties seront terminées, ou que personne ne l'aura utilisé pendant un moment..."),
+    ("shutdown.restarting", "Redémarrage en cours..."),
+    ("shutdown.immediate", "Tout de suite"),
+    ("shutdown.drain", "Quand plus personne ne joue"),
+    ("shutdown.restart", "Redémarrer"),
+    ("shutdown.no_new_games", "Le bot va bientôt s'arrêter, aucune nouvelle partie ne peut donc être lancée pour l'instant. Réessayez quand il sera de retour !"),
+    ("shutdown.notice", "⚠️ Le bot s'arrête. Votre partie est sauvegardée, vous pourrez la reprendre là où vous en étiez à son retour."),
+    ("shutdown.notice_restart", "⚠️ Le bot redémarre. Votre partie est sauvegardée, vous pourrez la reprendre là où vous en étiez dans un instant."),
+    ("language.automatic", "Automatique"),
+    ("language.set_user", "Les messages seront désormais en {0}."),
+    ("language.set_guild", "Les messages sur ce serveur seront désormais en {0}, sauf pour ceux qui ont choisi leur propre langue."),
+    ("language.reset_user", "Les messages suivront désormais la langue de votre Discord."),
+    ("language.reset_guild", "Ce serveur n'a plus de langue propre."),
+    ("language.guild_only", "Seuls les serveurs peuvent avoir leur propre langue."),
+
+    ("cmd.help", "Comment utiliser le bot"),
+    ("cmd.ping", "Une commande ping"),
+    ("cmd.shutdown", "Arrêter le bot"),
+    ("opt.shutdown.mode", "Comment arrêter le bot"),
+    ("cmd.language", "Choisir la langue du bot"),
+    ("opt.language.language", "La langue à utiliser, ou Automatique pour suivre Discord"),
+    ("opt.language.server", "La définir pour tout le serveur plutôt que pour vous seul"),
+    ("cmd.minicact_play", "Jouez au jeu !"),
+    ("cmd.minicact_simulate", "Jouez une partie simulée !"),
+    ("opt.minicact_simulate.seed", "Rejouer les mêmes tickets qu'une simulation précédente"),
+    ("cmd.minicact_daily", "Jouez au puzzle du jour, le même pour tout le serveur !"),
+    ("cmd.minicact_quiz", "Entraînez-vous à choisir les cases et les lignes !"),
+    ("cmd.minicact_solve", "Obtenir une recommandation pour un ticket écrit comme `53 17 92`"),
+    ("opt.minicact_solve.ticket", "Chaque case révélée (1-9, sens de lecture) puis son chiffre, par ex. `53 17 92`"),
+    ("cmd.minicact_import", "Reprendre une partie à partir de sa version texte"),
+    ("opt.minicact_import.game", "La version texte d'une partie, venant de /minicact_export ou de la fin d'une partie"),
+    ("cmd.minicact_export", "Obtenir la version texte de votre partie, pour la partager ou la reprendre plus tard"),
+    ("cmd.minicact_images", "Recevoir les recommandations en image plutôt qu'en graphique texte"),
+    ("opt.minicact_images.enabled", "Envoyer des images ou non"),
+    ("cmd.minicact_accessibility", "Utiliser des libellés écrits au lieu d'emoji, de couleurs et de graphiques"),
+    ("opt.minicact_accessibility.enabled", "Utiliser des libellés écrits ou non"),
+    ("cmd.minicact_config", "Voir ou modifier le fonctionnement du bot sur ce serveur (admins du serveur uniquement)"),
+    ("opt.minicact_config.announcement_channel", "Où annoncer les résultats et publier le puzzle du jour"),
+    ("opt.minicact_config.clear_announcement_channel", "Ne plus utiliser de salon d'annonces"),
+    ("opt.minicact_config.leaderboard", "Publier ou non les résultats de tous au puzzle du jour"),
+    ("opt.minicact_config.allowed_commands", "Les seules commandes utilisables ici, séparées par des espaces, ou \"all\""),
+    ("opt.minicact_config.add_admin_role", "Permettre aux membres avec ce rôle de changer la configuration"),
+    ("opt.minicact_config.remove_admin_role", "Ne plus traiter ce rôle comme un rôle admin"),
+    ("cmd.minicact_settings", "Voir ou modifier vos paramètres du Mini Cactpot"),
+    ("opt.minicact_settings.objective", "Ce que les recommandations doivent viser"),
+    ("opt.minicact_settings.multiplier", "Multiplier les montants de MGP, par ex. 1,5 pour un bonus de 50 %"),
+    ("opt.minicact_settings.graph", "Comment les chances de gain sont affichées"),
+    ("opt.minicact_settings.language", "La langue à utiliser, ou Automatique pour suivre le serveur et Discord"),
+    ("opt.minicact_settings.accessibility", "Utiliser du texte au lieu des emoji, couleurs et graphiques"),
+    ("opt.minicact_settings.announce", "Annoncer vos résultats dès la fin d'une partie"),
+    ("opt.minicact_settings.public", "Montrer vos parties et quiz à tout le salon"),
+    ("cmd.minicact_benchmark", "Jouer beaucoup de tickets avec une stratégie (admins uniquement)"),
+    ("opt.minicact_benchmark.strategy", "Comment jouer chaque ticket"),
+    ("opt.minicact_benchmark.tickets", "Combien de tickets jouer (100000 par défaut)"),
+    ("opt.minicact_benchmark.rule", "Pour la règle personnalisée, comme `tiles=5,1,3 lines=4,8`"),
+    ("opt.minicact_benchmark.exact", "Jouer chaque ticket possible une fois au lieu de tickets au hasard"),
+    ("cmd.minicact_stats", "Comment le bot a été utilisé depuis son démarrage (admins uniquement)"),
+    ("cmd.admin", "Outils d'administration du bot (admins uniquement)"),
+    ("opt.admin.games", "Lister les parties en cours, les plus anciennes d'abord"),
+    ("opt.admin.expire", "Supprimer la partie en cours de quelqu'un"),
+    ("opt.admin.expire.user", "La personne dont la partie est supprimée"),
+    ("opt.admin.reload", "Relire le fichier de configuration sans redémarrer"),
+    ("opt.admin.presence", "Changer ce que le bot affiche comme jeu en cours"),
+    ("opt.admin.presence.text", "Laissez vide pour revenir au texte par défaut"),
+    ("opt.admin.selfcheck", "Vérifier le solveur et toutes les parties en cours"),
+
+    ("position.0", "En haut à gauche"),
+    ("position.1", "En haut au milieu"),
+    ("position.2", "En haut à droite"),
+    ("position.3", "Au milieu à gauche"),
+    ("position.4", "Au centre"),
+    ("position.5", "Au milieu à droite"),
+    ("position.6", "En bas à gauche"),
+    ("position.7", "En bas au milieu"),
+    ("position.8", "En bas à droite"),
+    ("line.0", "Ligne du bas"),
+    ("line.1", "Ligne du milieu"),
+    ("line.2", "Ligne du haut"),
+    ("line.3", "Diagonale \\"),
+    ("line.4", "Colonne de gauche"),
+    ("line.5", "Colonne du milieu"),
+    ("line.6", "Colonne de droite"),
+    ("line.7", "Diagonale /"),
+    ("line.0.inline", "ligne du bas"),
+    ("line.1.inline", "ligne du milieu"),
+    ("line.2.inline", "ligne du haut"),
+    ("line.3.inline", "diagonale \\"),
+    ("line.4.inline", "colonne de gauche"),
+    ("line.5.inline", "colonne du milieu"),
+    ("line.6.inline", "colonne de droite"),
+    ("line.7.inline", "diagonale /"),
+    ("board.covered", "cachée"),
+    ("board.row", "{0} : {1}"),
+    ("the_tile", "la case {0}"),
+    ("the_line", "la {0}"),
+
+    ("button.restore", "Reprendre"),
+    ("button.discard", "Abandonner"),
+    ("button.continue", "Continuer"),
+    ("button.announce", "Annoncez vos résultats !"),
+    ("button.play_again", "Rejouer ?"),
+    ("button.next_question", "Question suivante"),
+    ("button.reveal_board", "Saisir le reste du ticket"),
+    ("button.replay", "Revoir ce ticket"),
+    ("button.undo", "Annuler"),
+    ("button.reset", "Recommencer"),
+    ("button.reveal_number", "Révéler {0}"),
+    ("button.last", "Dernier : {0}"),
+    ("button.nothing_yet", "Rien pour l'instant"),
+    ("note.recommended", "recommandée"),
+    ("note.recommended_line", "ligne recommandée"),
+    ("note.best", "meilleure"),
+    ("note.best_line", "meilleure ligne"),
+    ("note.your_pick", "votre choix"),
+    ("menu.payout", "Saisissez votre gain !"),
+    ("menu.line", "Choisissez votre ligne !"),
+
+    ("game.already_started", "{0} vous avez déjà une partie en cours.\nVoulez-vous :\n> ↩ Reprendre votre partie précédente\n> 🔄 L'abandonner et recommencer à zéro"),
+    ("game.enter_first_tile", "Saisissez la case déjà révélée :"),
+    ("game.enter_board_number", "Saisissez le chiffre sous {0} :"),
+    ("game.disabled", "Ce bouton est désactivé pour le moment. En cas d'erreur, appuyez sur `Annuler` ↩ / `Recommencer` 🔄"),
+    ("game.desync", "désynchronisation détectée et corrigée. Vérifiez que tout est correct."),
+    ("game.finished", "Merci d'avoir utilisé ce bot ! Vous pouvez fermer ce message.\nVotre gain total est de {0} MGP, soit le percentile {1}."),
+    ("game.share", "Partagez cette partie avec `{0}`"),
+    ("game.daily_note", "Vos résultats seront comparés à ceux des autres quand le prochain puzzle sera publié."),
+    ("game.seed_note", "Ces tickets avaient la graine `{0}`, utilisez `/minicact_simulate seed:{0}` pour les rejouer."),
+    ("game.ticket_paid", "Ticket {0} : la {1} a rapporté {2} MGP"),
+    ("game.announce", "{0} a gagné {1} MGP au Mini Cactpot aujourd'hui, soit le percentile {2} !"),
+    ("game.stale", "{0} Ce message vient d'une partie que vous avez remplacée depuis. Votre partie actuelle est celle commencée {1}.\nAppuyez sur ↩ pour la continuer ici."),
+    ("game.removed", "{0} Votre partie n'est plus suivie, soit vous l'avez terminée ailleurs, soit le bot a redémarré.\nVous pouvez fermer ce message. Utilisez /minicact_play pour commencer une nouvelle partie."),
+    ("daily.server_only", "Le puzzle du jour est partagé par tout le serveur, il ne peut donc se jouer que sur un serveur."),
+    ("daily.already_played", "Vous avez déjà joué le puzzle du jour et gagné {0} MGP ! Le prochain sera publié {1}."),
+    ("daily.already_started", "Vous avez déjà commencé le puzzle du jour, et il ne peut être joué qu'une fois. Le prochain sera publié {0}."),
+    ("daily.summary_title", "📅 Résultats du puzzle du jour du {0} :"),
+    ("daily.summary_ticket", "Ticket {0} :"),
+    ("daily.summary_solver", "Le solveur aurait gagné {0} MGP ({1} / {2} / {3})."),
+    ("daily.summary_line", "{0}. {1} : {2} MGP avec la {3}, la {4} et la {5}, avec {6}/12 choix aussi bons que ceux du solveur"),
+    ("daily.summary_more", "…et {0} de plus."),
+    ("daily.post", "🎟 Le puzzle Mini Cactpot du jour est disponible ! Tout le monde sur ce serveur a les trois mêmes tickets, et chaque choix est définitif.\nLes résultats de tous seront comparés ici {0}."),
+    ("button.play_daily", "Jouer au puzzle du jour"),
+    ("strategy.optimal", "Optimale"),
+    ("strategy.random", "Au hasard"),
+    ("strategy.diagonals", "Diagonales"),
+    ("strategy.rule", "Règle personnalisée"),
+    ("benchmark.no_strategy", "Veuillez choisir une stratégie."),
+    ("benchmark.not_deterministic", "Cette stratégie ne fait pas toujours le même choix, elle ne peut donc pas être évaluée exactement."),
+    ("benchmark.exact", "Les {0} tickets possibles ont été joués avec la stratégie {1} :\n{2} Gain moyen : exactement {3} MGP\nÉcart type : {4} MGP\nLa stratégie optimale rapporte en moyenne exactement {5} MGP, cette stratégie perd donc {6} MGP par ticket."),
+    ("benchmark.result", "{0} tickets joués avec la stratégie {1} :\n{2} Gain moyen : {3} MGP (± {4})\nÉcart type : {5} MGP\nLa stratégie optimale rapporte en moyenne exactement {6} MGP"),
+    ("benchmark.optimal_error", ", soit un écart de {0} erreurs types."),
+    ("benchmark.loss", ", cette stratégie perd donc environ {0} MGP par ticket."),
+    ("rule.bad_part", "`{0}` devrait ressembler à `tiles=5,1,3` ou `lines=4,8`."),
+    ("rule.bad_key", "`{0}` devrait être `tiles` ou `lines`."),
+    ("rule.bad_number", "`{0}` devrait être un nombre de 1 à {1}."),
+    ("stats.since", "📊 Depuis {0} :"),
+    ("stats.by_mode", "{0} ({1} normales, {2} simulées, {3} du jour)"),
+    ("stats.started", "Parties commencées : {0}"),
+    ("stats.finished", "Parties terminées : {0}"),
+    ("stats.desyncs", "Désynchronisations : {0} · Cases déjà révélées pressées : {1}"),
+    ("stats.errors", "Erreurs : {0} commandes, {1} composants"),
+    ("stats.commands", "{0} commandes"),
+    ("stats.components", "{0} composants"),
+    ("stats.latency", "{0}, en moyenne {1} ms, 95 % en moins de {2} ms"),
+    ("stats.latency_slow", "{0}, en moyenne {1} ms, mais plus de 5 % ont pris plus de 5 secondes"),
+    ("stats.responded", "Réponses à {0} et {1}"),
+
+    ("rec.average", "Gain moyen : {0} MGP"),
+    ("rec.uncover", "Recommandé : révéler la case {0}."),
+    ("rec.pick", "Recommandé : choisir la {0}."),
+    ("rec.table", "Probabilité de chaque gain :"),
+    ("rec.table_row", "{0} MGP : {1} %"),
+    ("summary.ticket", "Ticket {0} :"),
+    ("summary.line", "{0} : {1} MGP"),
+    ("replay.title", "Retour sur le ticket {0} :"),
+    ("replay.tile", "Vous avez révélé {0} (en moyenne {1} MGP), le solveur aurait révélé {2} (en moyenne {3} MGP)."),
+    ("replay.line", "Vous avez choisi la {0} (en moyenne {1} MGP), le solveur aurait choisi la {2} (en moyenne {3} MGP)."),
+    ("replay.every_line", "Voici ce que chaque ligne a vraiment rapporté :"),
+
+    ("solve.pick", "Choisissez la {0}."),
+    ("solve.uncover", "Révélez ensuite la case ❔."),
+    ("solve.nothing", "Il n'y a encore rien à décider. Ajoutez la case déjà révélée sur le ticket, comme `53` pour un 3 sous la case 5."),
+    ("import.done", "Partie importée ! Appuyez sur ↩ pour la continuer."),
+    ("import.replaced", "Elle remplace la partie commencée {0}."),
+    ("import.daily_in_progress", "Votre puzzle du jour est toujours en cours, et il n'y a qu'un seul essai. Terminez-le d'abord, puis importez votre partie."),
+    ("export.done", "Voici votre partie actuelle, collez-la dans `/minicact_import` pour la reprendre :\n`{0}`"),
+    ("export.empty", "Votre partie est encore vide."),
+    ("export.none", "Vous n'avez pas de partie en cours. Utilisez /minicact_play pour en commencer une."),
+    ("images.on", "🖼 Les recommandations seront désormais accompagnées d'une image du ticket et des gains."),
+    ("images.off", "📝 Les recommandations utiliseront désormais le graphique texte."),
+    ("accessibility.on", "Le mode accessibilité est activé. Les boutons auront des libellés écrits, les recommandations seront rédigées et les probabilités affichées en simple liste."),
+    ("accessibility.off", "Le mode accessibilité est désactivé."),
+    ("config.guild_only", "Seuls les serveurs ont une configuration."),
+    ("config.needs_admin", "Seuls les admins du serveur peuvent faire ça, c'est-à-dire ceux qui peuvent gérer le serveur ou qui ont un de ses rôles admin."),
+    ("config.command_disabled", "Cette commande est désactivée sur ce serveur."),
+    ("config.unknown_command", "{0} n'est pas une commande de ce bot."),
+    ("config.title", "Configuration de ce serveur :"),
+    ("config.announcement_channel", "Salon d'annonces : {0}"),
+    ("config.no_channel", "Aucun, les résultats sont annoncés là où se trouve la partie"),
+    ("config.leaderboard", "Classement du puzzle du jour : {0}"),
+    ("config.allowed_commands", "Commandes autorisées : {0}"),
+    ("config.all_commands", "Toutes"),
+    ("config.admin_roles", "Rôles admin : {0}"),
+    ("config.no_roles", "Aucun, seulement les membres qui peuvent gérer le serveur"),
+
+    ("settings.title", "Vos paramètres :"),
+    ("settings.objective", "Objectif : {0}"),
+    ("settings.multiplier", "Multiplicateur de MGP : ×{0}"),
+    ("settings.graph", "Graphique : {0}"),
+    ("settings.language", "Langue : {0}"),
+    ("settings.accessibility", "Mode accessibilité : {0}"),
+    ("settings.announce", "Annoncer les résultats automatiquement : {0}"),
+    ("settings.public", "Parties publiques : {0}"),
+    ("settings.on", "Activé"),
+    ("settings.off", "Désactivé"),
+    ("objective.average", "Meilleur gain moyen"),
+    ("objective.jackpot", "Meilleure chance de 10 000 MGP"),
+    ("graph.text", "Texte"),
+    ("graph.image", "Image"),
+    ("rec.jackpot", "Chance de 10 000 MGP : {0} %"),
+    ("game.not_yours", "C'est la partie de quelqu'un d'autre. Utilisez /minicact_play pour commencer la vôtre !"),
+
+    ("quiz.tile_question", "Quelle case révéleriez-vous ensuite ?"),
+    ("quiz.line_question", "Quelle ligne choisiriez-vous ?"),
+    ("quiz.correct", "✅ Bonne réponse ! Choisir {0} rapporte en moyenne {1} MGP, on ne peut pas faire mieux."),
+    ("quiz.wrong", "❌ Pas tout à fait. Vous avez choisi {0} (en moyenne {1} MGP), le meilleur choix est {2} (en moyenne {3} MGP).\nCela coûte {4} MGP par ticket."),
+    ("quiz.score", "Score : {0}/{1} · Série : {2} (record {3}) · MGP perdus : {4}"),
+    ("quiz.answered", "{0} Cette question a déjà reçu une réponse, ou vous en avez commencé une autre depuis.\nAppuyez sur ➡ pour une nouvelle question."),
+
+    ("error.duplicate_number", "Le {0} a déjà été révélé sur ce ticket."),
+    ("error.duplicate_position", "Cette case a déjà été révélée sur ce ticket."),
+    ("error.invalid_number", "{0} n'est pas un chiffre du ticket."),
+    ("error.invalid_position", "{0} n'est pas une case du ticket."),
+    ("error.invalid_payout", "Ce gain n'est pas possible."),
+    ("error.payout_before_reveals", "Vous devez révéler 4 cases avant de saisir un gain."),
+    ("error.game_over", "Les 3 tickets ont déjà été joués."),
+    ("error.expected_position", "Le jeu attendait que vous choisissiez une case."),
+    ("error.expected_number", "Le jeu attendait que vous saisissiez un chiffre."),
+    ("error.expected_payout", "Le jeu attendait que vous saisissiez votre gain."),
+    ("error.expected_board", "Le jeu attendait que vous saisissiez le reste du ticket."),
+    ("error.invalid_line", "{0} n'est pas une ligne du ticket."),
+    ("error.simulated", "C'est une partie simulée, le bot s'en charge pour vous."),
+    ("error.not_simulated", "Seules les parties simulées permettent de choisir une ligne directement."),
+    ("error.board_already_revealed", "Le reste de ce ticket a déjà été saisi."),
+    ("error.payout_mismatch", "Aucune ligne de ce ticket ne rapporte {0} MGP. Vérifiez les chiffres saisis, ou annulez pour corriger votre gain."),
+    ("error.unexpected", "Le jeu ne s'attendait pas à {0}."),
+    ("error.broken_invariant", "La partie est dans un état impossible : {0}"),
+    ("notation.empty", "Il n'y a rien à lire. Un ticket s'écrit comme `53 17 92 34 $1800`."),
+    ("notation.too_many_tickets", "Il n'y a que 3 tickets par jour, mais il y en a {0} ici."),
+    ("notation.bad_token", "Ticket {0} : impossible de lire `{1}`. Les cases révélées s'écrivent comme `53` (un 3 sous la case 5), les gains comme `$252`, les tickets complets comme `B218596347` et les lignes comme `L3`."),
+    ("notation.too_many_reveals", "Ticket {0} : on ne peut révéler que 4 cases par ticket."),
+    ("notation.out_of_order", "Ticket {0} : `{1}` n'est pas à sa place. Chaque ticket donne les cases révélées, puis le gain, puis le ticket complet, puis la ligne."),
+    ("notation.unfinished_ticket", "Ticket {0} : chaque ticket avant le dernier doit avoir un gain."),
+    ("notation.incomplete_board", "Ticket {0} : le ticket complet doit avoir un chiffre sur chaque case."),
+    ("notation.board_mismatch", "Ticket {0} : le ticket complet ne correspond pas au chiffre révélé sous la case {1}."),
+    ("notation.board_without_payout", "Ticket {0} : le ticket complet ne peut être saisi qu'après le gain."),
+    ("notation.line_without_board", "Ticket {0} : une ligne ne peut être donnée qu'avec le ticket complet."),
+    ("notation.line_mismatch", "Ticket {0} : la ligne {1} ne rapporte pas le gain indiqué sur le ticket."),
+    ("notation.refused", "Ticket {0}, `{1}` : {2}"),
+    ("mode.play", "normale"),
+    ("mode.simulate", "simulée"),
+    ("mode.daily", "du jour"),
+    ("admin.expired", "Partie {1} de {0} supprimée."),
+    ("admin.no_game", "{0} n'a pas de partie en cours."),
+    ("admin.choose_user", "Veuillez choisir une personne."),
+    ("admin.presence", "Joue maintenant à {0}."),
+    ("admin.unknown_subcommand", "Cette sous-commande n'existe pas encore."),
+    ("admin.no_games", "Personne n'a de partie en cours."),
+    ("admin.games", "{0} parties en cours :"),
+    ("admin.game", "{0} : {1}, commencée {2}, {3}/12 cases révélées"),
+    ("admin.more", "…et {0} de plus."),
+    ("check.payout_distribution", "Répartition des gains"),
+    ("check.payout_distribution_detail", "totalise {0}"),
+    ("check.daily_percentiles", "Centiles du jour"),
+    ("check.daily_percentiles_detail", "le meilleur jour est à {0} %"),
+    ("check.optimal_play", "Jeu optimal"),
+    ("check.optimal_play_detail", "{0} tickets joués sur {1} pour {2} MGP en moyenne, le solveur attend {3}"),
+    ("check.panicked", "plantage : {0}"),
+    ("check.games", "Parties en cours"),
+    ("check.games_ok", "les {0} respectent les règles"),
+    ("check.games_broken", "{0} sur {1} sont cassées : {2}"),
+    ("check.saving", "Sauvegarde des parties"),
+    ("check.saving_ok", "les {0} survivraient à un redémarrage"),
+    ("check.saving_broken", "{0} ne survivraient pas à un redémarrage : {1}"),
+    ("reload.unchanged", "Rien n'a changé dans le fichier de configuration."),
+    ("reload.changed", "Configuration rechargée, changé : {0}."),
+    ("reload.restart_only", " {0} ne prend effet qu'après un redémarrage."),
+    ("reload.bad_config", "Le fichier de configuration a un problème, rien n'a donc été changé :\n{0}"),
+    ("reload.no_admins", "La nouvelle configuration n'a aucun admin, plus personne ne pourrait utiliser les commandes d'admin. Rien n'a été changé."),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    // Falling back to English keeps the bot working, but it shouldn't be how a missing translation gets noticed.
+    #[test]
+    fn every_catalog_has_every_key() {
+        let english = ENGLISH.iter().map(|(key, _)| *key).collect::<HashSet<&str>>();
+        assert_eq!(english.len(), ENGLISH.len(), "the English catalog has a key twice");
+        for locale in Locale::ALL {
+            let keys = locale.catalog().iter().map(|(key, _)| *key).collect::<HashSet<&str>>();
+            assert_eq!(keys.len(), locale.catalog().len(), "the {:?} catalog has a key twice", locale);
+            assert!(english.is_subset(&keys), "{:?} is missing {:?}", locale, english.difference(&keys).collect::<Vec<_>>());
+            assert!(keys.is_subset(&english), "{:?} has extra keys {:?}", locale, keys.difference(&english).collect::<Vec<_>>());
+        }
+    }
+}
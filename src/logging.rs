@@ -0,0 +1,368 @@
+use crate::settings::or_default;
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::{Arguments, Display, Formatter};
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::sync::Mutex;
+
+use serenity::model::id::UserId;
+use serenity::model::user::User;
+
+use config::Config;
+
+use chrono::Local;
+
+use lazy_static::lazy_static;
+
+// Everything the bot prints goes through error!, warn!, info! and debug!, which work like println!.
+// How much gets printed, in what format and where is all set from the config file, see LogSettings.
+
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)*) => { $crate::logging::log($crate::logging::Level::Error, module_path!(), format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)*) => { $crate::logging::log($crate::logging::Level::Warn, module_path!(), format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => { $crate::logging::log($crate::logging::Level::Info, module_path!(), format_args!($($arg)*)) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => { $crate::logging::log($crate::logging::Level::Debug, module_path!(), format_args!($($arg)*)) };
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl Level {
+    fn from_name(name: &str) -> Option<Level> {
+        match name.to_ascii_lowercase().as_str() {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            _ => None
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Format {
+    Text,
+    // One JSON object per line, for anything that reads logs automatically.
+    Json,
+}
+
+struct Logger {
+    level: Level,
+    // Module path prefixes like "minicact::daily", each with its own level. The longest match wins.
+    filters: Vec<(String, Level)>,
+    format: Format,
+    file: Option<LogFile>,
+}
+
+impl Logger {
+    fn level_for(&self, module: &str) -> Level {
+        self.filters.iter()
+            .filter(|(prefix, _)| module == prefix || module.starts_with(format!("{}::", prefix).as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.level, |(_, level)| *level)
+    }
+}
+
+// Once the file gets bigger than max_bytes it becomes `path.1`, the old `path.1` becomes `path.2`, and so on,
+// keeping max_files old ones around.
+struct LogFile {
+    path: String,
+    file: File,
+    size: u64,
+    max_bytes: u64,
+    max_files: u64,
+}
+
+impl LogFile {
+    fn open(path: String, max_bytes: u64, max_files: u64) -> std::io::Result<LogFile> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(LogFile { path, file, size, max_bytes, max_files })
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        if self.size > 0 && self.size + line.len() as u64 + 1 > self.max_bytes {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{}", line)?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if self.max_files == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            // The oldest one just gets overwritten.
+            for i in (1..self.max_files).rev() {
+                let from = format!("{}.{}", self.path, i);
+                if std::path::Path::new(&from).exists() {
+                    std::fs::rename(&from, format!("{}.{}", self.path, i + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, format!("{}.1", self.path))?;
+            self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        }
+        self.size = 0;
+        Ok(())
+    }
+}
+
+// std::sync instead of tokio, since this gets used from code that isn't async too.
+lazy_static! {
+    static ref LOGGER: Mutex<Logger> = Mutex::new(Logger {
+        level: Level::Info,
+        filters: vec![],
+        format: Format::Text,
+        file: None,
+    });
+}
+
+// None unless `log_hash_user_ids` is on, in which case it's the salt. Kept apart from LOGGER, since it's needed while formatting a message.
+lazy_static! {
+    static ref USER_ID_SALT: Mutex<Option<String>> = Mutex::new(None);
+}
+
+const DEFAULT_LOG_MAX_BYTES: i64 = 10_000_000;
+const DEFAULT_LOG_MAX_FILES: i64 = 5;
+
+// The logging part of the config file, see src/settings.rs
+#[derive(Debug, PartialEq, Clone)]
+pub struct LogSettings {
+    level: Level,
+    filters: Vec<(String, Level)>,
+    format: Format,
+    file: Option<String>,
+    max_bytes: u64,
+    max_files: u64,
+    // Only there if `log_hash_user_ids` is on.
+    hash_salt: Option<String>,
+}
+
+impl LogSettings {
+    // Anything that doesn't make sense goes in `problems`, and gets left at the default.
+    // Same as the rest of the settings, a value that's there but the wrong type is a problem too, not just ignored.
+    pub fn from_config(config: &Config, problems: &mut Vec<String>) -> LogSettings {
+        let level = or_default(config.get_string("log_level").map(Some), "log_level", None, problems)
+            .map_or(Some(Level::Info), |name| Level::from_name(&name))
+            .unwrap_or_else(|| {
+                problems.push("log_level should be error, warn, info or debug".to_string());
+                Level::Info
+            });
+        let filters = or_default(config.get_array("log_filters"), "log_filters", vec![], problems).into_iter()
+            .filter_map(|val| {
+                let filter = match val.into_string() {
+                    Ok(filter) => filter,
+                    Err(_) => {
+                        problems.push("every entry in log_filters should be a string".to_string());
+                        return None
+                    }
+                };
+                match filter.split_once('=').and_then(|(module, name)| Some((module.trim().to_string(), Level::from_name(name.trim())?))) {
+                    Some(filter) => Some(filter),
+                    None => {
+                        problems.push(format!("log filter \"{}\" should look like \"module=level\"", filter));
+                        None
+                    }
+                }
+            })
+            .collect::<Vec<(String, Level)>>();
+        let format = match or_default(config.get_string("log_format"), "log_format", String::new(), problems).as_str() {
+            "" | "text" => Format::Text,
+            "json" => Format::Json,
+            other => {
+                problems.push(format!("log_format should be text or json, not {}", other));
+                Format::Text
+            }
+        };
+        let file = or_default(config.get_string("log_file").map(Some), "log_file", None, problems).filter(|path| !path.is_empty());
+        let max_bytes = or_default(config.get_int("log_max_bytes"), "log_max_bytes", DEFAULT_LOG_MAX_BYTES, problems);
+        if max_bytes < 1 {
+            problems.push("log_max_bytes should be at least 1".to_string());
+        }
+        let max_files = or_default(config.get_int("log_max_files"), "log_max_files", DEFAULT_LOG_MAX_FILES, problems);
+        if max_files < 0 {
+            problems.push("log_max_files can't be negative".to_string());
+        }
+        let hash_user_ids = or_default(config.get_bool("log_hash_user_ids"), "log_hash_user_ids", false, problems);
+        let hash_salt = or_default(config.get_string("log_hash_salt"), "log_hash_salt", String::new(), problems);
+        // Without a salt, anyone could hash every user ID they can see and look the logs up.
+        if hash_user_ids && hash_salt.is_empty() {
+            problems.push("log_hash_user_ids needs a log_hash_salt, or the hashes are easy to reverse".to_string());
+        }
+        LogSettings {
+            level,
+            filters,
+            format,
+            file,
+            max_bytes: max_bytes.max(1) as u64,
+            max_files: max_files.max(0) as u64,
+            hash_salt: hash_user_ids.then_some(hash_salt),
+        }
+    }
+}
+
+// Until this is called, everything at info and above is printed as text. Called again whenever the config file is reloaded.
+// A log file that can't be opened is warned about instead, since logging shouldn't stop the bot from starting.
+pub fn init_logging(settings: &LogSettings) {
+    let file = settings.file.as_ref().map(|path| LogFile::open(path.clone(), settings.max_bytes, settings.max_files));
+    *USER_ID_SALT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = settings.hash_salt.clone();
+    let mut logger = LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *logger = Logger {
+        level: settings.level,
+        filters: settings.filters.clone(),
+        format: settings.format,
+        file: None,
+    };
+    match file {
+        Some(Ok(file)) => logger.file = Some(file),
+        Some(Err(why)) => {
+            drop(logger);
+            warn!("Couldn't open log file {}: {}", settings.file.as_deref().unwrap_or_default(), why);
+        },
+        None => (),
+    }
+}
+
+// What the macros call. `module` is from module_path!, so it can be filtered on.
+pub fn log(level: Level, module: &str, args: Arguments) {
+    let module = module.strip_prefix("mini_cactbot::").unwrap_or(module);
+    if level > LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).level_for(module) {
+        return
+    }
+    // Formatted without the lock, so a slow message doesn't hold up everyone else's.
+    let message = args.to_string();
+    let mut logger = LOGGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let line = match logger.format {
+        Format::Text => format!("{:?}\t {:<5} {}\t {}", Local::now(), level.name().to_ascii_uppercase(), module, message),
+        Format::Json => format!(
+            "{{\"time\":\"{}\",\"level\":\"{}\",\"module\":\"{}\",\"message\":\"{}\"}}",
+            Local::now().to_rfc3339(),
+            level.name(),
+            json_escape(module),
+            json_escape(&message)
+        ),
+    };
+    println!("{}", line);
+    if let Some(file) = &mut logger.file {
+        if let Err(why) = file.write_line(&line) {
+            // Can't log this one the normal way, so it just goes to stderr.
+            eprintln!("{:?}\t Failed to write to log file {}: {}", Local::now(), file.path, why);
+        }
+    }
+}
+
+fn json_escape(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => output.push_str(format!("\\u{:04x}", c as u32).as_str()),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+// Anyone showing up in the logs should go through this, so `log_hash_user_ids` can keep them out.
+// Hashed users still get the same hash every time (for the same salt), so one person's actions can be followed without knowing who they are.
+pub struct LoggedUser<'a> {
+    name: Option<&'a str>,
+    id: UserId,
+}
+
+pub fn user(user: &User) -> LoggedUser<'_> {
+    LoggedUser { name: Some(user.name.as_str()), id: user.id }
+}
+
+// For when all we have is the ID, like when loading a saved file.
+pub fn user_id(id: UserId) -> LoggedUser<'static> {
+    LoggedUser { name: None, id }
+}
+
+impl Display for LoggedUser<'_> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match USER_ID_SALT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).as_ref() {
+            Some(salt) => {
+                // The standard library's hasher, so it can change between Rust versions. That's fine for lining up logs.
+                let mut hasher = DefaultHasher::new();
+                salt.hash(&mut hasher);
+                self.id.0.hash(&mut hasher);
+                write!(fmt, "#{:016x}", hasher.finish())
+            },
+            None => match self.name {
+                Some(name) => write!(fmt, "{} ({})", name, self.id),
+                None => write!(fmt, "{}", self.id),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use config::{File, FileFormat};
+
+    fn from_toml(toml: &str) -> (LogSettings, Vec<String>) {
+        let config = Config::builder().add_source(File::from_str(toml, FileFormat::Toml)).build().unwrap();
+        let mut problems = vec![];
+        let settings = LogSettings::from_config(&config, &mut problems);
+        (settings, problems)
+    }
+
+    #[test]
+    fn mistyped_values_are_problems() {
+        for toml in ["log_max_bytes = \"lots\"", "log_max_files = [1]", "log_hash_user_ids = \"maybe\"", "log_filters = \"minicact=debug\"", "log_level = [\"info\"]"] {
+            let (_, problems) = from_toml(toml);
+            assert_eq!(problems.len(), 1, "{}: {:?}", toml, problems);
+        }
+        let (settings, problems) = from_toml("");
+        assert_eq!(problems, Vec::<String>::new());
+        assert_eq!(settings.max_bytes, DEFAULT_LOG_MAX_BYTES as u64);
+    }
+
+    #[test]
+    fn hashing_needs_a_salt() {
+        assert_eq!(from_toml("log_hash_user_ids = true").1.len(), 1);
+        assert_eq!(from_toml("log_hash_user_ids = true\nlog_hash_salt = \"\"").1.len(), 1);
+        let (settings, problems) = from_toml("log_hash_user_ids = true\nlog_hash_salt = \"secret\"");
+        assert!(problems.is_empty());
+        assert_eq!(settings.hash_salt, Some("secret".to_string()));
+        // A salt on its own doesn't turn hashing on.
+        assert_eq!(from_toml("log_hash_salt = \"secret\"").0.hash_salt, None);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::settings::settings;
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::Local;
+
+use lazy_static::lazy_static;
+
+// Counts of what the bot has been up to since it started, for /minicact_stats and the optional Prometheus endpoint.
+// Nothing here is saved, so it all starts over from 0 on a restart, which Prometheus expects anyways.
+
+// Every metric, with the help text Prometheus shows for it. Recording anything that isn't listed here is a bug.
+const COUNTERS: &[(&str, &str)] = &[
+    ("minicact_games_started_total", "Games started, by mode."),
+    ("minicact_games_completed_total", "Games played through all three tickets, by mode."),
+    ("minicact_desyncs_total", "Times a game's buttons didn't match the game and it had to be resynced."),
+    ("minicact_disabled_presses_total", "Presses of tiles that were already revealed."),
+    ("interaction_errors_total", "Interactions whose handler returned an error, by kind."),
+];
+
+const HISTOGRAMS: &[(&str, &str)] = &[
+    ("interaction_duration_seconds", "How long it took to respond to an interaction, by kind."),
+];
+
+// In seconds. Discord gives up on an interaction after 3, so there's not much point in going higher than that.
+const BUCKETS: [f64; 8] = [0.05, 0.1, 0.25, 0.5, 1., 2., 3., 5.];
+
+#[derive(Default)]
+struct Histogram {
+    // Not cumulative, unlike what Prometheus wants. The last one is everything past the last bucket.
+    counts: [u64; BUCKETS.len() + 1],
+    sum: f64,
+}
+
+impl Histogram {
+    fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    // The smallest bucket bound that at least `fraction` of everything was under, or None if that's past every bucket.
+    fn quantile_bound(&self, fraction: f64) -> Option<f64> {
+        let target = (self.count() as f64 * fraction).ceil() as u64;
+        let mut seen = 0;
+        for (bound, count) in BUCKETS.iter().zip(self.counts) {
+            seen += count;
+            if seen >= target {
+                return Some(*bound)
+            }
+        }
+        None
+    }
+}
+
+// Keyed by name, then by the labels already formatted the way Prometheus wants them, so they're printed sorted.
+// std::sync since none of this is held across an await.
+lazy_static! {
+    static ref COUNTER_VALUES: Mutex<BTreeMap<(&'static str, String), u64>> = Mutex::new(BTreeMap::new());
+    static ref HISTOGRAM_VALUES: Mutex<BTreeMap<(&'static str, String), Histogram>> = Mutex::new(BTreeMap::new());
+    static ref STARTED: i64 = Local::now().timestamp();
+}
+
+// Whether the bot has connected to Discord, for the health check.
+static READY: AtomicBool = AtomicBool::new(false);
+
+pub fn set_ready(ready: bool) {
+    READY.store(ready, Ordering::Relaxed);
+}
+
+// A name with its labels, leaving out the braces when there aren't any.
+fn series(name: &str, labels: &str) -> String {
+    if labels.is_empty() { name.to_string() } else { format!("{}{{{}}}", name, labels) }
+}
+
+fn format_labels(labels: &[(&str, &str)]) -> String {
+    labels.iter().map(|(key, value)| format!("{}=\"{}\"", key, value.replace('\\', "\\\\").replace('"', "\\\""))).collect::<Vec<String>>().join(",")
+}
+
+pub fn increment(name: &'static str, labels: &[(&str, &str)]) {
+    debug_assert!(COUNTERS.iter().any(|(counter, _)| *counter == name), "Counter {} isn't in COUNTERS", name);
+    *COUNTER_VALUES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).entry((name, format_labels(labels))).or_default() += 1;
+}
+
+pub fn observe(name: &'static str, labels: &[(&str, &str)], seconds: f64) {
+    debug_assert!(HISTOGRAMS.iter().any(|(histogram, _)| *histogram == name), "Histogram {} isn't in HISTOGRAMS", name);
+    let mut histograms = HISTOGRAM_VALUES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let histogram = histograms.entry((name, format_labels(labels))).or_default();
+    let bucket = BUCKETS.iter().position(|bound| seconds <= *bound).unwrap_or(BUCKETS.len());
+    histogram.counts[bucket] += 1;
+    histogram.sum += seconds;
+}
+
+// The Prometheus text format, see https://prometheus.io/docs/instrumenting/exposition_formats/
+fn render() -> String {
+    let mut output = String::new();
+    let counters = COUNTER_VALUES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for (name, help) in COUNTERS {
+        _ = writeln!(output, "# HELP {} {}\n# TYPE {} counter", name, help, name);
+        for ((_, labels), value) in counters.range((*name, String::new())..).take_while(|((counter, _), _)| counter == name) {
+            _ = writeln!(output, "{} {}", series(name, labels), value);
+        }
+    }
+    drop(counters);
+    let histograms = HISTOGRAM_VALUES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    for (name, help) in HISTOGRAMS {
+        _ = writeln!(output, "# HELP {} {}\n# TYPE {} histogram", name, help, name);
+        for ((_, labels), histogram) in histograms.range((*name, String::new())..).take_while(|((histogram, _), _)| histogram == name) {
+            let separator = if labels.is_empty() { "" } else { "," };
+            let mut cumulative = 0;
+            for (bound, count) in BUCKETS.iter().zip(histogram.counts) {
+                cumulative += count;
+                _ = writeln!(output, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, separator, bound, cumulative);
+            }
+            _ = writeln!(output, "{}_bucket{{{}{}le=\"+Inf\"}} {}", name, labels, separator, histogram.count());
+            _ = writeln!(output, "{} {}", series(&format!("{}_sum", name), labels), histogram.sum);
+            _ = writeln!(output, "{} {}", series(&format!("{}_count", name), labels), histogram.count());
+        }
+    }
+    output
+}
+
+pub fn counter(name: &'static str, labels: &[(&str, &str)]) -> u64 {
+    COUNTER_VALUES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(&(name, format_labels(labels))).copied().unwrap_or(0)
+}
+
+// Adds up every label, e.g. games started in any mode.
+pub fn counter_total(name: &'static str) -> u64 {
+    COUNTER_VALUES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).iter()
+        .filter(|((counter, _), _)| *counter == name)
+        .map(|(_, value)| value)
+        .sum()
+}
+
+// For /minicact_stats: how many there were, the average in seconds, and what 95% of them were under (None if that's past every bucket).
+pub fn histogram_summary(name: &'static str, labels: &[(&str, &str)]) -> Option<(u64, f64, Option<f64>)> {
+    let histograms = HISTOGRAM_VALUES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let histogram = histograms.get(&(name, format_labels(labels))).filter(|histogram| histogram.count() > 0)?;
+    Some((histogram.count(), histogram.sum / histogram.count() as f64, histogram.quantile_bound(0.95)))
+}
+
+pub fn started() -> i64 {
+    *STARTED
+}
+
+// Serves /metrics and /health on `metrics_address`, e.g. "127.0.0.1:9100", if there is one.
+// It's only meant for a Prometheus on the same machine, so it's a bare-bones server on its own thread that handles one request at a time.
+pub async fn start_metrics_server() {
+    lazy_static::initialize(&STARTED);
+    let address = match settings().metrics_address.clone() {
+        Some(address) => address,
+        None => return
+    };
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(why) => {
+            error!("Failed to start the metrics server on {}: {}", address, why);
+            return
+        }
+    };
+    info!("Serving metrics on http://{}/metrics", address);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => if let Err(why) = respond(stream) {
+                    debug!("Failed to answer a metrics request: {}", why);
+                },
+                Err(why) => debug!("Failed to accept a metrics connection: {}", why),
+            }
+        }
+    });
+}
+
+fn respond(mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut request_line = String::new();
+    BufReader::new(&stream).read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+    let (status, content_type, body) = match path {
+        "/metrics" => ("200 OK", "text/plain; version=0.0.4", render()),
+        "/health" if READY.load(Ordering::Relaxed) => ("200 OK", "text/plain", "ok\n".to_string()),
+        "/health" => ("503 Service Unavailable", "text/plain", "not connected to Discord\n".to_string()),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+    write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", status, content_type, body.len(), body)?;
+    stream.flush()
+}
@@ -1,18 +1,30 @@
+mod accessibility;
+pub mod admin;
+mod benchmark;
+mod board_history;
 pub mod commands;
 pub mod components;
+mod custom_id;
+mod daily;
 mod game;
 mod generate_components;
+pub mod preferences;
+mod quiz;
 mod recommendations;
+mod render;
+pub mod saved_games;
+mod strategies;
 
 use game::board::Board;
 use game::computations::compute_best_uncover;
 
 use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Instant;
 
 use tokio::sync::Mutex;
 
-use chrono::Local;
+use serenity::http::Http;
 
 use lazy_static::lazy_static;
 
@@ -23,10 +35,20 @@ lazy_static! {
     pub static ref DAILY_PAYOUT_DIST: Mutex<BTreeMap<u16, f64>> = Mutex::new(BTreeMap::new());
 }
 
-pub async fn startup() {
+// The probability of each payout on a single ticket with optimal play, in the same order as the computations use.
+// This is what the daily distribution is built from, but it's also handy for checking anything that plays tickets.
+lazy_static! {
+    pub static ref TICKET_PAYOUT_DIST: Mutex<[f64; 16]> = Mutex::new([0.; 16]);
+}
+
+pub async fn startup(http: Arc<Http>) {
+    preferences::load_preferences().await;
+    // First, since bringing back a daily game in progress marks it as started again.
+    daily::load_daily_puzzles().await;
+    saved_games::load_active_games().await;
     let mut board = Board {
         state: [255, 255, 255, 255, 255, 255, 255, 255, 255],
-        unused_nums: (0..9).into_iter().collect(),
+        unused_nums: (0..9).collect(),
     };
     let (n, data) = compute_best_uncover(&mut board);
     // time to compute the probability of any given payout BEFORE you buy your scratch ticket, with optimal play.
@@ -36,6 +58,7 @@ pub async fn startup() {
     for i in 0..16 {
         p_data[i] = (data[i] as f64) / (n as f64);
     }
+    *TICKET_PAYOUT_DIST.lock().await = p_data;
     let mut daily_payout_dist = DAILY_PAYOUT_DIST.lock().await;
     // now, we just need to ""cube"" that probability distribution, to get all the combinations that give any given payout for the day.
     let mut now = Instant::now();
@@ -44,18 +67,13 @@ pub async fn startup() {
             for k in 0..16 {
                 // The key is the value, so we can get the percentile from the payout.
                 let key = PAYOUT_VALUES[i + 1] + PAYOUT_VALUES[j + 1] + PAYOUT_VALUES[k + 1];
-                let oldvalue = daily_payout_dist.get(&key).unwrap_or(&0.).clone();
+                let oldvalue = *daily_payout_dist.get(&key).unwrap_or(&0.);
                 daily_payout_dist.insert(key, p_data[i] * p_data[j] * p_data[k] + oldvalue);
             }
         }
     }
     let mut elapsed = now.elapsed();
-    println!(
-        "{:?}\t Computed {} payout options in {:.2?}.",
-        Local::now(),
-        daily_payout_dist.len(),
-        elapsed
-    );
+    info!("Computed {} payout options in {:.2?}.", daily_payout_dist.len(), elapsed);
     let mut total_p = 0.;
     now = Instant::now();
     // This works because BTreeMap produces sorted iters.
@@ -64,10 +82,7 @@ pub async fn startup() {
         *v = total_p;
     }
     elapsed = now.elapsed();
-    println!(
-        "{:?}\t Updated {} payout percentiles in {:.2?}.",
-        Local::now(),
-        daily_payout_dist.len(),
-        elapsed
-    );
+    info!("Updated {} payout percentiles in {:.2?}.", daily_payout_dist.len(), elapsed);
+    // Nothing is waiting on this, it just posts the daily puzzle at every reset from now on.
+    daily::start_daily_puzzles(http);
 }
@@ -0,0 +1,33 @@
+use crate::locale::{format_number, tr, trf, Locale};
+use super::game::payout::PAYOUT_VALUES;
+use super::generate_components::line_name;
+
+// For anyone who wants the game spelled out in words instead of emoji, colors and bar graphs, e.g. for screen readers.
+// Whether someone does is in their preferences.
+
+// make_graph without the graph. Block characters get read out one at a time, so this is just the numbers.
+pub fn make_table(data: [f64; 16], locale: Locale) -> String {
+    let mut output = format!("{}\n", tr(locale, "rec.table"));
+    for (i, p) in data.iter().enumerate().rev() {
+        if *p > 0. {
+            let payout = format_number(locale, PAYOUT_VALUES[i + 1] as f64, 0);
+            output.push_str(format!("{}\n", trf(locale, "rec.table_row", &[&payout, &format_number(locale, *p * 100.0, 1)])).as_str());
+        }
+    }
+    output
+}
+
+// make_board_grid's version, one row per line. `highlight` is read out as a question mark.
+pub fn describe_board(board: &[u8; 9], highlight: Option<usize>, locale: Locale) -> String {
+    let mut output = String::new();
+    // Rows go top to bottom, which is backwards from how POSITION_LINE_TABLE has them.
+    for (j, row) in [2, 1, 0].iter().enumerate() {
+        let tiles = (3 * j..3 * j + 3).map(|i| match board[i] {
+            _ if Some(i) == highlight => "?".to_string(),
+            255 => tr(locale, "board.covered").to_string(),
+            n => (n + 1).to_string()
+        }).collect::<Vec<String>>();
+        output.push_str(format!("{}\n", trf(locale, "board.row", &[&line_name(locale, *row), &tiles.join(", ")])).as_str());
+    }
+    output
+}
@@ -0,0 +1,142 @@
+use crate::logging;
+use crate::locale::{format_number, tr, trf, Locale};
+use super::benchmark::{evaluate_exact, EXACT_TICKETS};
+use super::game::*;
+use super::game::payout::PAYOUT_VALUES;
+use super::strategies::Optimal;
+use super::{DAILY_PAYOUT_DIST, TICKET_PAYOUT_DIST};
+
+use serenity::model::id::UserId;
+use serenity::model::user::User;
+
+// The Mini Cactpot side of /admin, see src/admin.rs
+
+// Only this many games get listed, so it stays under Discord's message limit.
+const MAX_LISTED_GAMES: usize = 20;
+// Same for the self-check, but broken games come with a reason, so fewer of them fit.
+const MAX_LISTED_PROBLEMS: usize = 5;
+
+fn mode_name(locale: Locale, mode: GameMode) -> &'static str {
+    tr(locale, &format!("mode.{}", mode.name()))
+}
+
+// The first few of a list, then how many got left out.
+fn list_some(locale: Locale, items: &[String], max: usize) -> String {
+    let mut listed = items.iter().take(max).cloned().collect::<Vec<String>>();
+    if items.len() > max {
+        listed.push(trf(locale, "admin.more", &[&(items.len() - max)]));
+    }
+    listed.join(", ")
+}
+
+// Oldest first, since those are the ones most likely to be abandoned.
+pub async fn describe_active_games(locale: Locale) -> String {
+    let active_games = ACTIVE_GAMES.lock().await;
+    describe_games(locale, &active_games.iter().collect::<Vec<(&UserId, &Game)>>())
+}
+
+fn describe_games(locale: Locale, games: &[(&UserId, &Game)]) -> String {
+    if games.is_empty() {
+        return tr(locale, "admin.no_games").to_string()
+    }
+    let mut games = games.to_vec();
+    games.sort_by_key(|(_, game)| game.started());
+    let mut lines = games.iter().take(MAX_LISTED_GAMES)
+        .map(|(user, game)| trf(locale, "admin.game", &[
+            &format!("<@{}>", user), &mode_name(locale, game.mode()), &format!("<t:{}:R>", game.started()), &game.index()
+        ]))
+        .collect::<Vec<String>>();
+    if games.len() > MAX_LISTED_GAMES {
+        lines.push(trf(locale, "admin.more", &[&(games.len() - MAX_LISTED_GAMES)]));
+    }
+    format!("{}\n{}", trf(locale, "admin.games", &[&games.len()]), lines.join("\n"))
+}
+
+// Throws away someone's game, as if they had finished it. Daily games still count as started, so it can't be used to get a second try.
+// Returns what kind of game it was, if they had one.
+pub async fn expire_game(user: &User) -> Option<GameMode> {
+    let game = ACTIVE_GAMES.lock().await.remove(&user.id)?;
+    info!("Expired {} game of user {}, started at {}", game.mode().name(), logging::user(user), game.started());
+    Some(game.mode())
+}
+
+// Double-checks the numbers everything else relies on, and every game in progress.
+// Each line is one check, starting with whether it passed. The log always gets them in English.
+pub async fn self_check(locale: Locale) -> Vec<String> {
+    let mut results = vec![];
+    let mut check = |name: &str, passed: bool, detail: &dyn Fn(Locale) -> String| {
+        let line = |locale| format!("{} {}: {}", if passed { "✅" } else { "❌" }, tr(locale, name), detail(locale));
+        info!("Self-check: {}", line(Locale::English));
+        results.push(line(locale));
+    };
+
+    let ticket_dist = *TICKET_PAYOUT_DIST.lock().await;
+    let total = ticket_dist.iter().sum::<f64>();
+    check("check.payout_distribution", (total - 1.).abs() < 1e-9, &|locale| trf(locale, "check.payout_distribution_detail", &[&total]));
+
+    let last_percentile = DAILY_PAYOUT_DIST.lock().await.values().last().copied().unwrap_or_default();
+    check("check.daily_percentiles", (last_percentile - 100.).abs() < 1e-6, &|locale| {
+        trf(locale, "check.daily_percentiles_detail", &[&format_number(locale, last_percentile, 6)])
+    });
+
+    // Plays every possible ticket, which takes a few seconds.
+    match tokio::task::spawn_blocking(|| evaluate_exact(&mut Optimal)).await {
+        Ok(histogram) => {
+            // Ties can be broken differently, so only the average has to match.
+            let played = histogram.iter().sum::<u32>();
+            let mean = (0..16).map(|i| histogram[i] as f64 * PAYOUT_VALUES[i + 1] as f64).sum::<f64>() / played as f64;
+            let expected = (0..16).map(|i| ticket_dist[i] * PAYOUT_VALUES[i + 1] as f64).sum::<f64>();
+            check("check.optimal_play", played == EXACT_TICKETS && (mean - expected).abs() < 1e-6, &|locale| trf(locale, "check.optimal_play_detail", &[
+                &format_number(locale, played as f64, 0), &format_number(locale, EXACT_TICKETS as f64, 0),
+                &format_number(locale, mean, 4), &format_number(locale, expected, 4)
+            ]));
+        },
+        Err(why) => check("check.optimal_play", false, &|locale| trf(locale, "check.panicked", &[&why])),
+    }
+
+    let active_games = ACTIVE_GAMES.lock().await;
+    let broken = active_games.iter()
+        .filter_map(|(user, game)| game.check_invariants().err().map(|why| (user, why)))
+        .collect::<Vec<_>>();
+    check("check.games", broken.is_empty(), &|locale| match broken.is_empty() {
+        true => trf(locale, "check.games_ok", &[&active_games.len()]),
+        false => {
+            let broken = broken.iter().map(|(user, why)| format!("<@{}> ({})", user, why.message(locale))).collect::<Vec<String>>();
+            trf(locale, "check.games_broken", &[&broken.len(), &active_games.len(), &list_some(locale, &broken, MAX_LISTED_PROBLEMS)])
+        },
+    });
+    // Anything that doesn't come back the same would be lost or changed by a restart.
+    let unsaveable = active_games.iter()
+        .filter(|(_, game)| Game::from_saved(&game.to_saved()).map(|saved| saved.to_saved()) != Ok(game.to_saved()))
+        .map(|(user, _)| format!("<@{}>", user))
+        .collect::<Vec<String>>();
+    check("check.saving", unsaveable.is_empty(), &|locale| match unsaveable.is_empty() {
+        true => trf(locale, "check.saving_ok", &[&active_games.len()]),
+        false => trf(locale, "check.saving_broken", &[&unsaveable.len(), &list_some(locale, &unsaveable, MAX_LISTED_PROBLEMS)]),
+    });
+    drop(active_games);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serenity::model::id::GuildId;
+
+    #[test]
+    fn long_lists_fit_in_a_message() {
+        let users = (0..100).map(|i| UserId(u64::MAX - i)).collect::<Vec<UserId>>();
+        let games = users.iter().map(|_| Game::new_daily(0, GuildId(1), 0)).collect::<Vec<Game>>();
+        let listed = users.iter().zip(games.iter()).collect::<Vec<(&UserId, &Game)>>();
+        for locale in Locale::ALL {
+            assert!(describe_games(locale, &listed).chars().count() <= 2000);
+            // Both game checks as long as they get, the number checks before them are never more than a few hundred.
+            let why = GameError::BrokenInvariant("a ticket was started before the last payout was entered");
+            let broken = users.iter().map(|user| format!("<@{}> ({})", user, why.message(locale))).collect::<Vec<String>>();
+            let unsaveable = users.iter().map(|user| format!("<@{}>", user)).collect::<Vec<String>>();
+            let games = trf(locale, "check.games_broken", &[&users.len(), &users.len(), &list_some(locale, &broken, MAX_LISTED_PROBLEMS)]);
+            let saving = trf(locale, "check.saving_broken", &[&users.len(), &list_some(locale, &unsaveable, MAX_LISTED_PROBLEMS)]);
+            assert!(games.chars().count() + saving.chars().count() + 500 <= 2000);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::game::board::Board;
+use super::game::computations::compute_best_line_rec;
+use super::game::payout::{line_payout, PAYOUT_VALUES};
+use super::game::HiddenTicket;
+use super::strategies::Strategy;
+
+use rand::Rng;
+
+// What came out of playing a strategy a bunch of times.
+pub struct BatchResult {
+    pub tickets: u32,
+    pub mean: f64,
+    pub variance: f64,
+    // Same layout as the distributions from the computations, so it can go straight into make_graph.
+    pub histogram: [u32; 16],
+}
+
+impl BatchResult {
+    // How far off the mean could reasonably be from sampling alone.
+    pub fn standard_error(&self) -> f64 {
+        (self.variance / self.tickets as f64).sqrt()
+    }
+}
+
+// Plays one random ticket start to finish, and returns the index into PAYOUT_VALUES it paid out.
+pub fn play_ticket<R: Rng>(strategy: &mut dyn Strategy, rng: &mut R) -> usize {
+    play_hidden_ticket(strategy, &HiddenTicket::random(rng))
+}
+
+// Same as play_ticket, for when the ticket is already decided.
+pub fn play_hidden_ticket(strategy: &mut dyn Strategy, hidden: &HiddenTicket) -> usize {
+    let mut board = Board {
+        state: [255, 255, 255, 255, 255, 255, 255, 255, 255],
+        unused_nums: (0..9).collect(),
+    };
+    let reveal = |board: &mut Board, i: usize| {
+        board.state[i] = hidden.board()[i];
+        board.unused_nums.remove(&hidden.board()[i]);
+    };
+    reveal(&mut board, hidden.first() as usize);
+    for _ in 0..3 {
+        let i = strategy.choose_tile(&board);
+        debug_assert_eq!(board.state[i], 255, "Strategy chose a tile that was already uncovered");
+        reveal(&mut board, i);
+    }
+    let line = strategy.choose_line(&board);
+    line_payout(&hidden.board(), line) as usize
+}
+
+// Monte Carlo, for when you want a rough idea of how a strategy does.
+pub fn simulate_batch<R: Rng>(strategy: &mut dyn Strategy, tickets: u32, rng: &mut R) -> BatchResult {
+    let mut histogram = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let mut total: f64 = 0.;
+    let mut total_squared: f64 = 0.;
+    for _ in 0..tickets {
+        let payout = play_ticket(strategy, rng);
+        let value = PAYOUT_VALUES[payout] as f64;
+        histogram[payout - 1] += 1;
+        total += value;
+        total_squared += value * value;
+    }
+    let n = tickets as f64;
+    let mean = total / n;
+    BatchResult {
+        tickets,
+        mean,
+        // Sample variance, so it's an unbiased estimate.
+        variance: if tickets > 1 { (total_squared - n * mean * mean) / (n - 1.) } else { 0. },
+        histogram,
+    }
+}
+
+// Every ticket is 9 possible starting tiles, times 9 numbers for it, times 8 * 7 * 6 numbers for the 3 tiles the strategy picks,
+// times 5! ways to fill in the rest of the board. evaluate_exact's distribution always adds up to this.
+pub const EXACT_TICKETS: u32 = 9 * 9 * 8 * 7 * 6 * 120;
+
+// No sampling: plays the strategy against every possible ticket and returns how many paid out each amount.
+// This walks the tree the same way compute_best_uncover does, except the strategy picks the tile instead of the best one.
+// The strategy has to be deterministic, otherwise this just measures one arbitrary set of its choices.
+pub fn evaluate_exact(strategy: &mut dyn Strategy) -> [u32; 16] {
+    let mut board = Board {
+        state: [255, 255, 255, 255, 255, 255, 255, 255, 255],
+        unused_nums: (0..9).collect(),
+    };
+    let mut output = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    // The first tile is revealed for you, so the strategy doesn't get a say in it.
+    for i in 0..9 {
+        add_dist(&mut output, evaluate_tile_rec(strategy, &mut board, i));
+    }
+    output
+}
+
+// Tries every number under tile i, and lets the strategy play on from each one.
+fn evaluate_tile_rec(strategy: &mut dyn Strategy, board: &mut Board, i: usize) -> [u32; 16] {
+    let mut output = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    let unused_nums = board.unused_nums.clone();
+    for x in unused_nums.iter() {
+        board.state[i] = *x;
+        board.unused_nums.remove(x);
+        let n = board.state.iter().filter(|&x| x != &255).count();
+        let data = if n == 4 {
+            // compute_best_line_rec already has the distribution for every line, we just need to look up the one the strategy picked.
+            let line = strategy.choose_line(board);
+            compute_best_line_rec(board)[line]
+        } else {
+            let next = strategy.choose_tile(board);
+            debug_assert_eq!(board.state[next], 255, "Strategy chose a tile that was already uncovered");
+            evaluate_tile_rec(strategy, board, next)
+        };
+        add_dist(&mut output, data);
+        board.unused_nums.insert(*x);
+    }
+    board.state[i] = 255;
+    output
+}
+
+fn add_dist(total: &mut [u32; 16], data: [u32; 16]) {
+    for j in 0..16 {
+        total[j] += data[j];
+    }
+}
@@ -0,0 +1,27 @@
+use crate::settings::settings;
+use super::game::*;
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use chrono::Local;
+
+// Every finished game with at least one whole board in it, so the boards are still around for statistics after the game is gone.
+// One game per line: when it finished, what kind of game it was, and the game itself in notation (see game/notation.rs).
+// `1700000000 play 53 17 92 34 $108 B714935862 L3 / 61 27 ...`
+// Nobody's ID goes in here, there's nothing about the boards that needs to know who played them.
+
+// Called once a game is finished, right before it gets thrown away.
+pub fn record_finished_game(game: &Game) {
+    if (0..3).all(|ticket| game.full_board(ticket).is_none()) {
+        return
+    }
+    let path = settings().board_history_file.clone();
+    let line = format!("{} {} {}\n", Local::now().timestamp(), game.mode().name(), game.to_notation());
+    // Appending means a crash can only ever lose the game being written, never the ones before it.
+    let result = OpenOptions::new().create(true).append(true).open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()));
+    if let Err(why) = result {
+        error!("Failed to save a finished game's boards to {}: {}", path, why);
+    }
+}
@@ -1,51 +1,279 @@
-use crate::commands::nyi_command;
+use crate::logging;
+use crate::metrics;
+use crate::commands::{command_locale, command_names, get_option, send_interaction_response_message, Permission};
+use crate::locale::{add_choice, describe_command, describe_option, format_number, tr, trf, Locale};
 use crate::generate_components::make_button;
+use crate::guild_config::*;
+use crate::shutdown::is_draining;
+use crate::registry::SlashCommand;
+use super::benchmark::{evaluate_exact, simulate_batch, EXACT_TICKETS};
+use super::custom_id::CustomId;
+use super::daily::{mark_daily_started, new_game};
 use super::game::*;
+use super::game::payout::PAYOUT_VALUES;
 use super::generate_components::*;
+use super::quiz::*;
+use super::recommendations::{make_graph, parse_data, recommend_line, recommend_position, recommend_start};
+use super::preferences::*;
+use super::render::attach_image;
+use super::saved_games::track_message;
+use super::strategies::*;
+use super::TICKET_PAYOUT_DIST;
 
-use serenity::builder::CreateApplicationCommands;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+use serenity::model::application::command::CommandOptionType;
+use serenity::model::channel::ChannelType;
 use serenity::model::application::interaction::InteractionResponseType;
-use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::application::interaction::application_command::{ApplicationCommandInteraction, CommandDataOptionValue};
 use serenity::prelude::*;
 use serenity::model::prelude::component::ButtonStyle;
 
-pub async fn handle_command(ctx: Context, command:ApplicationCommandInteraction) -> Result<(), SerenityError> {
-    // Add any custom commands here
-    match command.data.name.as_str() {
-        "minicact_play" => play_command(ctx, command, false).await,
-        "minicact_simulate" => play_command(ctx, command, true).await,
-        _ => nyi_command(ctx, command).await
-    }
-}
-
-pub fn create_commands(commands: &mut CreateApplicationCommands) -> &mut CreateApplicationCommands {
-    // DON'T FORGET to add your custom commands here!!
-    commands
-        .create_application_command(|command| {
-            command.name("minicact_play").description("Play the game!")
-        })
-        .create_application_command(|command| {
-            command.name("minicact_simulate").description("Play a simulated game!")
-        })
-        
-}
+// Every Mini Cactpot command, see crate::registry.
+pub const COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        name: "minicact_play",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_play")
+        },
+        handler: |ctx, command| Box::pin(play_command(ctx, command, GameMode::Play)),
+    },
+    SlashCommand {
+        name: "minicact_simulate",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_simulate")
+                .create_option(|option| {
+                    describe_option(option.name("seed"), "opt.minicact_simulate.seed")
+                        .kind(CommandOptionType::Integer)
+                        .min_int_value(0)
+                        .max_int_value(MAX_SEED)
+                })
+        },
+        handler: |ctx, command| Box::pin(play_command(ctx, command, GameMode::Simulate)),
+    },
+    SlashCommand {
+        name: "minicact_daily",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_daily")
+        },
+        handler: |ctx, command| Box::pin(play_command(ctx, command, GameMode::Daily)),
+    },
+    SlashCommand {
+        name: "minicact_quiz",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_quiz")
+        },
+        handler: |ctx, command| Box::pin(quiz_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_solve",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_solve")
+                .create_option(|option| {
+                    describe_option(option.name("ticket"), "opt.minicact_solve.ticket")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                })
+        },
+        handler: |ctx, command| Box::pin(solve_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_import",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_import")
+                .create_option(|option| {
+                    describe_option(option.name("game"), "opt.minicact_import.game")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                })
+        },
+        handler: |ctx, command| Box::pin(import_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_export",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_export")
+        },
+        handler: |ctx, command| Box::pin(export_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_images",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_images")
+                .create_option(|option| {
+                    describe_option(option.name("enabled"), "opt.minicact_images.enabled")
+                        .kind(CommandOptionType::Boolean)
+                        .required(true)
+                })
+        },
+        handler: |ctx, command| Box::pin(images_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_accessibility",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_accessibility")
+                .create_option(|option| {
+                    describe_option(option.name("enabled"), "opt.minicact_accessibility.enabled")
+                        .kind(CommandOptionType::Boolean)
+                        .required(true)
+                })
+        },
+        handler: |ctx, command| Box::pin(accessibility_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_settings",
+        permission: Permission::Anyone,
+        create: |command| {
+            describe_command(command, "cmd.minicact_settings")
+                .create_option(|option| {
+                    describe_option(option.name("objective"), "opt.minicact_settings.objective")
+                        .kind(CommandOptionType::String);
+                    for objective in Objective::ALL {
+                        add_choice(option, format!("objective.{}", objective.code()).as_str(), objective.code());
+                    }
+                    option
+                })
+                .create_option(|option| {
+                    describe_option(option.name("multiplier"), "opt.minicact_settings.multiplier")
+                        .kind(CommandOptionType::Number)
+                        .min_number_value(MIN_MULTIPLIER as f64 / 100.)
+                        .max_number_value(MAX_MULTIPLIER as f64 / 100.)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("graph"), "opt.minicact_settings.graph")
+                        .kind(CommandOptionType::String);
+                    for graph in GraphStyle::ALL {
+                        add_choice(option, format!("graph.{}", graph.code()).as_str(), graph.code());
+                    }
+                    option
+                })
+                .create_option(|option| {
+                    describe_option(option.name("language"), "opt.minicact_settings.language")
+                        .kind(CommandOptionType::String);
+                    add_choice(option, "language.automatic", "auto");
+                    for locale in Locale::ALL {
+                        option.add_string_choice(locale.name(), locale.code());
+                    }
+                    option
+                })
+                .create_option(|option| {
+                    describe_option(option.name("accessibility"), "opt.minicact_settings.accessibility")
+                        .kind(CommandOptionType::Boolean)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("announce"), "opt.minicact_settings.announce")
+                        .kind(CommandOptionType::Boolean)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("public"), "opt.minicact_settings.public")
+                        .kind(CommandOptionType::Boolean)
+                })
+        },
+        handler: |ctx, command| Box::pin(settings_command(ctx, command)),
+    },
+    SlashCommand {
+        name: CONFIG_COMMAND,
+        permission: Permission::GuildAdmin,
+        create: |command| {
+            describe_command(command, "cmd.minicact_config")
+                .dm_permission(false)
+                .create_option(|option| {
+                    describe_option(option.name("announcement_channel"), "opt.minicact_config.announcement_channel")
+                        .kind(CommandOptionType::Channel)
+                        .channel_types(&[ChannelType::Text, ChannelType::News])
+                })
+                .create_option(|option| {
+                    describe_option(option.name("clear_announcement_channel"), "opt.minicact_config.clear_announcement_channel")
+                        .kind(CommandOptionType::Boolean)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("leaderboard"), "opt.minicact_config.leaderboard")
+                        .kind(CommandOptionType::Boolean)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("allowed_commands"), "opt.minicact_config.allowed_commands")
+                        .kind(CommandOptionType::String)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("add_admin_role"), "opt.minicact_config.add_admin_role")
+                        .kind(CommandOptionType::Role)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("remove_admin_role"), "opt.minicact_config.remove_admin_role")
+                        .kind(CommandOptionType::Role)
+                })
+        },
+        handler: |ctx, command| Box::pin(config_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_benchmark",
+        // This can keep a core busy for a while.
+        permission: Permission::BotOwner,
+        create: |command| {
+            describe_command(command, "cmd.minicact_benchmark")
+                .create_option(|option| {
+                    describe_option(option.name("strategy"), "opt.minicact_benchmark.strategy")
+                        .kind(CommandOptionType::String)
+                        .required(true);
+                    for strategy in BENCHMARK_STRATEGIES {
+                        add_choice(option, format!("strategy.{}", strategy).as_str(), strategy);
+                    }
+                    option
+                })
+                .create_option(|option| {
+                    describe_option(option.name("tickets"), "opt.minicact_benchmark.tickets")
+                        .kind(CommandOptionType::Integer)
+                        .min_int_value(1)
+                        .max_int_value(MAX_BENCHMARK_TICKETS)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("rule"), "opt.minicact_benchmark.rule")
+                        .kind(CommandOptionType::String)
+                })
+                .create_option(|option| {
+                    describe_option(option.name("exact"), "opt.minicact_benchmark.exact")
+                        .kind(CommandOptionType::Boolean)
+                })
+        },
+        handler: |ctx, command| Box::pin(benchmark_command(ctx, command)),
+    },
+    SlashCommand {
+        name: "minicact_stats",
+        permission: Permission::BotOwner,
+        create: |command| {
+            describe_command(command, "cmd.minicact_stats")
+        },
+        handler: |ctx, command| Box::pin(stats_command(ctx, command)),
+    },
+];
 
-async fn play_command(ctx: Context, command: ApplicationCommandInteraction, simulate: bool) -> Result<(), SerenityError> { 
-    let mut active_games = ACTIVE_GAMES.lock().await;
-    if active_games.contains_key(&command.user.id) {  // if user has an active game already, warn them so they don't lose any data unintentionally.
+async fn play_command(ctx: Context, command: ApplicationCommandInteraction, mode: GameMode) -> Result<(), SerenityError> { 
+    let locale = command_locale(&command).await;
+    // Not held until the game goes in, since recommending a simulation's first tile can take a while.
+    if ACTIVE_GAMES.lock().await.contains_key(&command.user.id) {  // if user has an active game already, warn them so they don't lose any data unintentionally.
         return command.create_interaction_response(&ctx.http, |response| {
             response.kind(InteractionResponseType::ChannelMessageWithSource)
                 .interaction_response_data(|message| {
-                    message.content(format!("{} you already have a game started.\nWould you like to:\n> ↩ Restore your previous game\n> 🔄 Discard it and start from scratch", command.user.mention()))
+                    message.content(trf(locale, "game.already_started", &[&command.user.mention()]))
                         .ephemeral(true)
                         .components(|components| {
                             components.create_action_row(|action_row| {
-                                make_button(action_row, "minicact_restore", ButtonStyle::Primary, Some("↩"), Some(" Restore"), false);
+                                make_button(action_row, CustomId::Restore, ButtonStyle::Primary, Some("↩"), Some(format!(" {}", tr(locale, "button.restore")).as_str()), false);
                                 make_button(action_row, 
-                                    format!("minicact_full_reset{}", if simulate {"_sim"} else {""}), 
+                                    CustomId::FullReset { mode }, 
                                     ButtonStyle::Primary, 
                                     Some("🔄"), 
-                                    Some(" Discard"), 
+                                    Some(format!(" {}", tr(locale, "button.discard")).as_str()), 
                                     false)
                             })
                         })
@@ -53,20 +281,444 @@ async fn play_command(ctx: Context, command: ApplicationCommandInteraction, simu
         }).await
     }
     // Otherwise, we're good to go! Just make the default board.
-    let game = if simulate {Game::new_simulated()} else {Game::new()};
+    let seed = match get_option(&command, "seed") {
+        Some(CommandDataOptionValue::Integer(n)) => Some((*n).clamp(0, MAX_SEED as i64) as u64),
+        _ => None
+    };
+    let game = match new_game(mode, command.guild_id, command.user.id, seed, locale).await {
+        Ok(game) => game,
+        Err(why) => return send_interaction_response_message(&ctx, &command, why, true).await
+    };
+    if let (GameMode::Simulate, Some(seed)) = (mode, game.seed()) {
+        info!("User {} started a simulation with seed {}", logging::user(&command.user), seed);
+    }
+    let preferences = user_preferences(command.user.id).await;
+    let accessible = preferences.accessible;
+    let (recommendation, content, png) = recommend_start(&game, &preferences, locale).await?;
     command.create_interaction_response(&ctx.http, |response| {
         response.kind(InteractionResponseType::ChannelMessageWithSource)
             .interaction_response_data(|message| {
-                message.content("Enter the already revealed tile:")
-                    .ephemeral(true)
+                if preferences.image() {
+                    attach_image(message, png);
+                }
+                message.content(content)
+                    .ephemeral(preferences.ephemeral(mode == GameMode::Daily))
                     .components(|components| {
-                        make_game_rows(components, &game, 255);
-                        make_reset_bar(components, &game)
+                        make_game_rows(components, &game, recommendation, accessible, locale);
+                        make_reset_bar(components, &game, accessible, locale)
                     })
             })
     }).await?;
     // Rust is a beautiful language...
     // I have to make sure that the message returns successfully before I can put the game into active_games.
+    metrics::increment("minicact_games_started_total", &[("mode", mode.name())]);
+    mark_daily_started(&game, command.user.id).await;
+    ACTIVE_GAMES.lock().await.insert(command.user.id, game);
+    track_message(command.user.id, &command.token, locale).await;
+    Ok(())
+}
+
+// No recommendations here, the player has to figure it out themselves and then gets told how they did.
+async fn quiz_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    // Unlike games, there's nothing to lose by replacing a quiz, so there's no need to warn about it.
+    let mut active_quizzes = ACTIVE_QUIZZES.lock().await;
+    let quiz = Quiz::random(&mut rand::thread_rng());
+    let locale = command_locale(&command).await;
+    let content = quiz.question(QUIZ_SCORES.lock().await.get(&command.user.id), locale);
+    let preferences = user_preferences(command.user.id).await;
+    let accessible = preferences.accessible;
+    command.create_interaction_response(&ctx.http, |response| {
+        response.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|message| {
+                message.content(content)
+                    .ephemeral(preferences.ephemeral(false))
+                    .components(|components| make_quiz_rows(components, &quiz, None, accessible, locale))
+            })
+    }).await?;
+    active_quizzes.insert(command.user.id, quiz);
+    Ok(())
+}
+
+// Doesn't touch the user's game at all, this is just for asking about a specific ticket.
+async fn solve_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let notation = match get_option(&command, "ticket") {
+        Some(CommandDataOptionValue::String(s)) => s.as_str(),
+        _ => ""
+    };
+    let locale = command_locale(&command).await;
+    let game = match Game::from_notation(notation) {
+        Ok(game) => game,
+        Err(why) => return send_interaction_response_message(&ctx, &command, why.message(locale), true).await
+    };
+    let preferences = user_preferences(command.user.id).await;
+    let accessible = preferences.accessible;
+    let (content, png) = match (game.next_action(), game.last_action()) {
+        // Accessible recommendations already say what to pick, so there's no need to say it twice.
+        (Action::EnterPayout(_), _) => {
+            let (line, content, png) = recommend_line(&game, &preferences, locale);
+            let advice = if accessible { String::new() } else { format!("{}\n", trf(locale, "solve.pick", &[&line_name_inline(locale, line)])) };
+            (format!("{}{}{}", make_board_grid(&game.as_board().state, None, accessible, locale), advice, content), png)
+        },
+        (Action::ChoosePosition(_), Action::ChoosePosition(_) | Action::RevealNumber(_)) => {
+            let (position, content, png) = recommend_position(&game, &preferences, locale).await?;
+            let (highlight, advice) = if accessible { (None, String::new()) } else { (Some(position), format!("{}\n", tr(locale, "solve.uncover"))) };
+            (format!("{}{}{}", make_board_grid(&game.as_board().state, highlight, accessible, locale), advice, content), png)
+        },
+        _ => return send_interaction_response_message(&ctx, &command, tr(locale, "solve.nothing"), true).await
+    };
+    command.create_interaction_response(&ctx.http, |response| {
+        response.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|message| {
+                if preferences.image() {
+                    attach_image(message, png);
+                }
+                message.content(content).ephemeral(preferences.ephemeral(false))
+            })
+    }).await
+}
+
+async fn images_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let enabled = match get_option(&command, "enabled") {
+        Some(CommandDataOptionValue::Boolean(b)) => *b,
+        _ => return Err(SerenityError::Other("Images command didn't have enabled??"))
+    };
+    // The same as /minicact_settings graph, just quicker to find.
+    update_preferences(command.user.id, |preferences| preferences.graph = if enabled { GraphStyle::Image } else { GraphStyle::Text }).await;
+    let locale = command_locale(&command).await;
+    let content = tr(locale, if enabled { "images.on" } else { "images.off" });
+    send_interaction_response_message(&ctx, &command, content, true).await
+}
+
+async fn accessibility_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let enabled = match get_option(&command, "enabled") {
+        Some(CommandDataOptionValue::Boolean(b)) => *b,
+        _ => return Err(SerenityError::Other("Accessibility command didn't have enabled??"))
+    };
+    update_preferences(command.user.id, |preferences| preferences.accessible = enabled).await;
+    let locale = command_locale(&command).await;
+    let content = tr(locale, if enabled { "accessibility.on" } else { "accessibility.off" });
+    send_interaction_response_message(&ctx, &command, content, true).await
+}
+
+// Every option is optional. Whatever is given gets changed, and then all of the settings are shown either way.
+async fn settings_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let objective = match get_option(&command, "objective") {
+        Some(CommandDataOptionValue::String(s)) => Objective::from_code(s),
+        _ => None
+    };
+    let multiplier = match get_option(&command, "multiplier") {
+        Some(CommandDataOptionValue::Number(x)) => Some(((x * 100.).round() as u16).clamp(MIN_MULTIPLIER, MAX_MULTIPLIER)),
+        _ => None
+    };
+    let graph = match get_option(&command, "graph") {
+        Some(CommandDataOptionValue::String(s)) => GraphStyle::from_code(s),
+        _ => None
+    };
+    // Some(None) is choosing Automatic, which is different from not choosing anything.
+    let locale_choice = match get_option(&command, "language") {
+        Some(CommandDataOptionValue::String(s)) => Some(Locale::from_code(s)),
+        _ => None
+    };
+    let flag = |name: &str| match get_option(&command, name) {
+        Some(CommandDataOptionValue::Boolean(b)) => Some(*b),
+        _ => None
+    };
+    let (accessible, auto_announce, public) = (flag("accessibility"), flag("announce"), flag("public"));
+    let preferences = if command.data.options.is_empty() {
+        user_preferences(command.user.id).await
+    } else {
+        info!("User {} changed their settings", logging::user(&command.user));
+        update_preferences(command.user.id, |preferences| {
+            preferences.objective = objective.unwrap_or(preferences.objective);
+            preferences.multiplier = multiplier.unwrap_or(preferences.multiplier);
+            preferences.graph = graph.unwrap_or(preferences.graph);
+            preferences.locale = locale_choice.unwrap_or(preferences.locale);
+            preferences.accessible = accessible.unwrap_or(preferences.accessible);
+            preferences.auto_announce = auto_announce.unwrap_or(preferences.auto_announce);
+            preferences.public = public.unwrap_or(preferences.public);
+        }).await
+    };
+    // After the change, so a new language shows up right away.
+    let locale = command_locale(&command).await;
+    let on_off = |b: bool| tr(locale, if b { "settings.on" } else { "settings.off" });
+    let content = [
+        tr(locale, "settings.title").to_string(),
+        trf(locale, "settings.objective", &[&tr(locale, format!("objective.{}", preferences.objective.code()).as_str())]),
+        trf(locale, "settings.multiplier", &[&format_number(locale, preferences.multiplier as f64 / 100., 2)]),
+        trf(locale, "settings.graph", &[&tr(locale, format!("graph.{}", preferences.graph.code()).as_str())]),
+        trf(locale, "settings.language", &[&preferences.locale.map_or(tr(locale, "language.automatic"), |locale| locale.name())]),
+        trf(locale, "settings.accessibility", &[&on_off(preferences.accessible)]),
+        trf(locale, "settings.announce", &[&on_off(preferences.auto_announce)]),
+        trf(locale, "settings.public", &[&on_off(preferences.public)]),
+    ].join("\n> ");
+    send_interaction_response_message(&ctx, &command, content, true).await
+}
+
+// Like /minicact_settings, but for the whole server. Anything left out stays the same, and it always shows the result.
+// Only server admins get this far.
+async fn config_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let locale = command_locale(&command).await;
+    let guild = match command.guild_id {
+        Some(guild) => guild,
+        None => return send_interaction_response_message(&ctx, &command, tr(locale, "config.guild_only"), true).await
+    };
+    let announcement_channel = match get_option(&command, "announcement_channel") {
+        Some(CommandDataOptionValue::Channel(channel)) => Some(channel.id),
+        _ => None
+    };
+    let flag = |name: &str| match get_option(&command, name) {
+        Some(CommandDataOptionValue::Boolean(b)) => Some(*b),
+        _ => None
+    };
+    let (clear_channel, leaderboard) = (flag("clear_announcement_channel").unwrap_or(false), flag("leaderboard"));
+    // "all" clears the list, which allows everything. Otherwise every name has to be a real command, so a typo can't turn everything off.
+    let allowed_commands = match get_option(&command, "allowed_commands") {
+        Some(CommandDataOptionValue::String(s)) if s.trim().eq_ignore_ascii_case("all") => Some(vec![]),
+        Some(CommandDataOptionValue::String(s)) => {
+            let known = command_names();
+            let names = s.split(|c: char| c.is_whitespace() || c == ',')
+                .map(|name| name.trim_start_matches('/'))
+                .filter(|name| !name.is_empty())
+                .map(|name| name.to_string())
+                .collect::<Vec<String>>();
+            if let Some(unknown) = names.iter().find(|name| !known.contains(&name.as_str())) {
+                let content = trf(locale, "config.unknown_command", &[&format!("`/{}`", unknown)]);
+                return send_interaction_response_message(&ctx, &command, content, true).await
+            }
+            Some(names)
+        },
+        _ => None
+    };
+    let role = |name: &str| match get_option(&command, name) {
+        Some(CommandDataOptionValue::Role(role)) => Some(role.id),
+        _ => None
+    };
+    let (add_role, remove_role) = (role("add_admin_role"), role("remove_admin_role"));
+    let config = if command.data.options.is_empty() {
+        guild_config(guild).await
+    } else {
+        info!("User {} changed the config of guild {}", logging::user(&command.user), guild);
+        update_guild_config(guild, |config| {
+            if clear_channel {
+                config.announcement_channel = None;
+            }
+            config.announcement_channel = announcement_channel.or(config.announcement_channel);
+            config.leaderboard = leaderboard.unwrap_or(config.leaderboard);
+            if let Some(allowed_commands) = allowed_commands {
+                config.allowed_commands = allowed_commands;
+            }
+            if let Some(role) = add_role.filter(|role| !config.admin_roles.contains(role)) {
+                config.admin_roles.push(role);
+            }
+            config.admin_roles.retain(|role| Some(*role) != remove_role);
+        }).await
+    };
+    let on_off = |b: bool| tr(locale, if b { "settings.on" } else { "settings.off" });
+    let list = |items: Vec<String>, empty: &str| if items.is_empty() { tr(locale, empty).to_string() } else { items.join(", ") };
+    let content = [
+        tr(locale, "config.title").to_string(),
+        trf(locale, "config.announcement_channel", &[&config.announcement_channel.map_or(tr(locale, "config.no_channel").to_string(), |channel| channel.mention().to_string())]),
+        trf(locale, "config.leaderboard", &[&on_off(config.leaderboard)]),
+        trf(locale, "config.allowed_commands", &[&list(config.allowed_commands.iter().map(|name| format!("`/{}`", name)).collect(), "config.all_commands")]),
+        trf(locale, "config.admin_roles", &[&list(config.admin_roles.iter().map(|role| role.mention().to_string()).collect(), "config.no_roles")]),
+    ].join("\n> ");
+    send_interaction_response_message(&ctx, &command, content, true).await
+}
+
+// Replaces whatever game the user had, since they went out of their way to paste this one in.
+// Daily games are the exception, since replacing one would be a free second try.
+async fn import_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let notation = match get_option(&command, "game") {
+        Some(CommandDataOptionValue::String(s)) => s.as_str(),
+        _ => ""
+    };
+    let locale = command_locale(&command).await;
+    // Same as every other way to start a game, anything started now would just get cut off.
+    if is_draining() {
+        return send_interaction_response_message(&ctx, &command, tr(locale, "shutdown.no_new_games"), true).await
+    }
+    let game = match Game::from_notation(notation) {
+        Ok(game) => game,
+        Err(why) => return send_interaction_response_message(&ctx, &command, why.message(locale), true).await
+    };
+    let mut active_games = ACTIVE_GAMES.lock().await;
+    let replaced = match active_games.get(&command.user.id) {
+        Some(old_game) if old_game.daily().is_some() => return command.create_interaction_response(&ctx.http, |response| {
+            response.kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|message| {
+                    message.content(tr(locale, "import.daily_in_progress"))
+                        .ephemeral(true)
+                        .components(|components| {
+                            components.create_action_row(|action_row| {
+                                make_button(action_row, CustomId::Restore, ButtonStyle::Primary, Some("↩"), Some(format!(" {}", tr(locale, "button.restore")).as_str()), false)
+                            })
+                        })
+                })
+        }).await,
+        Some(old_game) => format!("\n{}", trf(locale, "import.replaced", &[&format!("<t:{}:R>", old_game.started())])),
+        None => String::new()
+    };
+    command.create_interaction_response(&ctx.http, |response| {
+        response.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|message| {
+                message.content(format!("{}{}", tr(locale, "import.done"), replaced))
+                    .ephemeral(true)
+                    .components(|components| {
+                        components.create_action_row(|action_row| {
+                            make_button(action_row, CustomId::Restore, ButtonStyle::Primary, Some("↩"), Some(format!(" {}", tr(locale, "button.continue")).as_str()), false)
+                        })
+                    })
+            })
+    }).await?;
+    info!("User {} imported game {}", logging::user(&command.user), notation);
+    metrics::increment("minicact_games_started_total", &[("mode", game.mode().name())]);
     active_games.insert(command.user.id, game);
     Ok(())
-}
\ No newline at end of file
+}
+
+async fn export_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let locale = command_locale(&command).await;
+    let content = match ACTIVE_GAMES.lock().await.get(&command.user.id) {
+        Some(game) if !game.to_notation().is_empty() => trf(locale, "export.done", &[&game.to_notation()]),
+        Some(_) => tr(locale, "export.empty").to_string(),
+        None => tr(locale, "export.none").to_string()
+    };
+    send_interaction_response_message(&ctx, &command, content, true).await
+}
+
+const MAX_BENCHMARK_TICKETS: u32 = 1_000_000;
+
+// The choices for the benchmark's strategy option, named by "strategy.<name>" in the locale catalog.
+const BENCHMARK_STRATEGIES: [&str; 4] = ["optimal", "random", "diagonals", "rule"];
+
+async fn benchmark_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let locale = command_locale(&command).await;
+    let strategy_name = match get_option(&command, "strategy") {
+        Some(CommandDataOptionValue::String(s)) => s.clone(),
+        _ => return send_interaction_response_message(&ctx, &command, tr(locale, "benchmark.no_strategy"), true).await
+    };
+    let tickets = match get_option(&command, "tickets") {
+        Some(CommandDataOptionValue::Integer(n)) => (*n).clamp(1, MAX_BENCHMARK_TICKETS as i64) as u32,
+        _ => 100_000
+    };
+    let mut strategy: Box<dyn Strategy + Send> = match strategy_name.as_str() {
+        "random" => Box::new(Random::new()),
+        "diagonals" => Box::new(Diagonals),
+        "rule" => {
+            let rule = match get_option(&command, "rule") {
+                Some(CommandDataOptionValue::String(s)) => s.as_str(),
+                _ => ""
+            };
+            match Priority::parse(rule, locale) {
+                Ok(priority) => Box::new(priority),
+                Err(why) => return send_interaction_response_message(&ctx, &command, why, true).await
+            }
+        },
+        _ => Box::new(Optimal)
+    };
+    let exact = matches!(get_option(&command, "exact"), Some(CommandDataOptionValue::Boolean(true)));
+    if exact && !strategy.is_deterministic() {
+        return send_interaction_response_message(&ctx, &command, tr(locale, "benchmark.not_deterministic"), true).await
+    }
+    if exact {
+        info!("Exact benchmark of {} from user {}", strategy_name, logging::user(&command.user));
+    } else {
+        info!("Benchmark of {} over {} tickets from user {}", strategy_name, tickets, logging::user(&command.user));
+    }
+    // This can take a few seconds, which is longer than Discord will wait for a response.
+    command.create_interaction_response(&ctx.http, |response| {
+        response.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            .interaction_response_data(|data| data.ephemeral(true))
+    }).await?;
+    let shown_name = tr(locale, format!("strategy.{}", strategy_name).as_str());
+    let number = |value: f64, decimals: usize| format_number(locale, value, decimals);
+    // The exact distribution with optimal play, from startup.
+    let exact_mean = mean_payout(*TICKET_PAYOUT_DIST.lock().await);
+    if exact {
+        let histogram = tokio::task::spawn_blocking(move || {
+            evaluate_exact(strategy.as_mut())
+        }).await.map_err(|_| SerenityError::Other("Benchmark task panicked??"))?;
+        let (mean, p_data) = parse_data(histogram);
+        let content = trf(locale, "benchmark.exact", &[
+            &number(EXACT_TICKETS as f64, 0),
+            &shown_name,
+            &make_graph(p_data),
+            &number(mean, 2),
+            &number(std_dev_payout(p_data, mean), 2),
+            &number(exact_mean, 2),
+            &number(exact_mean - mean, 2),
+        ]);
+        command.edit_original_interaction_response(&ctx.http, |response| {
+            response.content(content)
+        }).await?;
+        return Ok(())
+    }
+    let result = tokio::task::spawn_blocking(move || {
+        simulate_batch(strategy.as_mut(), tickets, &mut StdRng::from_entropy())
+    }).await.map_err(|_| SerenityError::Other("Benchmark task panicked??"))?;
+    let (_, p_data) = parse_data(result.histogram);
+    let mut content = trf(locale, "benchmark.result", &[
+        &number(result.tickets as f64, 0),
+        &shown_name,
+        &make_graph(p_data),
+        &number(result.mean, 2),
+        &number(result.standard_error(), 2),
+        &number(result.variance.sqrt(), 2),
+        &number(exact_mean, 2),
+    ]);
+    if strategy_name == "optimal" {
+        // If the solver is right, this should almost always be under 3.
+        content.push_str(&trf(locale, "benchmark.optimal_error", &[&number((result.mean - exact_mean).abs() / result.standard_error(), 1)]));
+    } else {
+        content.push_str(&trf(locale, "benchmark.loss", &[&number(exact_mean - result.mean, 2)]));
+    }
+    command.edit_original_interaction_response(&ctx.http, |response| {
+        response.content(content)
+    }).await?;
+    Ok(())
+}
+
+// Like parse_data, but for something that's already a probability distribution, like TICKET_PAYOUT_DIST.
+fn mean_payout(p_data: [f64; 16]) -> f64 {
+    let mut mean = 0.;
+    for i in 0..16 {
+        mean += p_data[i] * (PAYOUT_VALUES[i + 1] as f64);
+    }
+    mean
+}
+
+fn std_dev_payout(p_data: [f64; 16], mean: f64) -> f64 {
+    let mut variance = 0.;
+    for i in 0..16 {
+        let diff = (PAYOUT_VALUES[i + 1] as f64) - mean;
+        variance += p_data[i] * diff * diff;
+    }
+    variance.sqrt()
+}
+
+// The same numbers as the metrics endpoint, for anyone who doesn't have Prometheus set up.
+async fn stats_command(ctx: Context, command: ApplicationCommandInteraction) -> Result<(), SerenityError> {
+    let locale = command_locale(&command).await;
+    let by_mode = |name: &'static str| {
+        let counts = [GameMode::Play, GameMode::Simulate, GameMode::Daily].map(|mode| metrics::counter(name, &[("mode", mode.name())]));
+        trf(locale, "stats.by_mode", &[&counts.iter().sum::<u64>(), &counts[0], &counts[1], &counts[2]])
+    };
+    let latency = |kind: &str| {
+        let summary = metrics::histogram_summary("interaction_duration_seconds", &[("kind", kind)]);
+        let count = trf(locale, format!("stats.{}s", kind).as_str(), &[&summary.map_or(0, |(count, _, _)| count)]);
+        match summary {
+            Some((_, average, Some(p95))) => trf(locale, "stats.latency", &[&count, &format_number(locale, average * 1000., 0), &format_number(locale, p95 * 1000., 0)]),
+            Some((_, average, None)) => trf(locale, "stats.latency_slow", &[&count, &format_number(locale, average * 1000., 0)]),
+            None => count,
+        }
+    };
+    let content = [
+        trf(locale, "stats.since", &[&format!("<t:{}:R>", metrics::started())]),
+        trf(locale, "stats.started", &[&by_mode("minicact_games_started_total")]),
+        trf(locale, "stats.finished", &[&by_mode("minicact_games_completed_total")]),
+        trf(locale, "stats.desyncs", &[&metrics::counter_total("minicact_desyncs_total"), &metrics::counter_total("minicact_disabled_presses_total")]),
+        trf(locale, "stats.errors", &[&metrics::counter("interaction_errors_total", &[("kind", "command")]), &metrics::counter("interaction_errors_total", &[("kind", "component")])]),
+        trf(locale, "stats.responded", &[&latency("command"), &latency("component")]),
+    ].join("\n> ");
+    send_interaction_response_message(&ctx, &command, content, true).await
+}
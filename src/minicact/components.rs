@@ -1,8 +1,21 @@
+use crate::logging;
+use crate::metrics;
 use crate::generate_components::make_button;
+use crate::guild_config::guild_config;
+use crate::registry::ComponentRoute;
+use crate::locale::{format_number, resolve_locale, tr, trf, Locale};
+use super::board_history::record_finished_game;
+use super::custom_id::CustomId;
+use super::daily::{mark_daily_started, new_game, record_daily_result};
 use super::game::*;
 use super::game::Action::*;
+use super::game::payout::PAYOUT_VALUES;
 use super::generate_components::*;
+use super::quiz::*;
 use super::recommendations::*;
+use super::preferences::{user_preferences, Preferences};
+use super::render::attach_image;
+use super::saved_games::track_message;
 use super::DAILY_PAYOUT_DIST;
 
 use serenity::model::prelude::component::ButtonStyle;
@@ -10,35 +23,76 @@ use serenity::model::prelude::interaction::InteractionResponseType;
 use serenity::model::prelude::interaction::message_component::MessageComponentInteraction;
 use serenity::prelude::*;
 
-use lazy_static::lazy_static;
+// Every Mini Cactpot custom_id starts with this, and CustomId sorts out the rest.
+pub const COMPONENTS: &[ComponentRoute] = &[
+    ComponentRoute {
+        prefix: "minicact_",
+        handler: |ctx, component| Box::pin(handle_component(ctx, component)),
+    },
+];
 
-use regex::Regex;
-
-use chrono::Local;
-
-pub async fn handle_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
+async fn handle_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
+    // Games can be public, so anyone in the channel can see their buttons. Only whoever started one gets to press them.
+    if let Some(interaction) = &component.message.interaction {
+        if interaction.user.id != component.user.id {
+            return not_yours_response(&component, &ctx).await
+        }
+    }
+    let custom_id: CustomId = match component.data.custom_id.parse() {
+        Ok(custom_id) => custom_id,
+        Err(why) => {
+            // Most likely a message from before the bot updated, so just treat it the same as a game that isn't tracked anymore.
+            warn!("User {} sent unreadable custom_id {}: {}", logging::user(&component.user), component.data.custom_id, why);
+            return removed_game_response(&component, &ctx).await
+        }
+    };
+    // Components from a game the user has since replaced would otherwise be applied to their current game,
+    // as long as both happened to be on the same step.
+    if let Some(nonce) = custom_id.nonce() {
+        let active_games = ACTIVE_GAMES.lock().await;
+        if let Some(game) = active_games.get(&component.user.id) {
+            if game.nonce() != nonce {
+                info!("User {} pressed {} from a replaced game.", logging::user(&component.user), component.data.custom_id);
+                return stale_game_response(&component, &ctx, game).await
+            }
+        }
+    }
     // Add any custom components here
-    match component.data.custom_id.as_str() {
-        s if s.contains("X") => disabled_component(ctx, component).await,
-        "minicact_reset" => reset_component(ctx, component).await,
-        "minicact_undo" => undo_component(ctx, component).await,
-        "minicact_last_input" => last_input_component(ctx, component).await,
-        "minicact_announce_results" => announce_results_component(ctx, component).await,
-        "minicact_restore" => restore_component(ctx, component).await,
-        s if s.starts_with("minicact_full_reset") => full_reset_component(ctx, component).await,
-        "minicact_restart_simulation" => restart_simulation_component(ctx, component).await,
-        _ => minicact_component(ctx, component).await
+    match custom_id {
+        CustomId::DisabledTile { .. } => disabled_component(ctx, component).await,
+        CustomId::Reset { .. } => reset_component(ctx, component).await,
+        CustomId::Undo { .. } => undo_component(ctx, component).await,
+        CustomId::LastInput { .. } => last_input_component(ctx, component).await,
+        CustomId::AnnounceResults { total } => announce_results_component(ctx, component, total).await,
+        CustomId::Restore => restore_component(ctx, component).await,
+        CustomId::FullReset { mode } => full_reset_component(ctx, component, mode).await,
+        CustomId::RestartSimulation => start_game_component(ctx, component, GameMode::Simulate, InteractionResponseType::UpdateMessage).await,
+        // The daily puzzle post is public, so the game has to go in a new message instead.
+        CustomId::DailyPlay => start_game_component(ctx, component, GameMode::Daily, InteractionResponseType::ChannelMessageWithSource).await,
+        CustomId::Replay { ticket, .. } => replay_component(ctx, component, ticket as usize).await,
+        CustomId::QuizTile { nonce, position } => quiz_answer_component(ctx, component, nonce, position as usize).await,
+        CustomId::QuizLine { nonce } => {
+            let line = component.data.values
+                .first().and_then(|x| x.parse().ok())
+                .ok_or(SerenityError::Other("Quiz line component didn't return a line??"))?;
+            quiz_answer_component(ctx, component, nonce, line).await
+        },
+        CustomId::QuizNext => quiz_next_component(ctx, component).await,
+        CustomId::Numpad { .. } | CustomId::Tile { .. } | CustomId::Payout { .. } | CustomId::Line { .. }
+        | CustomId::RevealBoard { .. } | CustomId::BoardNumpad { .. } => minicact_component(ctx, component, custom_id).await
     }
 }
 
 async fn disabled_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
+    metrics::increment("minicact_disabled_presses_total", &[]);
     // truncates any errors/warnings that the user might have had
     let opt_i = component.message.content.find(component.user.mention().to_string().as_str());
     let mut s = component.message.content.clone();
     if let Some(i) = opt_i {
        s.truncate(i)
     }
-    let content = format!("{}\n{} That button is currently disabled. If you made a mistake, press `Undo` ↩ / `Reset` 🔄", s, component.user.mention());
+    let locale = component_locale(&component).await;
+    let content = format!("{}\n{} {}", s, component.user.mention(), tr(locale, "game.disabled"));
     component.create_interaction_response(ctx.http, |response| {
         response.kind(InteractionResponseType::UpdateMessage)
             .interaction_response_data(|message| {
@@ -47,101 +101,142 @@ async fn disabled_component(ctx: Context, component: MessageComponentInteraction
     }).await
 }
 
-async fn create_minicact_response<'a>(component: &MessageComponentInteraction, ctx: &Context, game: &Game, desync: bool) -> Result<(), SerenityError> {
+// `warning` is shown to the user under the game, for when something didn't go the way they expected.
+// Recommendations can take a while, so callers give this a copy of the game instead of holding ACTIVE_GAMES the whole time.
+async fn create_minicact_response(component: &MessageComponentInteraction, ctx: &Context, game: &Game, warning: Option<String>) -> Result<(), SerenityError> {
     let action = game.next_action();
-    let (recommendation, mut content) = if let ChoosePosition(_) = action {
+    let preferences = user_preferences(component.user.id).await;
+    let accessible = preferences.accessible;
+    let locale = component_locale(component).await;
+    // If they just finished entering a board, show them how it went above whatever else is in the message.
+    let summary = game.last_finished_ticket()
+        .filter(|_| !matches!(action, RevealBoard(_)))
+        .and_then(|t| summarize_board(game, t, accessible, locale));
+    let image = preferences.image();
+    let (recommendation, mut content, png) = if let ChoosePosition(_) = action {
         match game.last_action() {
-            EnterPayout(_) | Start => (255 as usize, "Enter the already revealed tile:".to_string()), // Can't recommend, haven't seen the first tile yet!
-            _ => recommend_position(&game)
+            EnterPayout(_) | RevealBoard(_) | Start => (255, format!("{}{}", summary.unwrap_or_default(), tr(locale, "game.enter_first_tile")), None), // Can't recommend, haven't seen the first tile yet!
+            _ => {
+                // Simulated games start the next ticket right away, so the summary goes above the recommendation instead.
+                let (recommendation, content, png) = recommend_position(game, &preferences, locale).await?;
+                (recommendation, format!("{}{}", summary.unwrap_or_default(), content), png)
+            }
         }
         
     } else if let EnterPayout(_) = action {
-        recommend_line(game)
+        recommend_line(game, &preferences, locale)
+    } else if let (RevealBoard(position), Some(t)) = (action, game.finished_ticket()) {
+        let tile = if accessible { trf(locale, "the_tile", &[&position_name(locale, position as usize).to_lowercase()]) } else { "❔".to_string() };
+        (255, format!("{}{}", make_board_grid(&game.ticket_board(t), Some(position as usize), accessible, locale), trf(locale, "game.enter_board_number", &[&tile])), None)
+    } else if let Some(summary) = summary {
+        (0, summary, None)
     } else {
         let opt_i = component.message.content.find(component.user.mention().to_string().as_str());  // finds if the user hit a disabled button last time
         let mut s = component.message.content.clone();
         if let Some(i) = opt_i {
             s.truncate(i)
         };
-        (0, s)  // the zero does nothing, because we have guaranteed that we are in the RevealNumber case.
+        (0, s, None)  // the zero does nothing, because we have guaranteed that we are in the RevealNumber or Done case.
     };
-    if desync {
-        content.push_str(format!("\n{} desync detected and fixed. Please double-check that everything is correct.", component.user.mention()).as_str());
+    if let Some(warning) = warning {
+        content.push_str(format!("\n{} {}", component.user.mention(), warning).as_str());
     }
     component.create_interaction_response(&ctx.http, |response| {
         response.kind(InteractionResponseType::UpdateMessage)
             .interaction_response_data(|message| {
+                if image {
+                    attach_image(message, png);
+                }
                 message.content(content)
                     .components(|components| {
                         match action {
-                            ChoosePosition(_) => {make_game_rows(components, &game, recommendation);},
-                            RevealNumber(_) => {make_numpad_rows(components, &game);},
-                            EnterPayout(_) => {make_game_rows(components, &game, recommendation); make_payout_dropdown(components, &game);},
+                            ChoosePosition(_) => {make_game_rows(components, game, recommendation, accessible, locale);},
+                            RevealNumber(_) | RevealBoard(_) => {make_numpad_rows(components, game, accessible, locale);},
+                            EnterPayout(_) => {make_game_rows(components, game, recommendation, accessible, locale); make_payout_dropdown(components, game, locale);},
                             _ => ()  // in the Done case, this means that only the reset_bar will be printed. It handles this specially.
                         }
-                        make_reset_bar(components, &game)
+                        make_board_row(components, game, locale);
+                        make_reset_bar(components, game, accessible, locale)
                     })  
                 })
-        }).await
+        }).await?;
+    track_message(component.user.id, &component.token, locale).await;
+    Ok(())
 }
 
-async fn minicact_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
-    // custom_id contains game.index() to catch desyncs
-    let (custom_id_index_index, _) = component.data.custom_id.char_indices().rev().nth(3).ok_or(SerenityError::Other("custom_id less than 4 characters???"))?;
-    // this only works because I am 100% confident that custom_id is an ASCII string
-    let custom_id_index:usize = component.data.custom_id[custom_id_index_index..(custom_id_index_index+2)].parse().map_err(|_| SerenityError::Other("custom_id index failed to parse!!"))?;
+async fn minicact_component(ctx: Context, component: MessageComponentInteraction, custom_id: CustomId) -> Result<(), SerenityError> {
     let mut active_games = ACTIVE_GAMES.lock().await;
     let game = handle_game_mut(active_games.get_mut(&component.user.id), &component, &ctx).await?;
     let action = game.next_action();
-    if game.index() != custom_id_index {  // Desync guaranteed.
-        println!("{:?}\t User {} with Id {} desynced from index {} to {}. Resyncing...", Local::now(), component.user.name, component.user.id, custom_id_index, game.index());
-        return create_minicact_response(&component, &ctx, game, true).await
+    // custom_id contains game.index() to catch desyncs, or the position being asked about for the rest of the board.
+    let in_sync = match custom_id {
+        CustomId::BoardNumpad { position, .. } => action == RevealBoard(position),
+        _ => custom_id.index() == Some(game.index())
+    };
+    if !in_sync {  // Desync guaranteed.
+        warn!("User {} desynced from {} to index {}. Resyncing...", logging::user(&component.user), custom_id, game.index());
+        metrics::increment("minicact_desyncs_total", &[]);
+        let warning = tr(component_locale(&component).await, "game.desync").to_string();
+        let game = game.clone();
+        drop(active_games);
+        return create_minicact_response(&component, &ctx, &game, Some(warning)).await
     }
-    // This is not fun but it never panics!
-    // It was genuinely a massive pain to do this with no .unwrap().
-    let mut desync = false;
-    match action {
-        RevealNumber(_) | ChoosePosition(_) => {
-            let num = component.data.custom_id.chars().last()
-                .and_then(|x| x.to_digit(10))
-                .ok_or(SerenityError::Other("Failed to convert last digit of custom_id during minicact_component??"))? as u8;
-            match action {
-                RevealNumber(_) if component.data.custom_id.contains("numpad") => game.set_number(num),
-                ChoosePosition(_) if component.data.custom_id.contains("game") => game.set_position(num),
-                _ => {
-                    println!("{:?}\t User {} with Id {} desynced on action {:?}. Resyncing...", Local::now(), component.user.name, component.user.id, action);
-                    desync = true;
-                }
-            }
-        },
-        EnterPayout(_) if component.data.custom_id.contains("payout") => {
-            game.set_payout(component.data.values
+    let result = match custom_id {
+        CustomId::Numpad { number, .. } => game.set_number(number),
+        CustomId::Tile { position, .. } => game.set_position(position),
+        CustomId::RevealBoard { .. } => game.begin_reveal(),
+        CustomId::BoardNumpad { number, .. } => game.set_board_number(number),
+        CustomId::Payout { .. } => {
+            let payout = component.data.values
                 .first().ok_or(SerenityError::Other("Payout component didn't return a value??"))?
-                .into());
+                .into();
+            game.set_payout(payout)
+        },
+        CustomId::Line { .. } => {
+            let line = component.data.values
+                .first().and_then(|x| x.parse().ok())
+                .ok_or(SerenityError::Other("Line component didn't return a line??"))?;
+            game.choose_line(line)
         },
-        _ => {
-            println!("{:?}\t User {} with Id {} desynced on action {:?}. Resyncing...", Local::now(), component.user.name, component.user.id, action);
-            desync = true;
+        _ => return Err(SerenityError::Other("minicact_component got a custom_id it doesn't handle??"))
+    };
+    // The game refuses anything it wasn't expecting, so even if the user "desyncs" somehow, calling create_minicact_response will show them the correct game state.
+    let locale = component_locale(&component).await;
+    let warning = match result {
+        Ok(()) => None,
+        Err(e @ GameError::WrongAction(_)) => {
+            warn!("User {} desynced on action {:?}: {:?}. Resyncing...", logging::user(&component.user), action, e);
+            metrics::increment("minicact_desyncs_total", &[]);
+            Some(tr(locale, "game.desync").to_string())
+        },
+        Err(e) => {
+            info!("User {} was refused on action {:?}: {:?}", logging::user(&component.user), action, e);
+            Some(e.message(locale))
         }
-    }
-    // conveniently, even if the user "desyncs" somehow, calling create_minicact_response will show them the correct game state.
+    };
 
     // Now that we have either mutated the board (or not), time to show the user!
-    create_minicact_response(&component, &ctx, game, desync).await
+    let game = game.clone();
+    drop(active_games);
+    create_minicact_response(&component, &ctx, &game, warning).await
 }
 
 async fn reset_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
     let mut active_games = ACTIVE_GAMES.lock().await;
     let game = handle_game_mut(active_games.get_mut(&component.user.id), &component, &ctx).await?;
     game.reset();
-    create_minicact_response(&component, &ctx, game, false).await
+    let game = game.clone();
+    drop(active_games);
+    create_minicact_response(&component, &ctx, &game, None).await
 }
 
 async fn undo_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
     let mut active_games = ACTIVE_GAMES.lock().await;
     let game = handle_game_mut(active_games.get_mut(&component.user.id), &component, &ctx).await?;
     game.undo();
-    create_minicact_response(&component, &ctx, game, false).await
+    let game = game.clone();
+    drop(active_games);
+    create_minicact_response(&component, &ctx, &game, None).await
 }
 
 // note that the only time this component IS NOT disabled is when the user has played ALL 3 games.
@@ -150,112 +245,262 @@ async fn last_input_component(ctx: Context, component: MessageComponentInteracti
     let game = handle_game_mut(active_games.get_mut(&component.user.id), &component, &ctx).await?;
     let total = game.total_payout();
     let daily_payout_dist = DAILY_PAYOUT_DIST.lock().await;
-    let percentile = daily_payout_dist.get(&total).ok_or(SerenityError::Other("Somehow total payout is not in daily_payout_dist??"))?.clone();
+    let percentile = *daily_payout_dist.get(&total).ok_or(SerenityError::Other("Somehow total payout is not in daily_payout_dist??"))?;
     drop(daily_payout_dist);
-    let simulated = game.is_simulated();
+    let mode = game.mode();
+    let preferences = user_preferences(component.user.id).await;
+    let accessible = preferences.accessible;
+    let locale = component_locale(&component).await;
+    record_daily_result(game, component.user.id).await;
+    let mut content = trf(locale, "game.finished", &[&format_number(locale, preferences.scale(total as f64), 0), &format_number(locale, percentile, 2)]);
+    content.push_str(format!("\n{}", trf(locale, "game.share", &[&game.to_notation()])).as_str());
+    match (mode, game.seed()) {
+        (GameMode::Daily, _) => content.push_str(format!("\n{}", tr(locale, "game.daily_note")).as_str()),
+        (GameMode::Simulate, Some(seed)) => content.push_str(format!("\n{}", trf(locale, "game.seed_note", &[&seed])).as_str()),
+        _ => ()
+    }
+    // Simulated games always know every board, but anyone who entered theirs gets to see them too.
+    for ticket in 0..3 {
+        if let (Some(board), Some(line)) = (game.full_board(ticket), game.chosen_line(ticket)) {
+            let paid = trf(locale, "game.ticket_paid", &[&(ticket + 1), &line_name_inline(locale, line), &format_number(locale, preferences.scale(PAYOUT_VALUES[game.payout(ticket) as usize] as f64), 0)]);
+            content.push_str(format!("\n\n{}\n{}", paid, make_board_grid(&board, None, accessible, locale)).as_str());
+        }
+    }
+    record_finished_game(game);
     active_games.remove(&component.user.id);
+    metrics::increment("minicact_games_completed_total", &[("mode", mode.name())]);
+    let image = preferences.image();
+    // Anyone who always announces doesn't need the button.
+    let auto_announce = mode == GameMode::Play && preferences.auto_announce;
     component.create_interaction_response(&ctx.http, |response|{
         response.kind(InteractionResponseType::UpdateMessage)
             .interaction_response_data(|message| {
-                message.content(format!("Thanks for using this bot! Feel free to dismiss this message.\nYour total payout is {} MGP, which is {:.2} percentile.", total, percentile))
+                // The last recommendation doesn't mean much once the game is over.
+                if image {
+                    attach_image(message, None);
+                }
+                message.content(content)
                     .components(|components| {
-                        components.create_action_row(|action_row| {
-                            if simulated {
+                        match mode {
+                            GameMode::Play if auto_announce => components,
+                            GameMode::Play => components.create_action_row(|action_row| {
                                 make_button(action_row, 
-                                    "minicact_restart_simulation", 
+                                    CustomId::AnnounceResults { total }, 
                                     ButtonStyle::Primary, 
-                                    Some("🔄"), 
-                                    Some(" Play again?"), 
+                                    Some("📢"), 
+                                    Some(format!(" {}", tr(locale, "button.announce")).as_str()), 
                                     false)
-                            } else {
+                            }),
+                            GameMode::Simulate => components.create_action_row(|action_row| {
                                 make_button(action_row, 
-                                    "minicact_announce_results", 
+                                    CustomId::RestartSimulation, 
                                     ButtonStyle::Primary, 
-                                    Some("📢"), 
-                                    Some(" Announce your results!"), 
+                                    Some("🔄"), 
+                                    Some(format!(" {}", tr(locale, "button.play_again")).as_str()), 
                                     false)
-                            }
-                            
-                        })
+                            }),
+                            // The daily puzzle gets announced for everyone at once.
+                            GameMode::Daily => components
+                        }
                     })  
             })
-    }).await
+    }).await?;
+    if auto_announce {
+        announce_results(&component, &ctx, total, percentile, &preferences).await?;
+    }
+    Ok(())
 }
 
-// Don't want to recompile the regex every time, so I made it a static
-// Regex that just matches a number surrounded by whitespace, with possible decimal point.
-// It technically also matches a period, but all periods in the relevant message are not surrounded by whitespace so it is ok. 
-lazy_static!{static ref MINICACT_REGEX: Regex = Regex::new(r"\s([\d.]+)\s").expect("MINICACT_REGEX errored on creation???????"); }
-
-async fn announce_results_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
-    let mut captures = MINICACT_REGEX.captures_iter(component.message.content.as_str());
-    // These use a regex to find the data in the message, since it should already be there. No need to recompute it!
-    let total = captures.next().and_then(|x| x.get(1)).ok_or(SerenityError::Other("Couldn't find total in results message!!"))?.as_str();
-    let percentile = captures.next().and_then(|x| x.get(1)).ok_or(SerenityError::Other("Couldn't find percentile in results message!!"))?.as_str();
+// The total comes from the button itself, since the message could be in any language. The percentile is cheap to look up again.
+async fn announce_results_component(ctx: Context, component: MessageComponentInteraction, total: u16) -> Result<(), SerenityError> {
+    let percentile = *DAILY_PAYOUT_DIST.lock().await.get(&total).ok_or(SerenityError::Other("Announced total payout is not in daily_payout_dist??"))?;
     component.create_interaction_response(&ctx.http, |response|{
         response.kind(InteractionResponseType::UpdateMessage)
             .interaction_response_data(|message| {
                 message.components(|components| { components })  
             })
     }).await?;
-    component.create_followup_message(&ctx.http, |message| {
-        message.content(format!("{} earned {} MGP from Mini Cactpot today, which is {} percentile!", component.user.mention(), total, percentile))
-    }).await?;
+    announce_results(&component, &ctx, total, percentile, &user_preferences(component.user.id).await).await
+}
+
+// The announcement is in the language of whoever is announcing, since they're the one choosing to post it.
+// It goes to the server's announcement channel if it has one, and right under the game otherwise.
+async fn announce_results(component: &MessageComponentInteraction, ctx: &Context, total: u16, percentile: f64, preferences: &Preferences) -> Result<(), SerenityError> {
+    let locale = component_locale(component).await;
+    let content = trf(locale, "game.announce", &[&component.user.mention(), &format_number(locale, preferences.scale(total as f64), 0), &format_number(locale, percentile, 2)]);
+    let announcement_channel = match component.guild_id {
+        Some(guild) => guild_config(guild).await.announcement_channel.filter(|channel| *channel != component.channel_id),
+        None => None
+    };
+    if let Some(channel) = announcement_channel {
+        match channel.send_message(&ctx.http, |message| message.content(&content)).await {
+            Ok(_) => return Ok(()),
+            // Probably missing permissions there, so it's better to announce here than not at all.
+            Err(why) => warn!("Failed to announce results in channel {}: {}", channel, why)
+        }
+    }
+    component.create_followup_message(&ctx.http, |message| message.content(content)).await?;
     Ok(())
 }
 
+// Sent as a new message, so the game stays where it is.
+async fn replay_component(ctx: Context, component: MessageComponentInteraction, ticket: usize) -> Result<(), SerenityError> {
+    let active_games = ACTIVE_GAMES.lock().await;
+    let game = handle_game(active_games.get(&component.user.id), &component, &ctx).await?;
+    let preferences = user_preferences(component.user.id).await;
+    let content = make_replay(game, ticket, preferences.accessible, component_locale(&component).await).ok_or(SerenityError::Other("Replay pressed on a ticket without a full board??"))?;
+    let ephemeral = preferences.ephemeral(game.mode() == GameMode::Daily);
+    drop(active_games);
+    component.create_interaction_response(&ctx.http, |response| {
+        response.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|message| {
+                message.content(content).ephemeral(ephemeral)
+            })
+    }).await
+}
+
 // These are both used in the case that the user typed /minicact_play and they already had a game started.
 
 async fn restore_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
     let active_games = ACTIVE_GAMES.lock().await;
-    let game= handle_game(active_games.get(&component.user.id), &component, &ctx).await?;
-    create_minicact_response(&component, &ctx, game, false).await
+    let game = handle_game(active_games.get(&component.user.id), &component, &ctx).await?.clone();
+    drop(active_games);
+    create_minicact_response(&component, &ctx, &game, None).await
 }
 
-async fn full_reset_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
-    let mut active_games = ACTIVE_GAMES.lock().await;
-    let game = if component.data.custom_id.contains("sim") {Game::new_simulated()} else {Game::new()};
-    create_minicact_response(&component, &ctx, &game, false).await?;
-    active_games.insert(component.user.id, game);
+async fn full_reset_component(ctx: Context, component: MessageComponentInteraction, mode: GameMode) -> Result<(), SerenityError> {
+    let game = match new_game(mode, component.guild_id, component.user.id, None, component_locale(&component).await).await {
+        Ok(game) => game,
+        Err(why) => return component.create_interaction_response(&ctx.http, |response| {
+            response.kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|message| {
+                    message.content(format!("{} {}", component.user.mention(), why))
+                        .components(|components| { components })
+                })
+        }).await
+    };
+    create_minicact_response(&component, &ctx, &game, None).await?;
+    metrics::increment("minicact_games_started_total", &[("mode", mode.name())]);
+    mark_daily_started(&game, component.user.id).await;
+    ACTIVE_GAMES.lock().await.insert(component.user.id, game);
     Ok(())
 }
 
-async fn restart_simulation_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
-    let mut active_games = ACTIVE_GAMES.lock().await;
-    if active_games.contains_key(&component.user.id) {  // if user has an active game already, warn them so they don't lose any data unintentionally.
+// `kind` is UpdateMessage to replace the message the button was on, or ChannelMessageWithSource to leave it alone.
+async fn start_game_component(ctx: Context, component: MessageComponentInteraction, mode: GameMode, kind: InteractionResponseType) -> Result<(), SerenityError> {
+    let locale = component_locale(&component).await;
+    // Not held until the game goes in, since recommending a simulation's first tile can take a while.
+    if ACTIVE_GAMES.lock().await.contains_key(&component.user.id) {  // if user has an active game already, warn them so they don't lose any data unintentionally.
         return component.create_interaction_response(&ctx.http, |response| {
-            response.kind(InteractionResponseType::UpdateMessage)
+            response.kind(kind)
                 .interaction_response_data(|message| {
-                    message.content(format!("{} you already have a game started.\nWould you like to:\n> ↩ Restore your previous game\n> 🔄 Discard it and start from scratch", component.user.mention()))
+                    message.content(trf(locale, "game.already_started", &[&component.user.mention()]))
                         .ephemeral(true)
                         .components(|components| {
                             components.create_action_row(|action_row| {
-                                make_button(action_row, "minicact_restore", ButtonStyle::Primary, Some("↩"), Some(" Restore"), false);
-                                make_button(action_row, "minicact_full_reset_sim", ButtonStyle::Primary, Some("🔄"), Some(" Discard"), false)
+                                make_button(action_row, CustomId::Restore, ButtonStyle::Primary, Some("↩"), Some(format!(" {}", tr(locale, "button.restore")).as_str()), false);
+                                make_button(action_row, CustomId::FullReset { mode }, ButtonStyle::Primary, Some("🔄"), Some(format!(" {}", tr(locale, "button.discard")).as_str()), false)
                             })
                         })
                 })
         }).await
     }
     // Otherwise, we're good to go! Just make the default board.
-    let game = Game::new_simulated();
+    let game = match new_game(mode, component.guild_id, component.user.id, None, locale).await {
+        Ok(game) => game,
+        Err(why) => return component.create_interaction_response(&ctx.http, |response| {
+            response.kind(kind)
+                .interaction_response_data(|message| {
+                    message.content(format!("{} {}", component.user.mention(), why))
+                        .ephemeral(true)
+                        .components(|components| { components })
+                })
+        }).await
+    };
+    let preferences = user_preferences(component.user.id).await;
+    let accessible = preferences.accessible;
+    let (recommendation, content, png) = recommend_start(&game, &preferences, locale).await?;
     component.create_interaction_response(&ctx.http, |response| {
-        response.kind(InteractionResponseType::UpdateMessage)
+        response.kind(kind)
             .interaction_response_data(|message| {
-                message.content("Enter the already revealed tile:")
-                    .ephemeral(true)
+                if preferences.image() {
+                    attach_image(message, png);
+                }
+                message.content(content)
+                    .ephemeral(preferences.ephemeral(mode == GameMode::Daily))
                     .components(|components| {
-                        make_game_rows(components, &game, 255);
-                        make_reset_bar(components, &game)
+                        make_game_rows(components, &game, recommendation, accessible, locale);
+                        make_reset_bar(components, &game, accessible, locale)
                     })
             })
     }).await?;
     // Rust is a beautiful language...
     // I have to make sure that the message returns successfully before I can put the game into active_games.
-    active_games.insert(component.user.id, game);
+    metrics::increment("minicact_games_started_total", &[("mode", mode.name())]);
+    mark_daily_started(&game, component.user.id).await;
+    ACTIVE_GAMES.lock().await.insert(component.user.id, game);
+    track_message(component.user.id, &component.token, locale).await;
+    Ok(())
+}
+
+async fn quiz_answer_component(ctx: Context, component: MessageComponentInteraction, nonce: u32, choice: usize) -> Result<(), SerenityError> {
+    let mut active_quizzes = ACTIVE_QUIZZES.lock().await;
+    let quiz = match active_quizzes.get(&component.user.id) {
+        Some(quiz) if quiz.nonce() == nonce => quiz,
+        // Either they already answered this one, or they've moved on to another question since.
+        _ => return answered_quiz_response(&component, &ctx).await
+    };
+    let grade = quiz.grade(choice).ok_or(SerenityError::Other("Quiz answer wasn't something the player could pick??"))?;
+    let mut quiz_scores = QUIZ_SCORES.lock().await;
+    let score = quiz_scores.entry(component.user.id).or_default();
+    score.record(&grade);
+    let accessible = user_preferences(component.user.id).await.accessible;
+    let locale = component_locale(&component).await;
+    let content = quiz.answer(&grade, score, accessible, locale);
+    drop(quiz_scores);
+    component.create_interaction_response(&ctx.http, |response| {
+        response.kind(InteractionResponseType::UpdateMessage)
+            .interaction_response_data(|message| {
+                message.content(content)
+                    .components(|components| make_quiz_rows(components, quiz, Some(&grade), accessible, locale))
+            })
+    }).await?;
+    active_quizzes.remove(&component.user.id);
     Ok(())
 }
 
+async fn quiz_next_component(ctx: Context, component: MessageComponentInteraction) -> Result<(), SerenityError> {
+    let mut active_quizzes = ACTIVE_QUIZZES.lock().await;
+    let quiz = Quiz::random(&mut rand::thread_rng());
+    let locale = component_locale(&component).await;
+    let content = quiz.question(QUIZ_SCORES.lock().await.get(&component.user.id), locale);
+    let accessible = user_preferences(component.user.id).await.accessible;
+    component.create_interaction_response(&ctx.http, |response| {
+        response.kind(InteractionResponseType::UpdateMessage)
+            .interaction_response_data(|message| {
+                message.content(content)
+                    .components(|components| make_quiz_rows(components, &quiz, None, accessible, locale))
+            })
+    }).await?;
+    active_quizzes.insert(component.user.id, quiz);
+    Ok(())
+}
+
+async fn answered_quiz_response(component: &MessageComponentInteraction, ctx: &Context) -> Result<(), SerenityError> {
+    let locale = component_locale(component).await;
+    component.create_interaction_response(&ctx.http, |response|{
+        response.kind(InteractionResponseType::UpdateMessage)
+            .interaction_response_data(|message| {
+                message.content(trf(locale, "quiz.answered", &[&component.user.mention()]))
+                    .components(|components| {
+                        components.create_action_row(|action_row| {
+                            make_button(action_row, CustomId::QuizNext, ButtonStyle::Primary, Some("➡"), Some(format!(" {}", tr(locale, "button.next_question")).as_str()), false)
+                        })
+                    })
+            })
+    }).await
+}
+
 // These are necessary in case the user pushed a component but they did not have a game started.
 
 async fn handle_game_mut<'a>(maybe_game: Option<&'a mut Game>, component: &MessageComponentInteraction, ctx: &Context) -> Result<&'a mut Game, SerenityError> {
@@ -263,7 +508,7 @@ async fn handle_game_mut<'a>(maybe_game: Option<&'a mut Game>, component: &Messa
         Some(game) => Ok(game),
         None => {
             removed_game_response(component, ctx).await?;
-            println!("{:?}\t Failed to get game for user {} with Id {} while attempting {}. Probably fine.", Local::now(), component.user.name, component.user.id, component.data.custom_id);
+            debug!("Failed to get game for user {} while attempting {}. Probably fine.", logging::user(&component.user), component.data.custom_id);
             Err(SerenityError::Other("Failed to get game for user. Probably fine."))
         }
     }
@@ -274,19 +519,53 @@ async fn handle_game<'a>(maybe_game: Option<&'a Game>, component: &MessageCompon
         Some(game) => Ok(game),
         None => {
             removed_game_response(component, ctx).await?;
-            println!("{:?}\t Failed to get game for user {} with Id {} while attempting {}. Probably fine.", Local::now(), component.user.name, component.user.id, component.data.custom_id);
+            debug!("Failed to get game for user {} while attempting {}. Probably fine.", logging::user(&component.user), component.data.custom_id);
             Err(SerenityError::Other("Failed to get game for user. Probably fine."))
         }
     }
 }
 
+// In the case that the user pressed something on an old game's message, this tells them where their current game is.
+// It also lets them pull their current game into this message, in case they can't find the other one anymore.
+async fn stale_game_response(component: &MessageComponentInteraction, ctx: &Context, game: &Game) -> Result<(), SerenityError> {
+    let locale = component_locale(component).await;
+    component.create_interaction_response(&ctx.http, |response|{
+        response.kind(InteractionResponseType::UpdateMessage)
+            .interaction_response_data(|message| {
+                message.content(trf(locale, "game.stale", &[&component.user.mention(), &format!("<t:{}:R>", game.started())]))
+                    .components(|components| {
+                        components.create_action_row(|action_row| {
+                            make_button(action_row, CustomId::Restore, ButtonStyle::Primary, Some("↩"), Some(format!(" {}", tr(locale, "button.restore")).as_str()), false)
+                        })
+                    })
+            })
+    }).await
+}
+
+// Sent as a new message, so the other person's game is left alone.
+async fn not_yours_response(component: &MessageComponentInteraction, ctx: &Context) -> Result<(), SerenityError> {
+    let locale = component_locale(component).await;
+    component.create_interaction_response(&ctx.http, |response|{
+        response.kind(InteractionResponseType::ChannelMessageWithSource)
+            .interaction_response_data(|message| {
+                message.content(tr(locale, "game.not_yours")).ephemeral(true)
+            })
+    }).await
+}
+
 // In the case that the user does not have a game, this lets them know to start a new one instead.
 async fn removed_game_response(component: &MessageComponentInteraction, ctx: &Context) -> Result<(), SerenityError> {
+    let locale = component_locale(component).await;
     component.create_interaction_response(&ctx.http, |response|{
         response.kind(InteractionResponseType::UpdateMessage)
             .interaction_response_data(|message| {
-                message.content(format!("{} Your game is no longer being tracked, meaning that either you completed it elsewhere or the bot restarted.\nFeel free to dismiss this message. Use /minicact_play to start a new game.", component.user.mention()))
+                message.content(trf(locale, "game.removed", &[&component.user.mention()]))
                     .components(|components| { components })  
             })
     }).await
-}
\ No newline at end of file
+}
+
+// Which language to answer in, the same way commands pick theirs.
+async fn component_locale(component: &MessageComponentInteraction) -> Locale {
+    resolve_locale(user_preferences(component.user.id).await.locale, component.guild_id, &component.locale).await
+}
@@ -0,0 +1,248 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use super::game::GameMode;
+
+use CustomId::*;
+
+// Every custom_id this module sends to Discord. They are only ever built with to_string() and read back with parse(),
+// so nothing else has to care about what the string actually looks like.
+// Anything tied to a specific game carries that game's nonce, and anything tied to a specific step carries game.index().
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CustomId {
+    Numpad { nonce: u32, index: u8, number: u8 },
+    Tile { nonce: u32, index: u8, position: u8 },
+    // The tiles that show up while choosing a line. Discord wants every custom_id in a message to be unique, so these still need a position.
+    DisabledTile { nonce: u32, index: u8, position: u8 },
+    Payout { nonce: u32, index: u8 },
+    // Simulated games choose a line instead of entering a payout.
+    Line { nonce: u32, index: u8 },
+    RevealBoard { nonce: u32, index: u8 },
+    // The numpad for entering the rest of the board. The position it's asking about is how desyncs get caught here.
+    BoardNumpad { nonce: u32, position: u8, number: u8 },
+    Replay { nonce: u32, ticket: u8 },
+    Undo { nonce: u32 },
+    LastInput { nonce: u32 },
+    Reset { nonce: u32 },
+    // The total is carried along so the announcement doesn't have to read it back out of the (possibly translated) message.
+    AnnounceResults { total: u16 },
+    Restore,
+    FullReset { mode: GameMode },
+    RestartSimulation,
+    // Quizzes have their own nonces, which are checked against ACTIVE_QUIZZES instead of ACTIVE_GAMES.
+    QuizTile { nonce: u32, position: u8 },
+    QuizLine { nonce: u32 },
+    QuizNext,
+    // The button on the daily puzzle post. The day isn't in here, pressing it always plays whichever puzzle is current.
+    DailyPlay,
+}
+
+impl CustomId {
+    // The game.index() this was generated for, if it was generated for a specific step.
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Numpad { index, .. } | Tile { index, .. } | DisabledTile { index, .. } | Payout { index, .. } | Line { index, .. } | RevealBoard { index, .. } => Some(*index as usize),
+            _ => None,
+        }
+    }
+
+    // The nonce of the game this was generated for, if it was generated for a specific game.
+    pub fn nonce(&self) -> Option<u32> {
+        match self {
+            Numpad { nonce, .. } | Tile { nonce, .. } | DisabledTile { nonce, .. } | Payout { nonce, .. } | Line { nonce, .. }
+            | RevealBoard { nonce, .. } | BoardNumpad { nonce, .. } | Replay { nonce, .. }
+            | Undo { nonce } | LastInput { nonce } | Reset { nonce } => Some(*nonce),
+            _ => None,
+        }
+    }
+}
+
+// Underscores only ever separate fields, which is what makes parsing it back trivial.
+// The nonce is in hex so it's always 8 characters, which keeps us well under Discord's 100 character limit.
+impl Display for CustomId {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Numpad { nonce, index, number } => write!(fmt, "minicact_numpad_{:08x}_{:02}_{}", nonce, index, number),
+            Tile { nonce, index, position } => write!(fmt, "minicact_game_{:08x}_{:02}_{}", nonce, index, position),
+            DisabledTile { nonce, index, position } => write!(fmt, "minicact_X_{:08x}_{:02}_{}", nonce, index, position),
+            Payout { nonce, index } => write!(fmt, "minicact_payouts_{:08x}_{:02}", nonce, index),
+            Line { nonce, index } => write!(fmt, "minicact_line_{:08x}_{:02}", nonce, index),
+            RevealBoard { nonce, index } => write!(fmt, "minicact_board_{:08x}_{:02}", nonce, index),
+            BoardNumpad { nonce, position, number } => write!(fmt, "minicact_boardnumpad_{:08x}_{}_{}", nonce, position, number),
+            Replay { nonce, ticket } => write!(fmt, "minicact_replay_{:08x}_{}", nonce, ticket),
+            Undo { nonce } => write!(fmt, "minicact_undo_{:08x}", nonce),
+            LastInput { nonce } => write!(fmt, "minicact_lastinput_{:08x}", nonce),
+            Reset { nonce } => write!(fmt, "minicact_reset_{:08x}", nonce),
+            AnnounceResults { total } => write!(fmt, "minicact_announce_{}", total),
+            Restore => write!(fmt, "minicact_restore"),
+            FullReset { mode } => write!(fmt, "minicact_fullreset_{}", match mode {
+                GameMode::Play => "play",
+                GameMode::Simulate => "sim",
+                GameMode::Daily => "daily",
+            }),
+            RestartSimulation => write!(fmt, "minicact_restartsim"),
+            QuizTile { nonce, position } => write!(fmt, "minicact_quiztile_{:08x}_{}", nonce, position),
+            QuizLine { nonce } => write!(fmt, "minicact_quizline_{:08x}", nonce),
+            QuizNext => write!(fmt, "minicact_quiznext"),
+            DailyPlay => write!(fmt, "minicact_daily"),
+        }
+    }
+}
+
+// The errors are &'static str so they can go straight into a SerenityError::Other.
+impl FromStr for CustomId {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split('_');
+        if fields.next() != Some("minicact") {
+            return Err("custom_id does not belong to minicact");
+        }
+        let custom_id = match fields.next().ok_or("custom_id has no action")? {
+            "numpad" => Numpad { nonce: next_nonce(&mut fields)?, index: next_index(&mut fields)?, number: next_tile(&mut fields)? },
+            "game" => Tile { nonce: next_nonce(&mut fields)?, index: next_index(&mut fields)?, position: next_tile(&mut fields)? },
+            "X" => DisabledTile { nonce: next_nonce(&mut fields)?, index: next_index(&mut fields)?, position: next_tile(&mut fields)? },
+            "payouts" => Payout { nonce: next_nonce(&mut fields)?, index: next_index(&mut fields)? },
+            "line" => Line { nonce: next_nonce(&mut fields)?, index: next_index(&mut fields)? },
+            "board" => RevealBoard { nonce: next_nonce(&mut fields)?, index: next_index(&mut fields)? },
+            "boardnumpad" => BoardNumpad { nonce: next_nonce(&mut fields)?, position: next_tile(&mut fields)?, number: next_tile(&mut fields)? },
+            "replay" => Replay { nonce: next_nonce(&mut fields)?, ticket: next_ticket(&mut fields)? },
+            "undo" => Undo { nonce: next_nonce(&mut fields)? },
+            "lastinput" => LastInput { nonce: next_nonce(&mut fields)? },
+            "reset" => Reset { nonce: next_nonce(&mut fields)? },
+            "announce" => AnnounceResults { total: fields.next().and_then(|x| x.parse().ok()).ok_or("custom_id total failed to parse")? },
+            "restore" => Restore,
+            "fullreset" => match fields.next() {
+                Some("play") => FullReset { mode: GameMode::Play },
+                Some("sim") => FullReset { mode: GameMode::Simulate },
+                Some("daily") => FullReset { mode: GameMode::Daily },
+                _ => return Err("custom_id has an unknown full reset mode"),
+            },
+            "restartsim" => RestartSimulation,
+            "quiztile" => QuizTile { nonce: next_nonce(&mut fields)?, position: next_tile(&mut fields)? },
+            "quizline" => QuizLine { nonce: next_nonce(&mut fields)? },
+            "quiznext" => QuizNext,
+            "daily" => DailyPlay,
+            _ => return Err("custom_id has an unknown action"),
+        };
+        match fields.next() {
+            None => Ok(custom_id),
+            Some(_) => Err("custom_id has too many fields"),
+        }
+    }
+}
+
+fn next_nonce<'a>(fields: &mut impl Iterator<Item = &'a str>) -> Result<u32, &'static str> {
+    fields.next()
+        .filter(|x| x.len() == 8)
+        .and_then(|x| u32::from_str_radix(x, 16).ok())
+        .ok_or("custom_id nonce failed to parse")
+}
+
+fn next_index<'a>(fields: &mut impl Iterator<Item = &'a str>) -> Result<u8, &'static str> {
+    fields.next()
+        .and_then(|x| x.parse().ok())
+        .filter(|&x| x <= 12)
+        .ok_or("custom_id index failed to parse")
+}
+
+fn next_ticket<'a>(fields: &mut impl Iterator<Item = &'a str>) -> Result<u8, &'static str> {
+    fields.next()
+        .and_then(|x| x.parse().ok())
+        .filter(|&x| x < 3)
+        .ok_or("custom_id ticket failed to parse")
+}
+
+// Numbers and positions are both 0..9, so they get parsed the same way.
+fn next_tile<'a>(fields: &mut impl Iterator<Item = &'a str>) -> Result<u8, &'static str> {
+    fields.next()
+        .and_then(|x| x.parse().ok())
+        .filter(|&x| x < 9)
+        .ok_or("custom_id tile failed to parse")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    // One of every variant, with values near the edges of what they can hold.
+    const EVERY_VARIANT: [CustomId; 21] = [
+        Numpad { nonce: 0, index: 12, number: 8 },
+        Tile { nonce: u32::MAX, index: 0, position: 4 },
+        DisabledTile { nonce: 0x00ab_cdef, index: 7, position: 0 },
+        Payout { nonce: 1, index: 4 },
+        Line { nonce: 0xdead_beef, index: 12 },
+        RevealBoard { nonce: 42, index: 8 },
+        BoardNumpad { nonce: 7, position: 8, number: 0 },
+        Replay { nonce: 9, ticket: 2 },
+        Undo { nonce: 0x1000_0000 },
+        LastInput { nonce: 3 },
+        Reset { nonce: 0xffff },
+        AnnounceResults { total: 10000 },
+        Restore,
+        FullReset { mode: GameMode::Play },
+        FullReset { mode: GameMode::Simulate },
+        FullReset { mode: GameMode::Daily },
+        RestartSimulation,
+        QuizTile { nonce: 5, position: 3 },
+        QuizLine { nonce: 6 },
+        QuizNext,
+        DailyPlay,
+    ];
+
+    // The match has no catch-all, so a new variant doesn't compile until it's numbered here, and then this fails until it's in EVERY_VARIANT.
+    #[test]
+    fn every_variant_is_listed() {
+        let kind = |custom_id: &CustomId| match custom_id {
+            Numpad { .. } => 0, Tile { .. } => 1, DisabledTile { .. } => 2, Payout { .. } => 3, Line { .. } => 4, RevealBoard { .. } => 5,
+            BoardNumpad { .. } => 6, Replay { .. } => 7, Undo { .. } => 8, LastInput { .. } => 9, Reset { .. } => 10, AnnounceResults { .. } => 11,
+            Restore => 12, FullReset { .. } => 13, RestartSimulation => 14, QuizTile { .. } => 15, QuizLine { .. } => 16, QuizNext => 17, DailyPlay => 18,
+        };
+        let listed = EVERY_VARIANT.iter().map(kind).collect::<HashSet<usize>>();
+        assert_eq!(listed, (0..19).collect::<HashSet<usize>>());
+    }
+
+    #[test]
+    fn every_variant_round_trips() {
+        for custom_id in EVERY_VARIANT {
+            let s = custom_id.to_string();
+            assert!(s.len() <= 100, "{} is longer than Discord allows", s);
+            assert_eq!(s.parse::<CustomId>(), Ok(custom_id));
+            assert_eq!(s.parse::<CustomId>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_prefixes() {
+        for s in ["", "minicact", "minicac_undo_00000001", "other_undo_00000001", "minicactundo_00000001", "Minicact_undo_00000001", "_minicact_undo_00000001"] {
+            assert!(s.parse::<CustomId>().is_err(), "{} should be refused", s);
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_nonce_width() {
+        for s in ["minicact_undo_1", "minicact_undo_0000001", "minicact_undo_000000001", "minicact_game_0000001_03_4", "minicact_undo_", "minicact_undo_0000000g"] {
+            assert!(s.parse::<CustomId>().is_err(), "{} should be refused", s);
+        }
+    }
+
+    #[test]
+    fn rejects_extra_and_missing_fields() {
+        for custom_id in EVERY_VARIANT {
+            let s = custom_id.to_string();
+            assert!(format!("{}_0", s).parse::<CustomId>().is_err(), "{}_0 should be refused", s);
+            // Everything but the variants without any fields has something to leave out.
+            if let Some((shorter, _)) = s.rsplit_once('_').filter(|(shorter, _)| *shorter != "minicact") {
+                assert!(shorter.parse::<CustomId>().is_err(), "{} should be refused", shorter);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_fields() {
+        for s in ["minicact_numpad_00000001_13_4", "minicact_numpad_00000001_03_9", "minicact_replay_00000001_3", "minicact_fullreset_other", "minicact_announce_lots", "minicact_nothing"] {
+            assert!(s.parse::<CustomId>().is_err(), "{} should be refused", s);
+        }
+    }
+}
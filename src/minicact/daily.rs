@@ -0,0 +1,423 @@
+use crate::settings::settings;
+use crate::shutdown::is_draining;
+use crate::guild_config::{all_guild_configs, guild_config};
+use crate::locale::{format_number, tr, trf, Locale};
+use crate::generate_components::make_button;
+use super::benchmark::play_hidden_ticket;
+use super::custom_id::CustomId;
+use super::game::*;
+use super::game::board::Board;
+use super::game::computations::*;
+use super::game::payout::PAYOUT_VALUES;
+use super::generate_components::{line_name_inline, make_board_grid};
+use super::recommendations::parse_data;
+use super::strategies::Optimal;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serenity::http::Http;
+use serenity::model::channel::Channel;
+use serenity::model::id::{GuildId, UserId};
+use serenity::model::prelude::component::ButtonStyle;
+use serenity::prelude::*;
+
+use chrono::Local;
+
+use lazy_static::lazy_static;
+
+// Every daily puzzle that's been played, by guild and day.
+// Only today's and yesterday's are kept around, since those are the only ones that still get summarized.
+// Saved to daily_puzzles_file on every change, so a restart doesn't give everyone another go.
+lazy_static! {
+    static ref DAILY_PUZZLES: Mutex<HashMap<(GuildId, i64), DailyPuzzle>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Default)]
+struct DailyPuzzle {
+    // Everyone who has started it, finished or not. Each member only gets one go.
+    started: HashSet<UserId>,
+    results: Vec<DailyResult>,
+}
+
+struct DailyResult {
+    user: UserId,
+    total: u16,
+    lines: [usize; 3],
+    // How many of the 12 choices (3 tiles and a line on each ticket) were as good as the solver's.
+    best_choices: u8,
+}
+
+// Discord refuses messages longer than this, so the summary leaves out whoever doesn't fit.
+const MESSAGE_LIMIT: usize = 2000;
+
+// Days are counted from the unix epoch, but they start at `hour` instead of midnight.
+fn puzzle_day(timestamp: i64, hour: i64) -> i64 {
+    (timestamp - hour * 3600).div_euclid(86400)
+}
+
+// The unix timestamp a day's puzzle goes up.
+fn puzzle_start(day: i64, hour: i64) -> i64 {
+    day * 86400 + hour * 3600
+}
+
+// Everyone in the guild gets the same tickets all day.
+// The exact way the guild and day get mixed doesn't matter, StdRng scrambles it plenty either way.
+fn daily_seed(guild: GuildId, day: i64) -> u64 {
+    (guild.0 ^ (day as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)) & MAX_SEED
+}
+
+// Makes a new game of whatever kind the user asked for. The error is written for the user, and says why they can't play.
+// This lives here because the daily puzzle is the only kind that can be refused.
+// `seed` is only used by simulations, the daily puzzle has its own.
+pub async fn new_game(mode: GameMode, guild_id: Option<GuildId>, user: UserId, seed: Option<u64>, locale: Locale) -> Result<Game, String> {
+    // Anything started now would just get cut off partway through.
+    if is_draining() {
+        return Err(tr(locale, "shutdown.no_new_games").to_string())
+    }
+    match mode {
+        GameMode::Play => Ok(Game::new()),
+        GameMode::Simulate => Ok(Game::new_simulated(seed)),
+        GameMode::Daily => start_daily_game(guild_id, user, locale).await,
+    }
+}
+
+async fn start_daily_game(guild_id: Option<GuildId>, user: UserId, locale: Locale) -> Result<Game, String> {
+    let guild = guild_id.ok_or(tr(locale, "daily.server_only"))?;
+    let hour = settings().daily_hour;
+    let day = puzzle_day(Local::now().timestamp(), hour);
+    let mut daily_puzzles = DAILY_PUZZLES.lock().await;
+    let puzzle = daily_puzzles.entry((guild, day)).or_default();
+    if let Some(result) = puzzle.results.iter().find(|result| result.user == user) {
+        return Err(trf(locale, "daily.already_played", &[&format_number(locale, result.total as f64, 0), &format!("<t:{}:R>", puzzle_start(day + 1, hour))]));
+    }
+    // Otherwise you could peek at a ticket, throw the game away, and start over knowing where the good tiles are.
+    // This only counts once the game actually made it to them though, see mark_daily_started.
+    if puzzle.started.contains(&user) {
+        return Err(trf(locale, "daily.already_started", &[&format!("<t:{}:R>", puzzle_start(day + 1, hour))]));
+    }
+    Ok(Game::new_daily(daily_seed(guild, day), guild, day))
+}
+
+// Called once a daily game has been shown to its player, and for daily games brought back after a restart, so they can't start over.
+// Nothing happens for any other game, so it can just be called for every new game.
+pub async fn mark_daily_started(game: &Game, user: UserId) {
+    if let Some((guild, day)) = game.daily() {
+        let mut daily_puzzles = DAILY_PUZZLES.lock().await;
+        if daily_puzzles.entry((guild, day)).or_default().started.insert(user) {
+            save_daily_puzzles(&daily_puzzles);
+        }
+    }
+}
+
+// Called once a daily game is finished, so it shows up in the summary. Does nothing for any other game.
+pub async fn record_daily_result(game: &Game, user: UserId) {
+    if let Some((guild, day)) = game.daily() {
+        let result = DailyResult {
+            user,
+            total: game.total_payout(),
+            lines: [0, 1, 2].map(|ticket| game.chosen_line(ticket).unwrap_or_default()),
+            best_choices: count_best_choices(game),
+        };
+        let mut daily_puzzles = DAILY_PUZZLES.lock().await;
+        let puzzle = daily_puzzles.entry((guild, day)).or_default();
+        if !puzzle.results.iter().any(|result| result.user == user) {
+            puzzle.results.push(result);
+            save_daily_puzzles(&daily_puzzles);
+        }
+    }
+}
+
+// One line per member and puzzle: the guild, the day and the member, then the result if they finished.
+// A result is the total, the three lines and how many choices were the best, all separated by spaces.
+fn to_saved(daily_puzzles: &HashMap<(GuildId, i64), DailyPuzzle>) -> String {
+    let mut output = String::new();
+    for ((guild, day), puzzle) in daily_puzzles.iter() {
+        for user in puzzle.started.iter().filter(|user| !puzzle.results.iter().any(|result| result.user == **user)) {
+            output.push_str(&format!("{} {} {}\n", guild, day, user));
+        }
+        for result in puzzle.results.iter() {
+            output.push_str(&format!(
+                "{} {} {} {} {} {} {} {}\n",
+                guild, day, result.user, result.total, result.lines[0], result.lines[1], result.lines[2], result.best_choices
+            ));
+        }
+    }
+    output
+}
+
+// The guild, the day and the member a line is about, and their result if it has one.
+fn from_saved(line: &str) -> Result<(GuildId, i64, UserId, Option<DailyResult>), String> {
+    let values = line.split_whitespace().collect::<Vec<&str>>();
+    if values.len() != 3 && values.len() != 8 {
+        return Err(format!("expected 3 or 8 values, not {}", values.len()))
+    }
+    let number = |i: usize| values[i].parse::<u64>().map_err(|_| format!("value {} isn't a number", i + 1));
+    let guild = GuildId(number(0)?);
+    let day = values[1].parse::<i64>().map_err(|_| "value 2 isn't a day".to_string())?;
+    let user = UserId(number(2)?);
+    if values.len() == 3 {
+        return Ok((guild, day, user, None))
+    }
+    let total = u16::try_from(number(3)?).map_err(|_| "the total is too big".to_string())?;
+    let lines = [number(4)?, number(5)?, number(6)?].map(|line| line as usize);
+    if lines.iter().any(|&line| line >= 8) {
+        return Err("a line is out of range".to_string())
+    }
+    let best_choices = number(7)?.min(12) as u8;
+    Ok((guild, day, user, Some(DailyResult { user, total, lines, best_choices })))
+}
+
+// Called with the lock held, so two changes at once can't save out of order.
+fn save_daily_puzzles(daily_puzzles: &HashMap<(GuildId, i64), DailyPuzzle>) {
+    let path = settings().daily_puzzles_file.clone();
+    // Same as the preferences file, so a crash halfway through can't leave a cut off file behind.
+    let temp_path = format!("{}.tmp", path);
+    if let Err(why) = std::fs::write(&temp_path, to_saved(daily_puzzles)).and_then(|_| std::fs::rename(&temp_path, &path)) {
+        error!("Failed to save daily puzzles to {}: {}", path, why);
+    }
+}
+
+// Called once at startup, before the games in progress are loaded. Puzzles older than yesterday are left out.
+pub async fn load_daily_puzzles() {
+    let path = settings().daily_puzzles_file.clone();
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(why) => {
+            info!("No daily puzzles loaded from {}: {}", path, why);
+            return
+        }
+    };
+    let today = puzzle_day(Local::now().timestamp(), settings().daily_hour);
+    let mut daily_puzzles = DAILY_PUZZLES.lock().await;
+    // Lines go by their number in the logs, since the user's ID is right there in them.
+    for (number, line) in contents.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        match from_saved(line) {
+            Ok((guild, day, user, result)) if day >= today - 1 => {
+                let puzzle = daily_puzzles.entry((guild, day)).or_default();
+                puzzle.started.insert(user);
+                if let Some(result) = result.filter(|_| !puzzle.results.iter().any(|result| result.user == user)) {
+                    puzzle.results.push(result);
+                }
+            },
+            Ok(_) => (),
+            Err(why) => warn!("Skipping daily puzzle line {}: {}", number + 1, why),
+        }
+    }
+    info!("Loaded {} daily puzzles.", daily_puzzles.len());
+}
+
+// Same comparison as make_replay, but just counting. Ties with the solver count as the best choice too.
+fn count_best_choices(game: &Game) -> u8 {
+    let mut output = 0;
+    for ticket in 0..3 {
+        let (positions, numbers) = game.ticket_reveals(ticket);
+        let mut board = Board {
+            state: [255, 255, 255, 255, 255, 255, 255, 255, 255],
+            unused_nums: (0..9).collect(),
+        };
+        for k in 0..positions.len().min(4) {
+            if k > 0 {
+                let dists = compute_uncover_dists(&mut board);
+                let (_, best_data) = compute_best_uncover(&mut board);
+                let chosen = dists[positions[k] as usize].map(|data| parse_data(data).0).unwrap_or_default();
+                if parse_data(best_data).0 - chosen < 1e-9 {
+                    output += 1;
+                }
+            }
+            board.state[positions[k] as usize] = numbers[k];
+            board.unused_nums.remove(&numbers[k]);
+        }
+        if let Some(line) = game.chosen_line(ticket) {
+            let (_, best_data) = compute_best_line(&mut board);
+            let line_data = compute_best_line_rec(&mut board);
+            if parse_data(best_data).0 - parse_data(line_data[line]).0 < 1e-9 {
+                output += 1;
+            }
+        }
+    }
+    output
+}
+
+// Everyone's results for one guild's puzzle, best first. None if nobody finished it.
+// This goes to the whole channel rather than one person, so it's in the server's language.
+async fn make_summary(guild: GuildId, day: i64, hour: i64, locale: Locale) -> Option<String> {
+    let daily_puzzles = DAILY_PUZZLES.lock().await;
+    let puzzle = daily_puzzles.get(&(guild, day)).filter(|puzzle| !puzzle.results.is_empty())?;
+    let tickets = seeded_tickets(daily_seed(guild, day));
+    let solver = tickets.map(|ticket| PAYOUT_VALUES[play_hidden_ticket(&mut Optimal, &ticket)]);
+    let mut output = format!("{}\n", trf(locale, "daily.summary_title", &[&format!("<t:{}:D>", puzzle_start(day, hour))]));
+    for (t, ticket) in tickets.iter().enumerate() {
+        output.push_str(format!("{}\n{}", trf(locale, "daily.summary_ticket", &[&(t + 1)]), make_board_grid(&ticket.board(), None, false, locale)).as_str());
+    }
+    let mgp = |value: u16| format_number(locale, value as f64, 0);
+    output.push_str(format!("{}\n\n", trf(locale, "daily.summary_solver", &[&mgp(solver.iter().sum()), &mgp(solver[0]), &mgp(solver[1]), &mgp(solver[2])])).as_str());
+    let mut results: Vec<&DailyResult> = puzzle.results.iter().collect();
+    results.sort_by(|a, b| b.total.cmp(&a.total).then(b.best_choices.cmp(&a.best_choices)));
+    let lines = results.iter().enumerate()
+        .map(|(rank, result)| format!("{}\n", trf(locale, "daily.summary_line", &[
+            &(rank + 1),
+            &result.user.mention(),
+            &mgp(result.total),
+            &line_name_inline(locale, result.lines[0]),
+            &line_name_inline(locale, result.lines[1]),
+            &line_name_inline(locale, result.lines[2]),
+            &result.best_choices,
+        ])))
+        .collect::<Vec<String>>();
+    Some(add_fitting_lines(output, &lines, |left_out| trf(locale, "daily.summary_more", &[&left_out])))
+}
+
+// Adds as many of `lines` as fit under MESSAGE_LIMIT, in order, and then `more` with how many didn't.
+// Room for `more` is kept the whole way, so it always fits too.
+fn add_fitting_lines(mut output: String, lines: &[String], more: impl Fn(usize) -> String) -> String {
+    let more_room = more(lines.len()).chars().count();
+    let mut length = output.chars().count();
+    let mut added = 0;
+    for line in lines {
+        let needed = line.chars().count() + if added + 1 < lines.len() { more_room } else { 0 };
+        if length + needed > MESSAGE_LIMIT {
+            break
+        }
+        output.push_str(line);
+        length += line.chars().count();
+        added += 1;
+    }
+    if added < lines.len() {
+        output.push_str(&more(lines.len() - added));
+    }
+    output
+}
+
+// Waits for each day's reset, then posts yesterday's summary and the new puzzle to every channel in `daily_channels`,
+// and to every server's announcement channel.
+pub fn start_daily_puzzles(http: Arc<Http>) {
+    tokio::spawn(async move {
+        loop {
+            let hour = settings().daily_hour;
+            let now = Local::now().timestamp();
+            let day = puzzle_day(now, hour);
+            tokio::time::sleep(Duration::from_secs((puzzle_start(day + 1, hour) - now).max(1) as u64)).await;
+            post_daily_puzzles(&http, day + 1, hour).await;
+        }
+    });
+}
+
+async fn post_daily_puzzles(http: &Arc<Http>, day: i64, hour: i64) {
+    let mut channels = settings().daily_channels.clone();
+    // Servers with an announcement channel get the puzzle there too.
+    for (_, config) in all_guild_configs().await {
+        if let Some(channel) = config.announcement_channel.filter(|channel| !channels.contains(channel)) {
+            channels.push(channel);
+        }
+    }
+    for channel in channels {
+        let guild = match channel.to_channel(http.as_ref()).await {
+            Ok(Channel::Guild(guild_channel)) => guild_channel.guild_id,
+            Ok(_) => {
+                warn!("Daily puzzle channel {} is not in a server, skipping it.", channel);
+                continue;
+            },
+            Err(why) => {
+                error!("Failed to get daily puzzle channel {}: {}", channel, why);
+                continue;
+            }
+        };
+        let config = guild_config(guild).await;
+        if !config.allows("minicact_daily") {
+            continue;
+        }
+        // Everyone in the channel sees these, so they go in the server's language.
+        let locale = config.locale.unwrap_or_default();
+        let summary = if config.leaderboard { make_summary(guild, day - 1, hour, locale).await } else { None };
+        if let Some(summary) = summary {
+            // The summary mentions everyone who played, but there's no need to ping them all.
+            if let Err(why) = channel.send_message(http, |message| {
+                message.content(summary).allowed_mentions(|mentions| mentions.empty_parse())
+            }).await {
+                error!("Failed to post daily summary to channel {}: {}", channel, why);
+            }
+        }
+        if let Err(why) = channel.send_message(http, |message| {
+            message.content(trf(locale, "daily.post", &[&format!("<t:{}:R>", puzzle_start(day + 1, hour))]))
+                .components(|components| {
+                    components.create_action_row(|action_row| {
+                        make_button(action_row, CustomId::DailyPlay, ButtonStyle::Success, Some("🎟"), Some(format!(" {}", tr(locale, "button.play_daily")).as_str()), false)
+                    })
+                })
+        }).await {
+            error!("Failed to post daily puzzle to channel {}: {}", channel, why);
+        }
+    }
+    let mut daily_puzzles = DAILY_PUZZLES.lock().await;
+    daily_puzzles.retain(|(_, puzzle_day), _| *puzzle_day >= day - 1);
+    save_daily_puzzles(&daily_puzzles);
+    drop(daily_puzzles);
+    info!("Posted daily puzzle for day {}.", day);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::DEFAULT_DAILY_HOUR;
+
+    // The longest a summary line can get: the longest mention, the most MGP, and the longest line names.
+    fn worst_case_result(user: u64) -> DailyResult {
+        DailyResult { user: UserId(u64::MAX - user), total: 30000, lines: [3, 3, 3], best_choices: 12 }
+    }
+
+    #[tokio::test]
+    async fn worst_case_summary_fits() {
+        let (guild, day) = (GuildId(1), 100);
+        for players in [1, 8, 15, 16, 100] {
+            let results = (0..players).map(worst_case_result).collect();
+            DAILY_PUZZLES.lock().await.insert((guild, day), DailyPuzzle { started: HashSet::new(), results });
+            for locale in Locale::ALL {
+                let summary = make_summary(guild, day, DEFAULT_DAILY_HOUR, locale).await.unwrap();
+                assert!(summary.chars().count() <= MESSAGE_LIMIT, "{} players made a {} character summary in {:?}", players, summary.chars().count(), locale);
+                assert!(summary.contains(&format!("<@{}>", u64::MAX)));
+            }
+        }
+    }
+
+    #[test]
+    fn fitting_lines_leave_room_for_more() {
+        let more = |left_out: usize| format!("...and {} more.", left_out);
+        let lines = (0..50).map(|i| format!("{:>99}\n", i)).collect::<Vec<String>>();
+        let output = add_fitting_lines("x".repeat(500), &lines, more);
+        assert!(output.chars().count() <= MESSAGE_LIMIT);
+        assert!(output.ends_with("...and 36 more."), "{}", output);
+        // Everything fits, so nothing gets left out.
+        assert_eq!(add_fitting_lines(String::new(), &lines[..5], more), lines[..5].concat());
+        // The last line doesn't need room for `more` after it.
+        let exact = add_fitting_lines("x".repeat(MESSAGE_LIMIT - 100), &lines[..1], more);
+        assert_eq!(exact.chars().count(), MESSAGE_LIMIT);
+    }
+
+    #[test]
+    fn saved_puzzles_round_trip() {
+        let mut daily_puzzles = HashMap::new();
+        let results = vec![worst_case_result(0), DailyResult { user: UserId(5), total: 0, lines: [0, 7, 2], best_choices: 0 }];
+        let started = HashSet::from([UserId(u64::MAX), UserId(5), UserId(6)]);
+        daily_puzzles.insert((GuildId(1), -3), DailyPuzzle { started, results });
+        daily_puzzles.insert((GuildId(2), 19000), DailyPuzzle { started: HashSet::from([UserId(7)]), results: vec![] });
+        let saved = to_saved(&daily_puzzles);
+        assert_eq!(saved.lines().count(), 4);
+        for line in saved.lines() {
+            let (guild, day, user, result) = from_saved(line).unwrap();
+            let puzzle = &daily_puzzles[&(guild, day)];
+            assert!(puzzle.started.contains(&user));
+            match (result, puzzle.results.iter().find(|result| result.user == user)) {
+                (Some(loaded), Some(saved)) => {
+                    assert_eq!((loaded.total, loaded.lines, loaded.best_choices), (saved.total, saved.lines, saved.best_choices));
+                },
+                (None, None) => (),
+                _ => panic!("{} came back with the wrong result", line),
+            }
+        }
+        for line in ["", "1 2", "1 2 3 4", "1 x 3", "1 2 3 30001 0 0 0 12 13", "1 2 3 70000 0 0 0 12", "1 2 3 100 0 8 0 12"] {
+            assert!(from_saved(line).is_err(), "{} was accepted", line);
+        }
+    }
+}
@@ -1,62 +1,224 @@
 pub mod board;
 pub mod computations;
+pub mod notation;
 pub mod payout;
+pub mod saved;
 
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU32, Ordering};
 
 use Action::*;
 
 use board::*;
+use computations::compute_best_line;
 use payout::Payout::*;
 use payout::*;
 
-use serenity::model::id::UserId;
+use crate::locale::{tr, trf, Locale};
+
+use serenity::model::id::{GuildId, UserId};
 use serenity::prelude::*;
 
+use chrono::Local;
+
 use lazy_static::lazy_static;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 use smallset::SmallSet;
 
 lazy_static! {
     pub static ref ACTIVE_GAMES: Mutex<HashMap<UserId, Game>> = Mutex::new(HashMap::new());
 }
 
+// Counting up guarantees no two games share a nonce until it wraps around, which is ~4 billion games from now.
+// Starting somewhere random means messages from before a restart are very unlikely to line up with new games either.
+lazy_static! {
+    static ref NEXT_NONCE: AtomicU32 = AtomicU32::new(rand::random());
+}
+
+// Anything else that needs to tell its messages apart (like quizzes) draws from the same counter.
+pub fn new_nonce() -> u32 {
+    NEXT_NONCE.fetch_add(1, Ordering::Relaxed)
+}
+
+// Discord can't send integers any bigger than this, so seeds are kept under it to make sure they can be typed back in.
+pub const MAX_SEED: u64 = (1 << 53) - 1;
+
+// Every random thing about a simulated game comes from its seed, so the same seed always plays out the same way.
+// StdRng isn't guaranteed to stay the same between versions of rand, so seeds are only good for as long as the bot isn't updated.
+pub fn seeded_tickets(seed: u64) -> [HiddenTicket; 3] {
+    let mut rng = StdRng::seed_from_u64(seed);
+    [HiddenTicket::random(&mut rng), HiddenTicket::random(&mut rng), HiddenTicket::random(&mut rng)]
+}
+
+// One ticket of a simulated game. The player only ever sees the parts of `board` they scratch.
+#[derive(Clone, Copy)]
+pub struct HiddenTicket {
+    board: [u8; 9],
+    // The tile the ticket comes with already revealed.
+    first: u8,
+}
+
+impl HiddenTicket {
+    pub fn random<R: Rng>(rng: &mut R) -> HiddenTicket {
+        let mut board = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+        board.shuffle(rng);
+        HiddenTicket { board, first: rng.gen_range(0..9) }
+    }
+
+    pub fn board(&self) -> [u8; 9] {
+        self.board
+    }
+
+    pub fn first(&self) -> u8 {
+        self.first
+    }
+}
+
 // This keeps track of the game state for a user. Each scratch ticket takes up 1/3 of each array, for 3 tickets total.
+#[derive(Clone)]
 pub struct Game {
+    // Unique for every game, so components from a different game can be told apart even if they are on the same step.
+    nonce: u32,
+    // Unix timestamp, so the user can be told which message is their current game.
+    started: i64,
     index: u8,
     position_history: [u8; 12],
     number_history: [u8; 12],
     payout_history: [Payout; 3],
-    simulated: bool,
+    // The 5 tiles the player didn't scratch, by position, if they chose to enter them after their payout. 255 means not entered.
+    board_history: [[u8; 9]; 3],
+    // Which line the player chose on each ticket. This is only known once the full board is entered, 255 otherwise.
+    line_history: [u8; 3],
+    // Whether the player is currently entering the rest of the last ticket they finished.
+    revealing: bool,
+    // Only simulated games have these. The game reveals the numbers itself instead of asking the player.
+    hidden: Option<[HiddenTicket; 3]>,
+    // What `hidden` was generated from. Shown to the player so they can play the same tickets again, or put it in a bug report.
+    seed: Option<u64>,
+    // The guild and day of the daily puzzle this is, if it is one. Every choice in a daily puzzle is final.
+    daily: Option<(GuildId, i64)>,
+}
+
+// The different ways a game can be started. Mostly so a game can be replaced with a new one of the same kind.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GameMode {
+    Play,
+    Simulate,
+    Daily,
+}
+
+impl GameMode {
+    // For logs and metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Play => "play",
+            GameMode::Simulate => "simulate",
+            GameMode::Daily => "daily",
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Action {
     Start,
     ChoosePosition(u8),
     RevealNumber(u8),
     EnterPayout(Payout),
+    // For next_action(), this is the position that needs a number. For last_action(), it's the number that was entered.
+    RevealBoard(u8),
     Done,
 }
 
+// Everything that can go wrong when trying to change a Game.
+// These used to just silently corrupt the game, which is how you ended up with two 5s on one ticket.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GameError {
+    DuplicateNumber(u8),
+    DuplicatePosition(u8),
+    InvalidNumber(u8),
+    InvalidPosition(u8),
+    InvalidPayout,
+    PayoutBeforeReveals,
+    GameOver,
+    WrongAction(Action),
+    BoardAlreadyRevealed,
+    InvalidLine(u8),
+    Simulated,
+    NotSimulated,
+    PayoutMismatch(Payout),
+    BrokenInvariant(&'static str),
+}
+
+// These get shown to the user, so they are written for the user.
+impl GameError {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            DuplicateNumber(n) => trf(locale, "error.duplicate_number", &[&(n + 1)]),
+            DuplicatePosition(_) => tr(locale, "error.duplicate_position").to_string(),
+            InvalidNumber(n) => trf(locale, "error.invalid_number", &[&(*n as u16 + 1)]),
+            InvalidPosition(p) => trf(locale, "error.invalid_position", &[p]),
+            InvalidPayout => tr(locale, "error.invalid_payout").to_string(),
+            PayoutBeforeReveals => tr(locale, "error.payout_before_reveals").to_string(),
+            GameOver => tr(locale, "error.game_over").to_string(),
+            WrongAction(ChoosePosition(_)) => tr(locale, "error.expected_position").to_string(),
+            WrongAction(RevealNumber(_)) => tr(locale, "error.expected_number").to_string(),
+            WrongAction(EnterPayout(_)) => tr(locale, "error.expected_payout").to_string(),
+            WrongAction(RevealBoard(_)) => tr(locale, "error.expected_board").to_string(),
+            WrongAction(action) => trf(locale, "error.unexpected", &[&format!("{:?}", action)]),
+            InvalidLine(l) => trf(locale, "error.invalid_line", &[l]),
+            Simulated => tr(locale, "error.simulated").to_string(),
+            NotSimulated => tr(locale, "error.not_simulated").to_string(),
+            BoardAlreadyRevealed => tr(locale, "error.board_already_revealed").to_string(),
+            PayoutMismatch(p) => trf(locale, "error.payout_mismatch", &[&PAYOUT_VALUES[*p as usize]]),
+            BrokenInvariant(s) => trf(locale, "error.broken_invariant", &[s]),
+        }
+    }
+}
+
+impl Display for GameError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(fmt, "{}", self.message(Locale::English))
+    }
+}
+
+use GameError::*;
+
 impl Game {
     pub fn new() -> Game {
         Game {
+            nonce: new_nonce(),
+            started: Local::now().timestamp(),
             index: 0,
             position_history: [255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255],
             number_history: [255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255],
             payout_history: [NoPayout, NoPayout, NoPayout],
-            simulated: false,
+            board_history: [[255; 9]; 3],
+            line_history: [255, 255, 255],
+            revealing: false,
+            hidden: None,
+            seed: None,
+            daily: None,
         }
     }
 
-    pub fn new_simulated() -> Game {
-        Game {
-            index: 0,
-            position_history: [255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255],
-            number_history: [255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255],
-            payout_history: [NoPayout, NoPayout, NoPayout],
-            simulated: true,
-        }
+    // Picks a random seed if there isn't one.
+    pub fn new_simulated(seed: Option<u64>) -> Game {
+        let seed = seed.unwrap_or_else(|| rand::thread_rng().gen_range(0..=MAX_SEED));
+        let mut game = Game::new();
+        game.hidden = Some(seeded_tickets(seed));
+        game.seed = Some(seed);
+        game.reveal_first_tile();
+        game
+    }
+
+    // Everyone in the guild gets the same seed for the day.
+    pub fn new_daily(seed: u64, guild: GuildId, day: i64) -> Game {
+        let mut game = Game::new_simulated(Some(seed));
+        game.daily = Some((guild, day));
+        game
     }
 
     // got tired of writing self.index as usize
@@ -64,17 +226,43 @@ impl Game {
         self.index.into()
     }
 
+    pub fn nonce(&self) -> u32 {
+        self.nonce
+    }
+
+    pub fn started(&self) -> i64 {
+        self.started
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn daily(&self) -> Option<(GuildId, i64)> {
+        self.daily
+    }
+
+    pub fn mode(&self) -> GameMode {
+        match (self.is_simulated(), self.daily.is_some()) {
+            (_, true) => GameMode::Daily,
+            (true, false) => GameMode::Simulate,
+            (false, false) => GameMode::Play,
+        }
+    }
+
     pub fn is_simulated(&self) -> bool {
-        self.simulated
+        self.hidden.is_some()
     }
 
     // Which action the game is expecting next.
     // Tells the bot which buttons to generate.
     pub fn next_action(&self) -> Action {
         let i = self.index();
-        if i == 12 && self.payout_history[2] != NoPayout {
+        if let (true, Some(position)) = (self.revealing, self.next_board_position()) {
+            RevealBoard(position)
+        } else if i == 12 && self.payout_history[2] != NoPayout {
             Done
-        } else if i > 0 && i % 4 == 0 && self.payout_history[i / 4 - 1] == NoPayout {
+        } else if i > 0 && i.is_multiple_of(4) && self.payout_history[i / 4 - 1] == NoPayout {
             EnterPayout(NoPayout)
         } else if self.position_history[i] != 255 {
             RevealNumber(255)
@@ -86,11 +274,10 @@ impl Game {
     // Which action was previously taken.
     pub fn last_action(&self) -> Action {
         let i = self.index();
-        if i > 0
-            && i % 4 == 0
-            && self.payout_history[i / 4 - 1] != NoPayout
-            && (i == 12 || self.position_history[i] == 255)
-        {
+        if let Some(number) = self.finished_ticket().and_then(|t| self.board_history[t].iter().rev().find(|&&x| x != 255)) {
+            // The board is always entered in order of position, so the last number entered is the one furthest along.
+            RevealBoard(*number)
+        } else if self.finished_ticket().is_some() {
             EnterPayout(self.payout_history[i / 4 - 1])
         } else if i < 12 && self.position_history[i] != 255 {
             ChoosePosition(self.position_history[i])
@@ -101,27 +288,252 @@ impl Game {
         }
     }
 
-    // note that these do not actually check that the game is in the correct state before setting.
-    // Fortunately, the worst case is just overwriting a previous action.
-    // edit: the worst case is actually having multiple of the same number, which can cause panics.
+    // These all check that the game is actually expecting the action before doing it.
+    // If they return an error, the game is guaranteed to be unchanged.
 
-    pub fn set_position(&mut self, position: u8) {
+    pub fn set_position(&mut self, position: u8) -> Result<(), GameError> {
+        if position >= 9 {
+            return Err(InvalidPosition(position));
+        }
+        match self.next_action() {
+            ChoosePosition(_) => (),
+            Done => return Err(GameOver),
+            action => return Err(WrongAction(action)),
+        }
+        if self.used_positions().contains(&position) {
+            return Err(DuplicatePosition(position));
+        }
         self.position_history[self.index()] = position;
+        // In a simulation, scratching the tile is what reveals the number.
+        if let Some(hidden) = self.hidden {
+            self.number_history[self.index()] = hidden[self.index() / 4].board[position as usize];
+            self.index += 1;
+        }
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+        Ok(())
     }
 
-    pub fn set_number(&mut self, number: u8) {
+    pub fn set_number(&mut self, number: u8) -> Result<(), GameError> {
+        if self.is_simulated() {
+            return Err(Simulated);
+        }
+        if number >= 9 {
+            return Err(InvalidNumber(number));
+        }
+        match self.next_action() {
+            RevealNumber(_) => (),
+            Done => return Err(GameOver),
+            action => return Err(WrongAction(action)),
+        }
+        if self.used_numbers().contains(&number) {
+            return Err(DuplicateNumber(number));
+        }
         self.number_history[self.index()] = number;
         self.index += 1;
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+        Ok(())
     }
 
-    pub fn set_payout(&mut self, payout: Payout) {
+    pub fn set_payout(&mut self, payout: Payout) -> Result<(), GameError> {
+        if self.is_simulated() {
+            return Err(Simulated);
+        }
+        if payout == NoPayout {
+            return Err(InvalidPayout);
+        }
+        match self.next_action() {
+            EnterPayout(_) => (),
+            Done => return Err(GameOver),
+            _ => return Err(PayoutBeforeReveals),
+        }
         self.payout_history[self.index() / 4 - 1] = payout;
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+        Ok(())
+    }
+
+    // Simulated games pick a line instead of entering a payout, since the game already knows what every line pays.
+    // This also shows the player the rest of the board, and reveals the first tile of the next ticket.
+    pub fn choose_line(&mut self, line: u8) -> Result<(), GameError> {
+        let hidden = self.hidden.ok_or(NotSimulated)?;
+        if line >= 8 {
+            return Err(InvalidLine(line));
+        }
+        match self.next_action() {
+            EnterPayout(_) => (),
+            Done => return Err(GameOver),
+            _ => return Err(PayoutBeforeReveals),
+        }
+        let t = self.index() / 4 - 1;
+        let board = hidden[t].board;
+        self.payout_history[t] = line_payout(&board, line as usize);
+        self.line_history[t] = line;
+        self.board_history[t] = board;
+        for k in 4 * t..4 * t + 4 {
+            self.board_history[t][self.position_history[k] as usize] = 255;
+        }
+        self.reveal_first_tile();
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+        Ok(())
+    }
+
+    // Simulated tickets come with one tile already revealed, just like the real thing.
+    fn reveal_first_tile(&mut self) {
+        let i = self.index();
+        if let (Some(hidden), true) = (self.hidden, i < 12 && i.is_multiple_of(4)) {
+            let first = hidden[i / 4].first;
+            self.position_history[i] = first;
+            self.number_history[i] = hidden[i / 4].board[first as usize];
+            self.index += 1;
+        }
+    }
+
+    // Starts entering the rest of the ticket that was just paid out.
+    pub fn begin_reveal(&mut self) -> Result<(), GameError> {
+        if self.is_simulated() {
+            return Err(Simulated);
+        }
+        match self.finished_ticket() {
+            Some(t) if !self.revealing && self.full_board(t).is_none() => self.revealing = true,
+            Some(_) => return Err(BoardAlreadyRevealed),
+            None => return Err(WrongAction(self.next_action())),
+        }
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+        Ok(())
+    }
+
+    // Fills in the next tile of the board being revealed.
+    // Once the last tile is in, the payout gets checked against the board, which is also how we find out which line they picked.
+    pub fn set_board_number(&mut self, number: u8) -> Result<(), GameError> {
+        if self.is_simulated() {
+            return Err(Simulated);
+        }
+        if number >= 9 {
+            return Err(InvalidNumber(number));
+        }
+        let (t, position) = match (self.finished_ticket(), self.next_action()) {
+            (Some(t), RevealBoard(position)) => (t, position as usize),
+            (_, Done) => return Err(GameOver),
+            (_, action) => return Err(WrongAction(action)),
+        };
+        let mut board = self.ticket_board(t);
+        if board.contains(&number) {
+            return Err(DuplicateNumber(number));
+        }
+        board[position] = number;
+        if !board.contains(&255) {
+            self.line_history[t] = self.find_line(t, &board).ok_or(PayoutMismatch(self.payout_history[t]))?;
+            self.revealing = false;
+        }
+        self.board_history[t][position] = number;
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+        Ok(())
+    }
+
+    // Which line on a full board pays out what the player said they got.
+    // If more than one line does, we assume they listened to the bot, and otherwise just take the first one.
+    fn find_line(&self, ticket: usize, board: &[u8; 9]) -> Option<u8> {
+        let lines: Vec<u8> = (0..8)
+            .filter(|&line| line_payout(board, line as usize) == self.payout_history[ticket])
+            .collect();
+        let (recommended, _) = compute_best_line(&mut self.ticket_as_board(ticket));
+        if lines.contains(&(recommended as u8)) {
+            Some(recommended as u8)
+        } else {
+            lines.first().copied()
+        }
+    }
+
+    // Checks everything that should always be true about a Game, no matter what buttons got pushed.
+    // The setters above should make it impossible for this to fail, so it is only checked in debug builds.
+    pub fn check_invariants(&self) -> Result<(), GameError> {
+        let i = self.index();
+        if i > 12 {
+            return Err(BrokenInvariant("index is past the last ticket"));
+        }
+        // These get used as indexes everywhere, so they're checked before anything else looks at them.
+        if self.board_history.iter().flatten().any(|&x| x >= 9 && x != 255) {
+            return Err(BrokenInvariant("a board has a number that doesn't exist"));
+        }
+        if self.line_history.iter().any(|&x| x >= 8 && x != 255) {
+            return Err(BrokenInvariant("a line that doesn't exist was chosen"));
+        }
+        for ticket in 0..3 {
+            let start = 4 * ticket;
+            let end = i.clamp(start, start + 4);
+            let numbers = &self.number_history[start..end];
+            let positions = &self.position_history[start..end];
+            if numbers.iter().chain(positions.iter()).any(|&x| x >= 9) {
+                return Err(BrokenInvariant("a revealed tile is missing its number or position"));
+            }
+            for k in 0..numbers.len() {
+                if numbers[..k].contains(&numbers[k]) {
+                    return Err(BrokenInvariant("a number was revealed twice on one ticket"));
+                }
+                if positions[..k].contains(&positions[k]) {
+                    return Err(BrokenInvariant("a tile was revealed twice on one ticket"));
+                }
+            }
+            let payout_set = self.payout_history[ticket] != NoPayout;
+            if payout_set && i < start + 4 {
+                return Err(BrokenInvariant("a payout was entered before its ticket was finished"));
+            }
+            if !payout_set && i > start + 4 {
+                return Err(BrokenInvariant("a ticket was started before the last payout was entered"));
+            }
+        }
+        if self.number_history[i..].iter().any(|&x| x != 255) {
+            return Err(BrokenInvariant("a number was entered ahead of the current index"));
+        }
+        // position_history[i] is allowed to be set, that is just a tile waiting for its number.
+        if self.position_history[(i + 1).min(12)..].iter().any(|&x| x != 255) {
+            return Err(BrokenInvariant("a tile was chosen ahead of the current index"));
+        }
+        if i.is_multiple_of(4) && i > 0 && self.payout_history[i / 4 - 1] == NoPayout && i < 12 && self.position_history[i] != 255 {
+            return Err(BrokenInvariant("a tile was chosen before the last payout was entered"));
+        }
+        for ticket in 0..3 {
+            let entered = self.board_history[ticket].iter().filter(|&&x| x != 255).count();
+            if entered > 0 && self.payout_history[ticket] == NoPayout {
+                return Err(BrokenInvariant("a board was entered before its payout"));
+            }
+            let mut board = self.ticket_board(ticket).to_vec();
+            board.retain(|&x| x != 255);
+            board.sort_unstable();
+            board.dedup();
+            let scratched = i.clamp(4 * ticket, 4 * ticket + 4) - 4 * ticket;
+            if board.len() != entered + scratched {
+                return Err(BrokenInvariant("a number or tile shows up twice on a board"));
+            }
+            if (entered == 5) != (self.line_history[ticket] != 255) {
+                return Err(BrokenInvariant("a line is only known when the whole board is"));
+            }
+        }
+        if self.revealing && self.finished_ticket().is_none() {
+            return Err(BrokenInvariant("revealing a board that isn't finished"));
+        }
+        Ok(())
     }
 
     // Undoes one action.
     pub fn undo(&mut self) {
         let i = self.index();
+        if !self.can_undo() {
+            return;
+        }
+        if self.is_simulated() {
+            self.undo_simulated();
+            return;
+        }
         match self.last_action() {
+            RevealBoard(_) => {
+                // last_action() only returns RevealBoard if there is at least one number entered.
+                if let Some(number) = self.board_history[i / 4 - 1].iter_mut().rev().find(|x| **x != 255) {
+                    *number = 255;
+                }
+                self.line_history[i / 4 - 1] = 255;
+                self.revealing = true;
+            },
+            EnterPayout(_) if self.revealing => self.revealing = false,
             EnterPayout(_) => self.payout_history[i / 4 - 1] = NoPayout,
             ChoosePosition(_) => self.position_history[i] = 255,
             RevealNumber(_) => {
@@ -130,11 +542,45 @@ impl Game {
             }
             _ => (),
         };
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+    }
+
+    // In a simulation, the player only ever picks tiles and lines, so those are the only things to undo.
+    // The tile each ticket starts with gets undone along with the line before it, since the player never chose it.
+    fn undo_simulated(&mut self) {
+        let i = self.index();
+        if i == 1 {
+            return;  // Just the tile the game started with.
+        }
+        if i.is_multiple_of(4) && self.payout_history[i / 4 - 1] != NoPayout {
+            self.clear_line(i / 4 - 1);
+        } else {
+            self.number_history[i - 1] = 255;
+            self.position_history[i - 1] = 255;
+            self.index -= 1;
+            if i % 4 == 1 {
+                self.clear_line(i / 4 - 1);
+            }
+        }
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+    }
+
+    fn clear_line(&mut self, ticket: usize) {
+        self.payout_history[ticket] = NoPayout;
+        self.line_history[ticket] = 255;
+        self.board_history[ticket] = [255; 9];
+    }
+
+    // Whether there is anything for undo() to do.
+    pub fn can_undo(&self) -> bool {
+        // Simulated games start one tile in, and that tile can't be undone.
+        // Daily puzzles can't undo at all, otherwise you could peek at a tile and then pick a different one.
+        !matches!(self.last_action(), Start) && (!self.is_simulated() || self.index() != 1) && self.daily.is_none()
     }
 
     pub fn used_numbers(&self) -> &[u8] {
         let i = self.index();
-        &self.number_history[(if let EnterPayout(_) = self.next_action() {
+        &self.number_history[(if let EnterPayout(_) | RevealBoard(_) = self.next_action() {
             i - 4
         } else {
             i - i % 4
@@ -143,13 +589,17 @@ impl Game {
 
     pub fn used_positions(&self) -> &[u8] {
         let i = self.index();
-        &self.position_history[(if let EnterPayout(_) = self.next_action() {
+        &self.position_history[(if let EnterPayout(_) | RevealBoard(_) = self.next_action() {
             i - 4
         } else {
             i - i % 4
         })..i]
     }
 
+    pub fn payout(&self, ticket: usize) -> Payout {
+        self.payout_history[ticket]
+    }
+
     pub fn total_payout(&self) -> u16 {
         let mut output: u16 = 0;
         for p in self.payout_history {
@@ -160,12 +610,124 @@ impl Game {
 
     // note that this only resets ONE SCRATCH TICKET. If you want to fully reset, you will need to click 3 times.
     pub fn reset(&mut self) {
+        if !self.can_undo() {
+            return;
+        }
+        if self.is_simulated() {
+            // Every simulated ticket starts one tile in.
+            self.undo_simulated();
+            while self.index() % 4 != 1 {
+                self.undo_simulated();
+            }
+            return;
+        }
+        while let RevealBoard(_) = self.last_action() {
+            self.undo();
+        }
+        self.revealing = false;
         self.undo();
         while let Done | ChoosePosition(_) | RevealNumber(_) = self.last_action() {
             self.undo();
         }
     }
 
+    // The ticket that was just paid out, if the game is sitting right after a payout.
+    // This is when the rest of the board can be entered.
+    pub fn finished_ticket(&self) -> Option<usize> {
+        let i = self.index();
+        if i > 0
+            && i.is_multiple_of(4)
+            && self.payout_history[i / 4 - 1] != NoPayout
+            && (i == 12 || self.position_history[i] == 255)
+        {
+            Some(i / 4 - 1)
+        } else {
+            None
+        }
+    }
+
+    // Everything we know about one ticket's board, with 255 for anything we don't.
+    pub fn ticket_board(&self, ticket: usize) -> [u8; 9] {
+        let mut state = self.board_history[ticket];
+        let start = 4 * ticket;
+        let end = self.index().clamp(start, start + 4);
+        for k in start..end {
+            state[self.position_history[k] as usize] = self.number_history[k];
+        }
+        state
+    }
+
+    // The positions and numbers the player scratched on one ticket, in the order they did it.
+    pub fn ticket_reveals(&self, ticket: usize) -> (&[u8], &[u8]) {
+        let start = 4 * ticket;
+        let end = self.index().clamp(start, start + 4);
+        (&self.position_history[start..end], &self.number_history[start..end])
+    }
+
+    // Only Some if the player entered the whole board after their payout.
+    pub fn full_board(&self, ticket: usize) -> Option<[u8; 9]> {
+        let state = self.ticket_board(ticket);
+        if state.contains(&255) {
+            None
+        } else {
+            Some(state)
+        }
+    }
+
+    pub fn chosen_line(&self, ticket: usize) -> Option<usize> {
+        match self.line_history[ticket] {
+            255 => None,
+            line => Some(line as usize),
+        }
+    }
+
+    // What every line would have paid out, if we know the full board.
+    pub fn line_payouts(&self, ticket: usize) -> Option<[Payout; 8]> {
+        let state = self.full_board(ticket)?;
+        let mut output = [NoPayout; 8];
+        for line in 0..8 {
+            output[line] = line_payout(&state, line);
+        }
+        Some(output)
+    }
+
+    // Like finished_ticket(), but simulated games have already moved on to the next ticket by the time a line is chosen.
+    pub fn last_finished_ticket(&self) -> Option<usize> {
+        let i = self.index();
+        match self.finished_ticket() {
+            Some(t) => Some(t),
+            None if self.is_simulated() && i > 1 && i % 4 == 1 => Some(i / 4 - 1),
+            None => None,
+        }
+    }
+
+    // Whether the rest of the last ticket can be entered right now.
+    pub fn can_reveal_board(&self) -> bool {
+        matches!(self.finished_ticket(), Some(t) if !self.revealing && self.full_board(t).is_none())
+    }
+
+    fn next_board_position(&self) -> Option<u8> {
+        let t = self.finished_ticket()?;
+        self.ticket_board(t).iter().position(|&x| x == 255).map(|x| x as u8)
+    }
+
+    // Like as_board(), but for any ticket and only using the tiles the player scratched.
+    pub fn ticket_as_board(&self, ticket: usize) -> Board {
+        let start = 4 * ticket;
+        let end = self.index().clamp(start, start + 4);
+        let mut state: [u8; 9] = [255, 255, 255, 255, 255, 255, 255, 255, 255];
+        for k in start..end {
+            state[self.position_history[k] as usize] = self.number_history[k];
+        }
+        let unused_nums: SmallSet<[u8; 9]> = (0..9)
+            .filter(|x| !self.number_history[start..end].contains(x))
+            .collect();
+        Board {
+            state,
+            unused_nums,
+        }
+    }
+
     // converts the game to a Board for computation.
     pub fn as_board(&self) -> Board {
         let mut state: [u8; 9] = [255, 255, 255, 255, 255, 255, 255, 255, 255];
@@ -175,12 +737,111 @@ impl Game {
             state[pos[i] as usize] = nums[i];
         }
         let unused_nums: SmallSet<[u8; 9]> = (0..9)
-            .into_iter()
             .filter(|&x| !self.used_numbers().contains(&x))
             .collect();
         Board {
-            state: state,
-            unused_nums: unused_nums,
+            state,
+            unused_nums,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAMES: u64 = 500;
+    const STEPS: usize = 80;
+
+    // Whatever the player does, next_action() and last_action() should describe the game the same way index does.
+    fn check_actions(game: &Game) {
+        let i = game.index();
+        match game.next_action() {
+            Done => assert_eq!(i, 12),
+            EnterPayout(_) => assert!(i > 0 && i.is_multiple_of(4) && game.payout(i / 4 - 1) == NoPayout),
+            RevealNumber(_) => assert!(i < 12 && game.position_history[i] != 255),
+            ChoosePosition(_) => assert!(i < 12 && game.position_history[i] == 255),
+            RevealBoard(position) => {
+                assert!(game.revealing);
+                assert_eq!(game.finished_ticket(), Some(i / 4 - 1));
+                assert_eq!(game.ticket_board(i / 4 - 1)[position as usize], 255);
+            },
+            Start => panic!("next_action() should never be Start"),
+        }
+        match game.last_action() {
+            Start => assert_eq!(i, 0),
+            ChoosePosition(position) => assert!(i < 12 && game.position_history[i] == position),
+            RevealNumber(number) => assert!(i > 0 && game.number_history[i - 1] == number),
+            EnterPayout(payout) => assert!(payout != NoPayout && game.finished_ticket() == Some(i / 4 - 1) && game.payout(i / 4 - 1) == payout),
+            RevealBoard(number) => assert!(game.finished_ticket().is_some_and(|t| game.board_history[t].contains(&number))),
+            Done => panic!("last_action() should never be Done"),
+        }
+    }
+
+    // One random step, with out of range values thrown in every so often. Anything refused has to leave the game exactly as it was.
+    fn random_step(game: &mut Game, rng: &mut StdRng) {
+        let before = game.to_saved();
+        let result = match rng.gen_range(0..10) {
+            0 | 1 => game.set_position(rng.gen_range(0..10)),
+            2 | 3 => game.set_number(rng.gen_range(0..10)),
+            4 => game.set_payout(Payout::from(&PAYOUT_VALUES[rng.gen_range(0..17)].to_string())),
+            5 => game.choose_line(rng.gen_range(0..9)),
+            6 => game.begin_reveal(),
+            7 => game.set_board_number(rng.gen_range(0..10)),
+            8 => {
+                game.undo();
+                Ok(())
+            },
+            _ => {
+                game.reset();
+                Ok(())
+            },
+        };
+        if result.is_err() {
+            assert_eq!(game.to_saved(), before, "{:?} changed the game", result);
+        }
+    }
+
+    fn play_randomly(mut game: Game, rng: &mut StdRng) {
+        for _ in 0..STEPS {
+            random_step(&mut game, rng);
+            assert_eq!(game.check_invariants(), Ok(()), "{}", game.to_saved());
+            check_actions(&game);
+        }
+    }
+
+    #[test]
+    fn random_play_keeps_invariants() {
+        for seed in 0..GAMES {
+            play_randomly(Game::new(), &mut StdRng::seed_from_u64(seed));
+        }
+    }
+
+    #[test]
+    fn random_simulations_keep_invariants() {
+        for seed in 0..GAMES {
+            play_randomly(Game::new_simulated(Some(seed)), &mut StdRng::seed_from_u64(seed));
+            play_randomly(Game::new_daily(seed, GuildId(1), 0), &mut StdRng::seed_from_u64(seed));
+        }
+    }
+
+    // Random play mostly gets refused once the numbers run out, so this plays a whole game properly too.
+    #[test]
+    fn full_game_reaches_done() {
+        let mut game = Game::new();
+        for t in 0..3 {
+            for k in 0..4 {
+                game.set_position(k).unwrap();
+                game.set_number(k + t as u8).unwrap();
+                check_actions(&game);
+            }
+            game.set_payout(_72).unwrap();
+            check_actions(&game);
         }
+        assert_eq!(game.next_action(), Done);
+        assert_eq!(game.set_position(5), Err(GameOver));
+        game.undo();
+        assert_eq!(game.next_action(), EnterPayout(NoPayout));
+        assert_eq!(game.check_invariants(), Ok(()));
     }
 }
@@ -1,6 +1,7 @@
 use smallset::SmallSet;
 
 // A Board, to be used for computation. Usually, you will want this to be mutable.
+#[derive(Clone)]
 pub struct Board {
     pub state: [u8; 9],
     // A SmallSet is used for easy removing and re-adding of one number at a time.
@@ -38,8 +39,8 @@ impl Board {
                     n if n == state[6] => if state[8] < state[0] || (state[0] == 255 && (state[7] < state[3]) || (state[3] == 255 && state[5] < state[1])) {&FLIP_VERTICAL} else {&ROTATE_RIGHT},
                     n if n == state[8] => if state[2] < state[6] || (state[6] == 255 && (state[5] < state[7]) || (state[7] == 255 && state[1] < state[3])) {&FLIP_ROTATE_TR} else {&ROTATE_180},
                     _ => {
-                        println!("Impossible state reached during board.simplify(): corners case.\n{:?}", state);
-                        return (Board{ state: state.clone(), unused_nums: self.unused_nums.clone() }, &DO_NOTHING)
+                        error!("Impossible state reached during board.simplify(): corners case.\n{:?}", state);
+                        return (Board{ state: *state, unused_nums: self.unused_nums.clone() }, &DO_NOTHING)
                     }
                 }
             } else {
@@ -51,8 +52,8 @@ impl Board {
                         n if n == state[5] => if state[7] < state[1] {&FLIP_ROTATE_TR} else {&ROTATE_LEFT},
                         n if n == state[7] => if state[3] < state[5] {&FLIP_VERTICAL} else {&ROTATE_180},
                         _ => {
-                            println!("Impossible state reached during board.simplify(): corners case.\n{:?}", state);
-                            return (Board{ state: state.clone(), unused_nums: self.unused_nums.clone() }, &DO_NOTHING)
+                            error!("Impossible state reached during board.simplify(): corners case.\n{:?}", state);
+                            return (Board{ state: *state, unused_nums: self.unused_nums.clone() }, &DO_NOTHING)
                         }
                     }
                 } else {  // either just middle or empty board
@@ -1,6 +1,7 @@
-use super::super::generate_components::POSITION_LINE_TABLE;
 use super::board::*;
-use super::payout::{PAYOUTS, PAYOUT_VALUES};
+use super::payout::{PAYOUTS, PAYOUT_VALUES, POSITION_LINE_TABLE};
+
+use std::collections::HashMap;
 
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
@@ -25,7 +26,7 @@ pub fn compute_best_uncover(original_board: &mut Board) -> (usize, [u32; 16]) {
 
     // This is used to know whether we have to compute lines next or not.
     let n = board.state.iter().filter(|&x| x != &255).count();
-    let state_clone = board.state.clone();
+    let state_clone = board.state;
     let empty_indices = state_clone
         .iter()
         .enumerate()
@@ -101,6 +102,37 @@ pub fn compute_best_uncover(original_board: &mut Board) -> (usize, [u32; 16]) {
     (operation[max_i], result[max_i])
 }
 
+// Like compute_best_uncover, but returns the distribution for uncovering every tile instead of just the best one.
+// Tiles that are already uncovered are None.
+// This is not precomputed, but each tile only needs one more level of lookups, so it's still fast.
+pub fn compute_uncover_dists(board: &mut Board) -> [Option<[u32; 16]>; 9] {
+    let n = board.state.iter().filter(|&x| x != &255).count();
+    let mut output = [None; 9];
+    for i in 0..9 {
+        if board.state[i] != 255 {
+            continue;
+        }
+        let mut result = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let unused_nums = board.unused_nums.clone();
+        for x in unused_nums.iter() {
+            board.state[i] = *x;
+            board.unused_nums.remove(x);
+            let (_, data) = if n == 3 {
+                compute_best_line(board)
+            } else {
+                compute_best_uncover(board)
+            };
+            for j in 0..16 {
+                result[j] += data[j];
+            }
+            board.unused_nums.insert(*x);
+        }
+        board.state[i] = 255;
+        output[i] = Some(result);
+    }
+    output
+}
+
 // This function, similar to the above function, returns the index of the best line and a distribution over payouts if you choose that line.
 pub fn compute_best_line(board: &mut Board) -> (usize, [u32; 16]) {
     let data = compute_best_line_rec(board);
@@ -167,3 +199,72 @@ pub fn compute_best_line_rec(board: &mut Board) -> [[u32; 16]; 8] {
         }
     }
 }
+
+// compute_uncover_dists and compute_best_uncover, but every choice from here on is whichever scores highest instead of the best average.
+// None of this is precomputed, so it's a lot slower, especially early in a ticket. Only use it when the average isn't what's wanted.
+pub fn compute_uncover_dists_by<S: PartialOrd>(board: &mut Board, score: &impl Fn(&[u32; 16]) -> S) -> [Option<[u32; 16]>; 9] {
+    uncover_dists_by(board, score, &mut HashMap::new())
+}
+
+pub fn compute_best_uncover_by<S: PartialOrd>(board: &mut Board, score: &impl Fn(&[u32; 16]) -> S) -> (usize, [u32; 16]) {
+    let dists = compute_uncover_dists_by(board, score);
+    best_by(dists.iter().enumerate().filter_map(|(i, data)| data.map(|data| (i, data))), score)
+}
+
+pub fn compute_best_line_by<S: PartialOrd>(board: &mut Board, score: &impl Fn(&[u32; 16]) -> S) -> (usize, [u32; 16]) {
+    best_by(compute_best_line_rec(board).into_iter().enumerate(), score)
+}
+
+// `seen` has the distribution for every board that's already been played out in this search, by its compressed simplified board.
+fn uncover_dists_by<S: PartialOrd>(board: &mut Board, score: &impl Fn(&[u32; 16]) -> S, seen: &mut HashMap<u32, [u32; 16]>) -> [Option<[u32; 16]>; 9] {
+    let mut output = [None; 9];
+    for i in 0..9 {
+        if board.state[i] != 255 {
+            continue;
+        }
+        let mut result = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        let unused_nums = board.unused_nums.clone();
+        for x in unused_nums.iter() {
+            board.state[i] = *x;
+            board.unused_nums.remove(x);
+            let data = play_out_by(board, score, seen);
+            for j in 0..16 {
+                result[j] += data[j];
+            }
+            board.unused_nums.insert(*x);
+        }
+        board.state[i] = 255;
+        output[i] = Some(result);
+    }
+    output
+}
+
+// The distribution from playing the rest of the board by `score`.
+// The same tiles get uncovered in lots of different orders, and rotating or flipping a board doesn't change which lines it has,
+// so remembering these is what makes the search fast enough to answer a button press.
+fn play_out_by<S: PartialOrd>(board: &mut Board, score: &impl Fn(&[u32; 16]) -> S, seen: &mut HashMap<u32, [u32; 16]>) -> [u32; 16] {
+    let key = board.simplify().0.compress();
+    if let Some(data) = seen.get(&key) {
+        return *data;
+    }
+    let (_, data) = if board.state.iter().filter(|&x| x != &255).count() == 4 {
+        compute_best_line_by(board, score)
+    } else {
+        let dists = uncover_dists_by(board, score, seen);
+        best_by(dists.iter().enumerate().filter_map(|(i, data)| data.map(|data| (i, data))), score)
+    };
+    seen.insert(key, data);
+    data
+}
+
+// The first of the highest scoring choices, like the max loops above.
+fn best_by<S: PartialOrd>(choices: impl Iterator<Item = (usize, [u32; 16])>, score: &impl Fn(&[u32; 16]) -> S) -> (usize, [u32; 16]) {
+    let mut best: Option<(usize, [u32; 16], S)> = None;
+    for (i, data) in choices {
+        let s = score(&data);
+        if best.as_ref().is_none_or(|(_, _, max)| s > *max) {
+            best = Some((i, data, s));
+        }
+    }
+    best.map(|(i, data, _)| (i, data)).unwrap_or((9, [0; 16]))
+}
@@ -0,0 +1,333 @@
+// A short text version of a Game, so players can share exactly what happened (or what they're stuck on).
+//
+// Each ticket is a list of tokens separated by spaces, and tickets are separated by " / ". Everything is 1-indexed, like the buttons.
+// - `53`: the 3 under tile 5. Tiles are numbered 1-9 in reading order, and these go in the order they were revealed.
+// - `$252`: the payout.
+// - `B218596347`: the whole board in reading order, once it's known. Every tile needs its number.
+// - `L3`: the line that was picked, in the same order as LINE_NAMES. Only needed if more than one line pays the same.
+//
+// So a finished ticket and the start of the next one look like `53 17 92 34 $108 B714935862 L3 / 61 27`.
+// Within a ticket, the tokens have to go in that order.
+
+use super::*;
+
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum NotationError {
+    Empty,
+    TooManyTickets(usize),
+    // The ticket (0-indexed) and the token that couldn't be read.
+    BadToken(usize, String),
+    TooManyReveals(usize),
+    // Reveals, then the payout, then the board, then the line. Anything else is probably a typo.
+    OutOfOrder(usize, String),
+    // A ticket that doesn't have a payout, but isn't the last one either.
+    UnfinishedTicket(usize),
+    // A board with a `?` in it. The game would be stuck waiting for the rest, so those aren't taken.
+    IncompleteBoard(usize),
+    // The board disagrees with a tile that was revealed, at this position.
+    BoardMismatch(usize, u8),
+    BoardWithoutPayout(usize),
+    LineWithoutBoard(usize),
+    LineMismatch(usize, u8),
+    // Anything the Game itself refused, along with the token it was reading.
+    Refused(usize, String, GameError),
+}
+
+use NotationError::*;
+
+// These get shown to the user too, same as GameError.
+impl NotationError {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            Empty => tr(locale, "notation.empty").to_string(),
+            TooManyTickets(n) => trf(locale, "notation.too_many_tickets", &[n]),
+            BadToken(t, token) => trf(locale, "notation.bad_token", &[&(t + 1), token]),
+            TooManyReveals(t) => trf(locale, "notation.too_many_reveals", &[&(t + 1)]),
+            OutOfOrder(t, token) => trf(locale, "notation.out_of_order", &[&(t + 1), token]),
+            UnfinishedTicket(t) => trf(locale, "notation.unfinished_ticket", &[&(t + 1)]),
+            IncompleteBoard(t) => trf(locale, "notation.incomplete_board", &[&(t + 1)]),
+            BoardMismatch(t, p) => trf(locale, "notation.board_mismatch", &[&(t + 1), &(p + 1)]),
+            BoardWithoutPayout(t) => trf(locale, "notation.board_without_payout", &[&(t + 1)]),
+            LineWithoutBoard(t) => trf(locale, "notation.line_without_board", &[&(t + 1)]),
+            LineMismatch(t, l) => trf(locale, "notation.line_mismatch", &[&(t + 1), &(l + 1)]),
+            Refused(t, token, e) => trf(locale, "notation.refused", &[&(t + 1), token, &e.message(locale)]),
+        }
+    }
+}
+
+impl Display for NotationError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(fmt, "{}", self.message(Locale::English))
+    }
+}
+
+// One ticket's worth of tokens, before any of it gets checked against the rules.
+#[derive(Default)]
+struct TicketNotation {
+    reveals: Vec<(u8, u8, String)>,
+    payout: Option<(Payout, String)>,
+    board: Option<([u8; 9], String)>,
+    line: Option<u8>,
+}
+
+impl Game {
+    // Reveals that are halfway entered (a tile without its number yet) are left out, there's no way to write those down.
+    pub fn to_notation(&self) -> String {
+        let mut tickets = vec![];
+        for t in 0..3 {
+            let mut tokens = vec![];
+            for k in 4 * t..4 * t + 4 {
+                if self.position_history[k] != 255 && self.number_history[k] != 255 {
+                    tokens.push(format!("{}{}", self.position_history[k] + 1, self.number_history[k] + 1));
+                }
+            }
+            if tokens.is_empty() {
+                break;
+            }
+            if self.payout_history[t] != NoPayout {
+                tokens.push(format!("${}", PAYOUT_VALUES[self.payout_history[t] as usize]));
+            }
+            if let (Some(board), Some(line)) = (self.full_board(t), self.chosen_line(t)) {
+                tokens.push(format!("B{}", board.iter().map(|n| (n + 1).to_string()).collect::<String>()));
+                tokens.push(format!("L{}", line + 1));
+            }
+            tickets.push(tokens.join(" "));
+        }
+        tickets.join(" / ")
+    }
+
+    // Plays the notation back through the same methods the buttons use, so anything a player couldn't have entered gets refused the same way.
+    pub fn from_notation(notation: &str) -> Result<Game, NotationError> {
+        let tickets = notation.split('/').map(str::trim).collect::<Vec<&str>>();
+        if tickets.iter().all(|ticket| ticket.is_empty()) {
+            return Err(Empty);
+        }
+        if tickets.len() > 3 {
+            return Err(TooManyTickets(tickets.len()));
+        }
+        let mut game = Game::new();
+        for (t, ticket) in tickets.iter().enumerate() {
+            let parsed = parse_ticket(t, ticket)?;
+            if parsed.payout.is_none() && t + 1 < tickets.len() {
+                return Err(UnfinishedTicket(t));
+            }
+            for (position, number, token) in parsed.reveals.iter() {
+                game.set_position(*position).map_err(|e| Refused(t, token.clone(), e))?;
+                game.set_number(*number).map_err(|e| Refused(t, token.clone(), e))?;
+            }
+            if let Some((payout, token)) = &parsed.payout {
+                game.set_payout(*payout).map_err(|e| Refused(t, token.clone(), e))?;
+            }
+            if let Some((board, token)) = parsed.board {
+                if parsed.payout.is_none() {
+                    return Err(BoardWithoutPayout(t));
+                }
+                for (position, number, _) in parsed.reveals.iter() {
+                    if board[*position as usize] != *number {
+                        return Err(BoardMismatch(t, *position));
+                    }
+                }
+                game.begin_reveal().map_err(|e| Refused(t, token.clone(), e))?;
+                while let RevealBoard(position) = game.next_action() {
+                    game.set_board_number(board[position as usize]).map_err(|e| Refused(t, token.clone(), e))?;
+                }
+            }
+            if let Some(line) = parsed.line {
+                let board = game.full_board(t).ok_or(LineWithoutBoard(t))?;
+                if line_payout(&board, line as usize) != game.payout_history[t] {
+                    return Err(LineMismatch(t, line));
+                }
+                game.line_history[t] = line;
+            }
+        }
+        debug_assert_eq!(game.check_invariants(), Ok(()));
+        Ok(game)
+    }
+}
+
+fn parse_ticket(t: usize, ticket: &str) -> Result<TicketNotation, NotationError> {
+    let mut output = TicketNotation::default();
+    for token in ticket.split_whitespace() {
+        let bad_token = || BadToken(t, token.to_string());
+        let out_of_order = || OutOfOrder(t, token.to_string());
+        let digit = |c: char| c.to_digit(10).filter(|&d| d >= 1).map(|d| d as u8 - 1);
+        let mut chars = token.chars();
+        match chars.next() {
+            Some('$') => {
+                // The buttons say 10k, so that works too.
+                let payout = Payout::from(&chars.as_str().replace("10k", "10000"));
+                if payout == NoPayout || output.payout.is_some() {
+                    return Err(bad_token());
+                }
+                if output.board.is_some() || output.line.is_some() {
+                    return Err(out_of_order());
+                }
+                output.payout = Some((payout, token.to_string()));
+            },
+            Some('B') | Some('b') => {
+                if chars.as_str().contains('?') {
+                    return Err(IncompleteBoard(t));
+                }
+                let board = chars.map(digit).collect::<Option<Vec<u8>>>().ok_or_else(bad_token)?;
+                if output.board.is_some() {
+                    return Err(bad_token());
+                }
+                if output.line.is_some() {
+                    return Err(out_of_order());
+                }
+                output.board = Some((board.try_into().map_err(|_| bad_token())?, token.to_string()));
+            },
+            Some('L') | Some('l') => {
+                let line = chars.as_str().parse::<u8>().ok().filter(|l| (1..=8).contains(l)).ok_or_else(bad_token)?;
+                if output.line.is_some() {
+                    return Err(bad_token());
+                }
+                output.line = Some(line - 1);
+            },
+            Some(c) => {
+                let (position, number) = match (digit(c), chars.next().and_then(digit), chars.next()) {
+                    (Some(position), Some(number), None) => (position, number),
+                    _ => return Err(bad_token()),
+                };
+                if output.payout.is_some() || output.board.is_some() || output.line.is_some() {
+                    return Err(out_of_order());
+                }
+                if output.reveals.len() == 4 {
+                    return Err(TooManyReveals(t));
+                }
+                output.reveals.push((position, number, token.to_string()));
+            },
+            None => (),
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAMES: u64 = 500;
+
+    // Plays a real board the way someone at the Gold Saucer would, stopping at a random point.
+    fn random_game(rng: &mut StdRng) -> Game {
+        let mut game = Game::new();
+        for _ in 0..3 {
+            let mut board = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+            board.shuffle(rng);
+            let mut positions = [0, 1, 2, 3, 4, 5, 6, 7, 8];
+            positions.shuffle(rng);
+            for &position in positions.iter().take(4) {
+                if rng.gen_range(0..12) == 0 {
+                    return game
+                }
+                game.set_position(position).unwrap();
+                // Halfway entered, which the notation leaves out.
+                if rng.gen_range(0..12) == 0 {
+                    return game
+                }
+                game.set_number(board[position as usize]).unwrap();
+            }
+            if rng.gen_range(0..6) == 0 {
+                return game
+            }
+            game.set_payout(line_payout(&board, rng.gen_range(0..8))).unwrap();
+            if rng.gen_bool(0.5) {
+                game.begin_reveal().unwrap();
+                while let RevealBoard(position) = game.next_action() {
+                    game.set_board_number(board[position as usize]).unwrap();
+                }
+            }
+        }
+        game
+    }
+
+    fn simulated_game(seed: u64, rng: &mut StdRng) -> Game {
+        let mut game = Game::new_simulated(Some(seed));
+        for _ in 0..rng.gen_range(0..60) {
+            let _ = match rng.gen_bool(0.8) {
+                true => game.set_position(rng.gen_range(0..9)),
+                false => game.choose_line(rng.gen_range(0..8)),
+            };
+        }
+        game
+    }
+
+    fn assert_round_trip(game: &Game) {
+        let notation = game.to_notation();
+        if notation.is_empty() {
+            return
+        }
+        let imported = Game::from_notation(&notation).unwrap_or_else(|why| panic!("{}: {}", notation, why));
+        assert_eq!(imported.to_notation(), notation);
+        assert_eq!(imported.check_invariants(), Ok(()));
+        for t in 0..3 {
+            assert_eq!(imported.payout(t), game.payout(t), "{}", notation);
+            assert_eq!(imported.full_board(t), game.full_board(t), "{}", notation);
+            assert_eq!(imported.chosen_line(t), game.full_board(t).and(game.chosen_line(t)), "{}", notation);
+        }
+        assert!(!imported.revealing, "{}", notation);
+    }
+
+    #[test]
+    fn played_games_round_trip() {
+        for seed in 0..GAMES {
+            assert_round_trip(&random_game(&mut StdRng::seed_from_u64(seed)));
+        }
+    }
+
+    #[test]
+    fn simulations_round_trip() {
+        for seed in 0..GAMES {
+            assert_round_trip(&simulated_game(seed, &mut StdRng::seed_from_u64(seed)));
+        }
+    }
+
+    fn refused(notation: &str) -> NotationError {
+        match Game::from_notation(notation) {
+            Ok(game) => panic!("{} was imported as {}", notation, game.to_notation()),
+            Err(why) => why,
+        }
+    }
+
+    #[test]
+    fn illegal_boards_are_refused() {
+        assert_eq!(refused(" / "), Empty);
+        assert_eq!(refused("53 17 92 34 $108 / 11 / 22 / 33"), TooManyTickets(4));
+        // The same number twice, on the revealed tiles or on the board.
+        assert_eq!(refused("53 13"), Refused(0, "13".to_string(), GameError::DuplicateNumber(2)));
+        assert_eq!(refused("53 17 92 34 $108 B714935812"), Refused(0, "B714935812".to_string(), GameError::DuplicateNumber(0)));
+        // Out of range, or not a number at all.
+        assert_eq!(refused("03"), BadToken(0, "03".to_string()));
+        assert_eq!(refused("530"), BadToken(0, "530".to_string()));
+        assert_eq!(refused("53 17 92 34 $999"), BadToken(0, "$999".to_string()));
+        assert_eq!(refused("53 17 92 34 $108 B71493586"), BadToken(0, "B71493586".to_string()));
+        assert_eq!(refused("53 17 92 34 $108 B714935862 L9"), BadToken(0, "L9".to_string()));
+        assert_eq!(refused("53 17 92 34 55"), TooManyReveals(0));
+        // Reveals after the payout, board or line.
+        assert_eq!(refused("53 17 92 $108 34"), OutOfOrder(0, "34".to_string()));
+        assert_eq!(refused("53 17 92 34 $108 B714935862 L3 61"), OutOfOrder(0, "61".to_string()));
+        assert_eq!(refused("53 17 92 34 $108 L3 B714935862"), OutOfOrder(0, "B714935862".to_string()));
+        assert_eq!(refused("53 17 92 34 / 61"), UnfinishedTicket(0));
+        assert_eq!(refused("53 17 92 34 $108 B314935862"), BoardMismatch(0, 0));
+        assert_eq!(refused("53 17 92 34 B714935862"), BoardWithoutPayout(0));
+        assert_eq!(refused("53 17 92 34 $108 L3"), LineWithoutBoard(0));
+        assert_eq!(refused("53 17 92 34 $108 B714935862 L1"), LineMismatch(0, 0));
+    }
+
+    // A `?` would leave the game waiting for the rest of the board, with no way to enter it.
+    #[test]
+    fn unfinished_boards_are_refused() {
+        assert_eq!(refused("53 17 92 34 $108 B7149358??"), IncompleteBoard(0));
+        assert_eq!(refused("53 17 92 34 $108 B?????????"), IncompleteBoard(0));
+    }
+
+    #[test]
+    fn errors_are_translated() {
+        let why = refused("53 13");
+        assert_ne!(why.message(Locale::Japanese), why.message(Locale::English));
+        assert_eq!(why.to_string(), why.message(Locale::English));
+    }
+}
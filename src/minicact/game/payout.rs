@@ -4,6 +4,7 @@ use crate::minicact::game::payout::Payout::*;
 
 // All possible payouts. These aren't really used other than compressing the size of a Game by 4 bytes, and a couple conversions.
 #[derive(PartialEq, Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)]  // NoPayout reads better than None here, since None is already taken by Option.
 pub enum Payout {
     NoPayout,
     _36,
@@ -31,11 +32,37 @@ pub const PAYOUT_VALUES: [u16; 17] = [0, 36, 54, 72, 80, 108, 119, 144, 180, 252
 // Except for the first 3, since they should be unreachable. I chose 22 because it would stand out, i.e. "how is it 22?? oh."
 pub const PAYOUTS: [usize; 22] = [22, 22, 22, 15, 0, 11, 10, 3, 8, 4, 2, 1, 7, 2, 7, 5, 0, 9, 12, 6, 13, 14];
 
+// Which positions are on each line. Every other list of lines (names, buttons, strategies) goes in this order.
+pub const POSITION_LINE_TABLE: [[bool; 9]; 8] = [
+    [false, false, false, false, false, false, true, true, true],  // bottom row
+    [false, false, false, true, true, true, false, false, false],  // middle row
+    [true, true, true, false, false, false, false, false, false],  // top row
+    [true, false, false, false, true, false, false, false, true],  // \ diagonal
+    [true, false, false, true, false, false, true, false, false],  // left column
+    [false, true, false, false, true, false, false, true, false],  // middle column
+    [false, false, true, false, false, true, false, false, true],  // right column
+    [false, false, true, false, true, false, true, false, false],  // / diagonal
+];
+
+// Every Payout in the same order as PAYOUT_VALUES, for going from an index back to a Payout.
+const ALL_PAYOUTS: [Payout; 17] = [NoPayout, _36, _54, _72, _80, _108, _119, _144, _180, _252, _306, _360, _720, _1080, _1800, _3600, _10000];
+
+// What a line on a full board pays out.
+pub fn line_payout(board: &[u8; 9], line: usize) -> Payout {
+    let mut total = 0;
+    for j in 0..9 {
+        if POSITION_LINE_TABLE[line][j] {
+            total += board[j] as usize;
+        }
+    }
+    ALL_PAYOUTS[PAYOUTS[total] + 1]
+}
+
 // TIL that this gets you ToString for free
 impl Display for Payout {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
-            _10000 => write!(fmt, "10k"), // 5 characters is too wide for the button
+            Payout::_10000 => write!(fmt, "10k"), // 5 characters is too wide for the button
             _ => write!(fmt, "{}", PAYOUT_VALUES[*self as usize])
         }
     }
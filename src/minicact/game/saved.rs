@@ -0,0 +1,182 @@
+// How a Game gets written to the active games file, so nobody loses their game when the bot shuts down.
+//
+// Unlike the notation, this keeps everything, down to the nonce, so the buttons on messages from before the restart keep working.
+// It's one line of `key=value` fields, the same idea as the preferences file:
+// `nonce=123 started=1700000000 index=5 positions=4,0,8,2,6,... numbers=... payouts=252,0,0 boards=... lines=... seed=none daily=none revealing=false`
+// The fields can go in any order, but `revealing` goes last when writing, since no line cut off partway through it still reads as true or false.
+// Lists use 255 for anything not entered yet, like the Game itself. Payouts are written as what they pay, 0 for none.
+// Simulated tickets aren't written down, since they come right back out of the seed.
+
+use super::*;
+
+impl Game {
+    pub fn to_saved(&self) -> String {
+        let list = |items: &[u8]| items.iter().map(|item| item.to_string()).collect::<Vec<String>>().join(",");
+        format!(
+            "nonce={} started={} index={} positions={} numbers={} payouts={} boards={} lines={} seed={} daily={} revealing={}",
+            self.nonce,
+            self.started,
+            self.index,
+            list(&self.position_history),
+            list(&self.number_history),
+            self.payout_history.iter().map(|payout| PAYOUT_VALUES[*payout as usize].to_string()).collect::<Vec<String>>().join(","),
+            list(&self.board_history.concat()),
+            list(&self.line_history),
+            self.seed.map_or("none".to_string(), |seed| seed.to_string()),
+            self.daily.map_or("none".to_string(), |(guild, day)| format!("{}:{}", guild, day)),
+            self.revealing
+        )
+    }
+
+    // Anything missing or off is refused instead of guessed at, since a game that breaks halfway through is worse than no game.
+    pub fn from_saved(s: &str) -> Result<Game, &'static str> {
+        let mut game = Game::new();
+        let mut seen = vec![];
+        for field in s.split_whitespace() {
+            let (key, value) = field.split_once('=').ok_or("saved game is missing an =")?;
+            // Otherwise a field written twice could stand in for one that's missing.
+            if seen.contains(&key) {
+                return Err("saved game has a field twice");
+            }
+            match key {
+                "nonce" => game.nonce = value.parse().map_err(|_| "nonce failed to parse")?,
+                "started" => game.started = value.parse().map_err(|_| "start time failed to parse")?,
+                "index" => game.index = value.parse().map_err(|_| "index failed to parse")?,
+                "positions" => game.position_history = parse_list(value).ok_or("positions failed to parse")?,
+                "numbers" => game.number_history = parse_list(value).ok_or("numbers failed to parse")?,
+                "payouts" => {
+                    let payouts = value.split(',')
+                        .map(|payout| match payout {
+                            "0" => Some(NoPayout),
+                            payout => Some(Payout::from(&payout.to_string())).filter(|payout| *payout != NoPayout),
+                        })
+                        .collect::<Option<Vec<Payout>>>().ok_or("payouts failed to parse")?;
+                    game.payout_history = payouts.try_into().map_err(|_| "there should be 3 payouts")?;
+                },
+                "boards" => {
+                    let boards: [u8; 27] = parse_list(value).ok_or("boards failed to parse")?;
+                    for (t, board) in boards.chunks(9).enumerate() {
+                        game.board_history[t].copy_from_slice(board);
+                    }
+                },
+                "lines" => game.line_history = parse_list(value).ok_or("lines failed to parse")?,
+                "revealing" => game.revealing = value.parse().map_err(|_| "revealing failed to parse")?,
+                "seed" => game.seed = match value {
+                    "none" => None,
+                    seed => Some(seed.parse().map_err(|_| "seed failed to parse")?),
+                },
+                "daily" => game.daily = match value.split_once(':') {
+                    None if value == "none" => None,
+                    Some((guild, day)) => Some((
+                        GuildId(guild.parse().map_err(|_| "daily guild failed to parse")?),
+                        day.parse().map_err(|_| "daily day failed to parse")?,
+                    )),
+                    None => return Err("daily failed to parse"),
+                },
+                _ => return Err("saved game has a field it doesn't know"),
+            }
+            seen.push(key);
+        }
+        if seen.len() != 11 {
+            return Err("saved game is missing fields");
+        }
+        game.hidden = game.seed.map(seeded_tickets);
+        game.check_invariants().map_err(|_| "saved game breaks the rules")?;
+        // If rand changed how seeds turn into tickets since this was saved, the game won't line up with its tickets anymore.
+        if let Some(hidden) = game.hidden {
+            for k in 0..game.index() {
+                if hidden[k / 4].board[game.position_history[k] as usize] != game.number_history[k] {
+                    return Err("saved game doesn't match its seed anymore");
+                }
+            }
+        }
+        Ok(game)
+    }
+}
+
+fn parse_list<const N: usize>(value: &str) -> Option<[u8; N]> {
+    value.split(',').map(|item| item.parse().ok()).collect::<Option<Vec<u8>>>()?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Whatever comes back has to be the same game, down to what the next button press would do.
+    fn assert_round_trip(game: &Game) {
+        let saved = game.to_saved();
+        let loaded = Game::from_saved(&saved).unwrap_or_else(|why| panic!("{}: {}", saved, why));
+        assert_eq!(loaded.to_saved(), saved);
+        assert_eq!(loaded.nonce(), game.nonce());
+        assert_eq!(loaded.mode(), game.mode());
+        assert_eq!(loaded.daily(), game.daily());
+        assert_eq!(loaded.seed(), game.seed());
+        assert_eq!(loaded.next_action(), game.next_action());
+        assert_eq!(loaded.hidden.map(|hidden| hidden.map(|ticket| ticket.board)), game.hidden.map(|hidden| hidden.map(|ticket| ticket.board)));
+    }
+
+    // Plays up to the payout, then into the board for anything that isn't simulated, so every field has something in it.
+    fn play(game: &mut Game) {
+        loop {
+            match game.next_action() {
+                ChoosePosition(_) => game.set_position((0..9).find(|p| !game.used_positions().contains(p)).unwrap()).unwrap(),
+                RevealNumber(_) => game.set_number(game.position_history[game.index()]).unwrap(),
+                _ => break,
+            }
+        }
+        if game.is_simulated() {
+            game.choose_line(3).unwrap();
+        } else {
+            // The numbers match the positions, so the board is 1-9 in reading order.
+            game.set_payout(line_payout(&[0, 1, 2, 3, 4, 5, 6, 7, 8], 3)).unwrap();
+            game.begin_reveal().unwrap();
+            if let RevealBoard(position) = game.next_action() {
+                game.set_board_number(position).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn every_mode_round_trips() {
+        let games = [Game::new(), Game::new_simulated(None), Game::new_simulated(Some(1234)), Game::new_daily(5678, GuildId(42), 20000)];
+        for mut game in games {
+            assert_round_trip(&game);
+            play(&mut game);
+            assert_round_trip(&game);
+        }
+    }
+
+    #[test]
+    fn truncated_lines_are_refused() {
+        let mut game = Game::new_daily(5678, GuildId(42), 20000);
+        play(&mut game);
+        let saved = game.to_saved();
+        // Cut off partway through a field, and right between two of them.
+        for length in 1..saved.len() {
+            if let Some(truncated) = saved.get(..length) {
+                assert!(Game::from_saved(truncated).is_err(), "{}", truncated);
+            }
+        }
+        let fields = saved.split_whitespace().collect::<Vec<&str>>();
+        assert_eq!(Game::from_saved(&fields[..fields.len() - 1].join(" ")).err(), Some("saved game is missing fields"));
+        assert!(Game::from_saved("").is_err());
+    }
+
+    #[test]
+    fn broken_fields_are_refused() {
+        let mut game = Game::new();
+        play(&mut game);
+        let saved = game.to_saved();
+        let replace = |key: &str, value: &str| saved.split_whitespace()
+            .map(|field| if field.starts_with(&format!("{}=", key)) { format!("{}={}", key, value) } else { field.to_string() })
+            .collect::<Vec<String>>().join(" ");
+        let mut boards = [255; 27];
+        boards[4] = 9;
+        let out_of_range = replace("boards", &boards.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(","));
+        assert_eq!(Game::from_saved(&out_of_range).err(), Some("saved game breaks the rules"));
+        assert_eq!(Game::from_saved(&replace("lines", "8,255,255")).err(), Some("saved game breaks the rules"));
+        // One field twice and another left out still adds up to the right number of fields.
+        let doubled = saved.replace("seed=none", "nonce=1");
+        assert_eq!(Game::from_saved(&doubled).err(), Some("saved game has a field twice"));
+    }
+}
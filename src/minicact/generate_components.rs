@@ -1,52 +1,70 @@
 use crate::generate_components::make_button;
 
+use crate::locale::{tr, trf, Locale};
+use super::accessibility::describe_board;
+use super::custom_id::CustomId;
 use super::game::*;
 use super::game::Action::*;
 use super::game::payout::*;
-use super::game::computations::compute_best_line;
+use super::quiz::{Grade, Quiz};
 
-use rand::seq::index::sample_weighted;
 use serenity::builder::CreateComponents;
 use serenity::model::prelude::component::ButtonStyle;
 
-use rand::seq::IteratorRandom;
-
 // damn, I miss one-indexing... Julia my beloved D:
-const NUMBER_EMOJI: [&'static str; 9] = [
+const NUMBER_EMOJI: [&str; 9] = [
     "1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣", "9️⃣"
 ];
 
-const POSITION_EMOJI: [&'static str; 9] = [
+pub const POSITION_EMOJI: [&str; 9] = [
     "↖", "⬆", "↗", "⬅", "🇽", "➡", "↙",  "⬇", "↘"
 ];
 
-pub const POSITION_LINE_TABLE: [[bool; 9]; 8] = [
-    [false, false, false, false, false, false, true, true, true],  // bottom row
-    [false, false, false, true, true, true, false, false, false],  // middle row
-    [true, true, true, false, false, false, false, false, false],  // top row
-    [true, false, false, false, true, false, false, false, true],  // \ diagonal
-    [true, false, false, true, false, false, true, false, false],  // left column
-    [false, true, false, false, true, false, false, true, false],  // middle column
-    [false, false, true, false, false, true, false, false, true],  // right column
-    [false, false, true, false, true, false, true, false, false],  // / diagonal
-];
+// Lines are in the same order as POSITION_LINE_TABLE, and positions in the same order as POSITION_EMOJI.
+pub fn line_name(locale: Locale, line: usize) -> &'static str {
+    tr(locale, format!("line.{}", line).as_str())
+}
+
+// For the middle of a sentence, e.g. "you picked the bottom row".
+pub fn line_name_inline(locale: Locale, line: usize) -> &'static str {
+    tr(locale, format!("line.{}.inline", line).as_str())
+}
+
+pub fn position_name(locale: Locale, position: usize) -> &'static str {
+    tr(locale, format!("position.{}", position).as_str())
+}
 
-pub fn make_numpad_rows<'a>(components: &'a mut CreateComponents, game: &Game) -> &'a mut CreateComponents {
-    let chosen_i = if game.is_simulated() {
-        (0..9)
-            .filter(|x| !game.used_numbers().contains(x))
-            .choose(&mut rand::thread_rng())
-            .expect("Somehow there are 9 used numbers???")
-    } else {255};
-    
+// `accessible` everywhere below means buttons get written labels instead of emoji, and nothing is shown only by its color.
+
+pub fn make_numpad_rows<'a>(components: &'a mut CreateComponents, game: &Game, accessible: bool, locale: Locale) -> &'a mut CreateComponents {
+    // When entering the rest of the board, the numpad is asking about a specific tile instead.
+    if let (RevealBoard(position), Some(t)) = (game.next_action(), game.finished_ticket()) {
+        let board = game.ticket_board(t);
+        for j in 0..3 {
+            components.create_action_row(|action_row| {
+                for i in (3*j)..(3*j+3) {
+                    let (emoji, label) = number_button(i, accessible, locale);
+                    make_button(action_row,
+                        CustomId::BoardNumpad { nonce: game.nonce(), position, number: i },
+                        ButtonStyle::Primary,
+                        emoji,
+                        label.as_deref(),
+                        board.contains(&i));
+                }
+                action_row
+            });
+        }
+        return components
+    }
     for j in 0..3 {
         components.create_action_row(|action_row| {
             for i in (3*j)..(3*j+3) {
+                let (emoji, label) = number_button(i, accessible, locale);
                 make_button(action_row,
-                    format!("minicact_numpad_{:02}_{}", game.index(), i),
-                    if i == chosen_i {ButtonStyle::Secondary} else {ButtonStyle::Primary},
-                    Some(NUMBER_EMOJI[i as usize]),
-                    None,
+                    CustomId::Numpad { nonce: game.nonce(), index: game.index() as u8, number: i },
+                    ButtonStyle::Primary,
+                    emoji,
+                    label.as_deref(),
                     game.used_numbers().contains(&i));
             }
             action_row
@@ -55,13 +73,27 @@ pub fn make_numpad_rows<'a>(components: &'a mut CreateComponents, game: &Game) -
     components
 }
 
-pub fn make_game_rows<'a>(components: &'a mut CreateComponents, game: &Game, recommendation: usize) -> &'a mut CreateComponents {
-    let chosen_i = if game.is_simulated() && matches!(game.last_action(), Start | EnterPayout(_)) {
-        (0..9)
-            .choose(&mut rand::thread_rng())
-            .expect("Failed to choose a number?????")
-    } else {255};
+fn number_button(number: u8, accessible: bool, locale: Locale) -> (Option<&'static str>, Option<String>) {
+    if accessible {
+        (None, Some(trf(locale, "button.reveal_number", &[&(number + 1)])))
+    } else {
+        (Some(NUMBER_EMOJI[number as usize]), None)
+    }
+}
+
+// The label for tile i in accessible mode. `number` is what's under it, if it's uncovered, and `note` is the key for why it's highlighted.
+fn tile_label(i: usize, number: Option<u8>, note: Option<&str>, locale: Locale) -> String {
+    let mut output = position_name(locale, i).to_string();
+    if let Some(number) = number {
+        output.push_str(format!(": {}", number + 1).as_str());
+    }
+    if let Some(note) = note {
+        output.push_str(format!(" ({})", tr(locale, note)).as_str());
+    }
+    output
+}
 
+pub fn make_game_rows<'a>(components: &'a mut CreateComponents, game: &Game, recommendation: usize, accessible: bool, locale: Locale) -> &'a mut CreateComponents {
     for j in 0..3 {
         components.create_action_row(|action_row| {
             for i in (3*j)..(3*j+3) {
@@ -70,31 +102,34 @@ pub fn make_game_rows<'a>(components: &'a mut CreateComponents, game: &Game, rec
                 // update: this is not even the ugliest nest of if statements in this project anymore. See minicact_component().
 
                 // if payout is true, then recommendation is guaranteed to be valid (i.e. not 255).
-                let payout_style = if payout && POSITION_LINE_TABLE[recommendation][i as usize] {ButtonStyle::Success} else {ButtonStyle::Primary};
+                let on_line = payout && POSITION_LINE_TABLE[recommendation][i as usize];
+                let payout_style = if on_line {ButtonStyle::Success} else {ButtonStyle::Primary};
+                let line_note = on_line.then_some("note.recommended_line");
                 if let Some(k) = game.used_positions().iter().position(|a| a == &i) {  // if the game is using position i already.
+                    let number = game.used_numbers()[k];
+                    let label = accessible.then(|| tile_label(i as usize, Some(number), line_note, locale));
                     make_button(action_row, 
-                        format!("minicact_game_{:02}_{}", game.index(), i), 
+                        CustomId::Tile { nonce: game.nonce(), index: game.index() as u8, position: i }, 
                         payout_style, 
-                        Some(NUMBER_EMOJI[game.used_numbers()[k] as usize]), 
-                        None, 
+                        (!accessible).then_some(NUMBER_EMOJI[number as usize]), 
+                        label.as_deref(), 
                         true);  // the emoji corresponding to the number at position i.
                 } else if payout{
+                    let label = accessible.then(|| tile_label(i as usize, None, line_note, locale));
                     make_button(action_row, 
-                        format!("minicact_X_game_{:02}_{}", game.index(), i), 
+                        CustomId::DisabledTile { nonce: game.nonce(), index: game.index() as u8, position: i }, 
                         payout_style, 
-                        Some("🟡"), 
-                        None, 
+                        (!accessible).then_some("🟡"), 
+                        label.as_deref(), 
                         true);
                 } else {
+                    let recommended = i as usize == recommendation;
+                    let label = accessible.then(|| tile_label(i as usize, None, recommended.then_some("note.recommended"), locale));
                     make_button(action_row, 
-                        format!("minicact_game_{:02}_{}", game.index(), i), 
-                        match i{
-                            _ if i as usize == recommendation => ButtonStyle::Success,
-                            _ if i == chosen_i => ButtonStyle::Secondary,
-                            _ => ButtonStyle::Primary
-                        }, 
-                        Some("🟡"), 
-                        None,
+                        CustomId::Tile { nonce: game.nonce(), index: game.index() as u8, position: i }, 
+                        if recommended {ButtonStyle::Success} else {ButtonStyle::Primary}, 
+                        (!accessible).then_some("🟡"), 
+                        label.as_deref(),
                         false);
                 }
             }
@@ -104,21 +139,19 @@ pub fn make_game_rows<'a>(components: &'a mut CreateComponents, game: &Game, rec
     components
 }
 
-pub fn make_payout_dropdown<'a>(components: &'a mut CreateComponents, game: &Game) -> &'a mut CreateComponents {
-    let chosen_i = if game.is_simulated() {
-        let (_, payout_dist) = compute_best_line(&mut game.as_board());
-        sample_weighted(&mut rand::thread_rng(), 16, |x| payout_dist[x], 1).expect("No Possible Payouts???").iter().next().expect("No result??") + 1
-    } else {255};
-
+pub fn make_payout_dropdown<'a>(components: &'a mut CreateComponents, game: &Game, locale: Locale) -> &'a mut CreateComponents {
+    if game.is_simulated() {
+        return make_line_dropdown(components, game, locale)
+    }
     components.create_action_row(|action_row| {
         action_row.create_select_menu(|menu| {
-            menu.custom_id(format!("minicact_payouts_{:02}__", game.index())) // double underscore at the end so the index is the same number of characters from the end.
-                .placeholder("Enter your payout!")
+            menu.custom_id(CustomId::Payout { nonce: game.nonce(), index: game.index() as u8 })
+                .placeholder(tr(locale, "menu.payout"))
                 .options(|options| {
                     for i in 1..17 {
                         options.create_option(|option|{
                             option
-                                .label(format!("{}{}", if chosen_i == i {">"} else {""}, PAYOUT_VALUES[i]))
+                                .label(PAYOUT_VALUES[i])
                                 .value(PAYOUT_VALUES[i])
                         });
                     }
@@ -128,28 +161,149 @@ pub fn make_payout_dropdown<'a>(components: &'a mut CreateComponents, game: &Gam
     })
 }
 
-pub fn make_reset_bar<'a>(components: &'a mut CreateComponents, game: &Game) -> &'a mut CreateComponents {
+// Simulated games already know what every line pays, so the player just picks one.
+fn make_line_dropdown<'a>(components: &'a mut CreateComponents, game: &Game, locale: Locale) -> &'a mut CreateComponents {
+    make_line_menu(components, CustomId::Line { nonce: game.nonce(), index: game.index() as u8 }, locale)
+}
+
+fn make_line_menu(components: &mut CreateComponents, custom_id: CustomId, locale: Locale) -> &mut CreateComponents {
+    components.create_action_row(|action_row| {
+        action_row.create_select_menu(|menu| {
+            menu.custom_id(custom_id)
+                .placeholder(tr(locale, "menu.line"))
+                .options(|options| {
+                    for i in 0..8 {
+                        options.create_option(|option|{
+                            option
+                                .label(line_name(locale, i))
+                                .value(i)
+                        });
+                    }
+                    options
+                })
+        })
+    })
+}
+
+// Only shown right after a payout, so players can record the whole board if they want.
+// Once they have, it lets them replay the ticket instead.
+pub fn make_board_row<'a>(components: &'a mut CreateComponents, game: &Game, locale: Locale) -> &'a mut CreateComponents {
+    match game.last_finished_ticket() {
+        Some(_) if game.can_reveal_board() => components.create_action_row(|action_row| {
+            make_button(action_row,
+                CustomId::RevealBoard { nonce: game.nonce(), index: game.index() as u8 },
+                ButtonStyle::Secondary,
+                Some("🔍"),
+                Some(format!(" {}", tr(locale, "button.reveal_board")).as_str()),
+                false)
+        }),
+        Some(t) if game.full_board(t).is_some() => components.create_action_row(|action_row| {
+            make_button(action_row,
+                CustomId::Replay { nonce: game.nonce(), ticket: t as u8 },
+                ButtonStyle::Secondary,
+                Some("🔁"),
+                Some(format!(" {}", tr(locale, "button.replay")).as_str()),
+                false)
+        }),
+        _ => components
+    }
+}
+
+// Draws a board as a 3x3 grid of emoji for the message content.
+// `highlight` is a position to mark with ❔, i.e. the one we're asking about.
+pub fn make_board_grid(board: &[u8; 9], highlight: Option<usize>, accessible: bool, locale: Locale) -> String {
+    if accessible {
+        return describe_board(board, highlight, locale)
+    }
+    let mut output = String::new();
+    for (i, &n) in board.iter().enumerate() {
+        output.push_str(match n {
+            _ if Some(i) == highlight => "❔",
+            255 => "🟡",
+            _ => NUMBER_EMOJI[n as usize]
+        });
+        if i % 3 == 2 {
+            output.push('\n');
+        }
+    }
+    output
+}
+
+pub fn make_reset_bar<'a>(components: &'a mut CreateComponents, game: &Game, accessible: bool, locale: Locale) -> &'a mut CreateComponents {
     let action = game.last_action();
+    let nonce = game.nonce();
     components.create_action_row(|action_row| {
         make_button(action_row, 
-            "minicact_undo", 
+            CustomId::Undo { nonce }, 
             ButtonStyle::Primary, 
             Some("↩"), 
-            None, 
-            matches!(action, Start));
-        match action {
-            ChoosePosition(pos) => make_button(action_row, "minicact_last_input", ButtonStyle::Secondary, Some(POSITION_EMOJI[pos as usize]), None, true),
-            RevealNumber(num) => make_button(action_row, "minicact_last_input", ButtonStyle::Secondary, Some(NUMBER_EMOJI[num as usize]), None, true),
-            EnterPayout(p) => {
-                if let Done = game.next_action() {  // Hey look, the user just took their last action!! Enable the button, which will confirm that they are done.
-                    make_button(action_row, "minicact_last_input", ButtonStyle::Success, None, Some(p.to_string().as_str()), false)
-                } else {
-                    make_button(action_row, "minicact_last_input", ButtonStyle::Secondary, None, Some(p.to_string().as_str()), true)
-                }
-                
-            },
-            _ => make_button(action_row, "minicact_last_input", ButtonStyle::Secondary, None, None, true)
-        };
-        make_button(action_row, "minicact_reset", ButtonStyle::Primary, Some("🔄"), None, false)
+            accessible.then(|| format!(" {}", tr(locale, "button.undo"))).as_deref(), 
+            !game.can_undo());
+        if let Done = game.next_action() {  // Hey look, the user just took their last action!! Enable the button, which will confirm that they are done.
+            make_button(action_row, CustomId::LastInput { nonce }, ButtonStyle::Success, None, Some(game.payout(2).to_string().as_str()), false);
+        } else if accessible {
+            let label = match action {
+                ChoosePosition(pos) => trf(locale, "button.last", &[&position_name(locale, pos as usize)]),
+                RevealNumber(num) | RevealBoard(num) => trf(locale, "button.last", &[&(num + 1)]),
+                EnterPayout(p) => trf(locale, "button.last", &[&format!("{} MGP", p)]),
+                _ => tr(locale, "button.nothing_yet").to_string()
+            };
+            make_button(action_row, CustomId::LastInput { nonce }, ButtonStyle::Secondary, None, Some(label.as_str()), true);
+        } else {
+            match action {
+                ChoosePosition(pos) => make_button(action_row, CustomId::LastInput { nonce }, ButtonStyle::Secondary, Some(POSITION_EMOJI[pos as usize]), None, true),
+                RevealNumber(num) | RevealBoard(num) => make_button(action_row, CustomId::LastInput { nonce }, ButtonStyle::Secondary, Some(NUMBER_EMOJI[num as usize]), None, true),
+                EnterPayout(p) => make_button(action_row, CustomId::LastInput { nonce }, ButtonStyle::Secondary, None, Some(p.to_string().as_str()), true),
+                _ => make_button(action_row, CustomId::LastInput { nonce }, ButtonStyle::Secondary, None, None, true)
+            };
+        }
+        // Daily puzzles can't be reset either, for the same reason they can't be undone.
+        make_button(action_row, CustomId::Reset { nonce }, ButtonStyle::Primary, Some("🔄"), accessible.then(|| format!(" {}", tr(locale, "button.reset"))).as_deref(), game.mode() == GameMode::Daily)
     })
-}
\ No newline at end of file
+}
+// Like make_game_rows, but there's no recommendation to show until the player answers.
+// Once they have, `grade` shows the best answer in green and theirs in red (if they're different), and everything is disabled.
+pub fn make_quiz_rows<'a>(components: &'a mut CreateComponents, quiz: &Quiz, grade: Option<&Grade>, accessible: bool, locale: Locale) -> &'a mut CreateComponents {
+    let board = quiz.board();
+    let nonce = quiz.nonce();
+    let line_question = quiz.is_line_question();
+    for j in 0..3 {
+        components.create_action_row(|action_row| {
+            for i in (3*j)..(3*j+3) {
+                let style = match grade {
+                    Some(grade) if line_question && POSITION_LINE_TABLE[grade.best][i] => ButtonStyle::Success,
+                    Some(grade) if !line_question && grade.best == i => ButtonStyle::Success,
+                    Some(grade) if !line_question && grade.choice == i => ButtonStyle::Danger,
+                    _ => ButtonStyle::Primary
+                };
+                let emoji = match board[i] {
+                    255 => "🟡",
+                    n => NUMBER_EMOJI[n as usize]
+                };
+                let label = accessible.then(|| {
+                    let note = match style {
+                        ButtonStyle::Success if line_question => Some("note.best_line"),
+                        ButtonStyle::Success => Some("note.best"),
+                        ButtonStyle::Danger => Some("note.your_pick"),
+                        _ => None
+                    };
+                    tile_label(i, (board[i] != 255).then_some(board[i]), note, locale)
+                });
+                make_button(action_row,
+                    CustomId::QuizTile { nonce, position: i as u8 },
+                    style,
+                    (!accessible).then_some(emoji),
+                    label.as_deref(),
+                    grade.is_some() || line_question || board[i] != 255);
+            }
+            action_row
+        });
+    }
+    match grade {
+        None if line_question => make_line_menu(components, CustomId::QuizLine { nonce }, locale),
+        None => components,
+        Some(_) => components.create_action_row(|action_row| {
+            make_button(action_row, CustomId::QuizNext, ButtonStyle::Primary, Some("➡"), Some(format!(" {}", tr(locale, "button.next_question")).as_str()), false)
+        })
+    }
+}
@@ -0,0 +1,214 @@
+use crate::logging;
+use crate::settings::settings;
+use crate::locale::Locale;
+use super::recommendations::parse_data;
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serenity::model::id::UserId;
+use serenity::prelude::*;
+
+use lazy_static::lazy_static;
+
+// Everything a user can change about how the bot treats them. Anyone who never changed anything just gets the default.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Preferences {
+    pub objective: Objective,
+    // In percent, so 150 means every MGP amount is shown 1.5x bigger. The percentiles still come from the unmultiplied payouts.
+    pub multiplier: u16,
+    pub graph: GraphStyle,
+    // None follows the server's language, then Discord's.
+    pub locale: Option<Locale>,
+    pub accessible: bool,
+    // Posts the results as soon as a game is finished, instead of waiting for the button.
+    pub auto_announce: bool,
+    // Games, quizzes and answers show up for the whole channel instead of just the user.
+    pub public: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            objective: Objective::Average,
+            multiplier: 100,
+            graph: GraphStyle::Text,
+            locale: None,
+            accessible: false,
+            auto_announce: false,
+            public: false,
+        }
+    }
+}
+
+impl Preferences {
+    pub fn image(&self) -> bool {
+        self.graph == GraphStyle::Image
+    }
+
+    pub fn scale(&self, mgp: f64) -> f64 {
+        mgp * self.multiplier as f64 / 100.
+    }
+
+    // The daily puzzle is always private, otherwise everyone else in the channel could see the tickets before playing.
+    pub fn ephemeral(&self, daily: bool) -> bool {
+        !self.public || daily
+    }
+}
+
+// What the recommendations try to get the most of.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Objective {
+    Average,
+    Jackpot,
+}
+
+impl Objective {
+    pub const ALL: [Objective; 2] = [Objective::Average, Objective::Jackpot];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Objective::Average => "average",
+            Objective::Jackpot => "jackpot",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Objective> {
+        Objective::ALL.into_iter().find(|objective| objective.code() == code)
+    }
+
+    // Higher is better. Ties in the first number are broken by the average, so Jackpot still plays sensibly when it can't tell.
+    pub fn score(&self, data: &[u32; 16]) -> (f64, f64) {
+        let (mean, p_data) = parse_data(*data);
+        match self {
+            Objective::Average => (mean, 0.),
+            // The distributions leave out NoPayout, so 10000 MGP is the last one.
+            Objective::Jackpot => (p_data[15], mean),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum GraphStyle {
+    Text,
+    Image,
+}
+
+impl GraphStyle {
+    pub const ALL: [GraphStyle; 2] = [GraphStyle::Text, GraphStyle::Image];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            GraphStyle::Text => "text",
+            GraphStyle::Image => "image",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<GraphStyle> {
+        GraphStyle::ALL.into_iter().find(|graph| graph.code() == code)
+    }
+}
+
+// One line of the preferences file, minus the user. Every field is written out, so the file is easy to read and edit by hand.
+impl Display for Preferences {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            fmt,
+            "objective={} multiplier={} graph={} locale={} accessible={} announce={} public={}",
+            self.objective.code(),
+            self.multiplier,
+            self.graph.code(),
+            self.locale.map_or("auto", |locale| locale.code()),
+            self.accessible,
+            self.auto_announce,
+            self.public
+        )
+    }
+}
+
+// Fields that are missing keep their default, and ones this version doesn't know about are skipped,
+// so the file keeps working when settings get added or removed.
+impl FromStr for Preferences {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut preferences = Preferences::default();
+        for field in s.split_whitespace() {
+            let (key, value) = field.split_once('=').ok_or("preference is missing an =")?;
+            match key {
+                "objective" => preferences.objective = Objective::from_code(value).ok_or("unknown objective")?,
+                "multiplier" => preferences.multiplier = value.parse().ok().filter(|x| (MIN_MULTIPLIER..=MAX_MULTIPLIER).contains(x)).ok_or("multiplier failed to parse")?,
+                "graph" => preferences.graph = GraphStyle::from_code(value).ok_or("unknown graph style")?,
+                "locale" => preferences.locale = match value {
+                    "auto" => None,
+                    code => Some(Locale::from_code(code).ok_or("unknown locale")?),
+                },
+                "accessible" => preferences.accessible = value.parse().map_err(|_| "accessible failed to parse")?,
+                "announce" => preferences.auto_announce = value.parse().map_err(|_| "announce failed to parse")?,
+                "public" => preferences.public = value.parse().map_err(|_| "public failed to parse")?,
+                _ => (),
+            }
+        }
+        Ok(preferences)
+    }
+}
+
+// The range /minicact_settings allows, in percent.
+pub const MIN_MULTIPLIER: u16 = 100;
+pub const MAX_MULTIPLIER: u16 = 1000;
+
+// Only users who changed something are in here.
+lazy_static! {
+    static ref PREFERENCES: Mutex<HashMap<UserId, Preferences>> = Mutex::new(HashMap::new());
+}
+
+pub async fn user_preferences(user: UserId) -> Preferences {
+    PREFERENCES.lock().await.get(&user).copied().unwrap_or_default()
+}
+
+// Changes someone's preferences and saves them right away, returning what they ended up as.
+pub async fn update_preferences(user: UserId, change: impl FnOnce(&mut Preferences)) -> Preferences {
+    let path = settings().preferences_file.clone();
+    let mut all_preferences = PREFERENCES.lock().await;
+    let mut preferences = all_preferences.get(&user).copied().unwrap_or_default();
+    change(&mut preferences);
+    if preferences == Preferences::default() {
+        all_preferences.remove(&user);
+    } else {
+        all_preferences.insert(user, preferences);
+    }
+    let contents = all_preferences.iter()
+        .map(|(user, preferences)| format!("{} {}\n", user, preferences))
+        .collect::<String>();
+    // Written somewhere else first, so a crash halfway through can't leave a cut off file behind.
+    // The lock is still held, so two changes at once can't save out of order.
+    let temp_path = format!("{}.tmp", path);
+    if let Err(why) = std::fs::write(&temp_path, contents).and_then(|_| std::fs::rename(&temp_path, &path)) {
+        error!("Failed to save preferences to {}: {}", path, why);
+    }
+    preferences
+}
+
+// Called once at startup. A missing file just means nobody has changed anything yet.
+pub async fn load_preferences() {
+    let path = settings().preferences_file.clone();
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(why) => {
+            info!("No preferences loaded from {}: {}", path, why);
+            return
+        }
+    };
+    let mut all_preferences = PREFERENCES.lock().await;
+    // Lines go by their number in the logs, since the user's ID is right there in them.
+    for (number, line) in contents.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let (user, preferences) = line.split_once(' ').unwrap_or((line, ""));
+        match (user.parse::<u64>(), preferences.parse::<Preferences>()) {
+            (Ok(user), Ok(preferences)) => { all_preferences.insert(UserId(user), preferences); },
+            (Err(_), _) => warn!("Skipping preferences line {} with a bad user", number + 1),
+            (Ok(user), Err(why)) => warn!("Skipping preferences for user {}: {}", logging::user_id(UserId(user)), why),
+        }
+    }
+    info!("Loaded preferences for {} users.", all_preferences.len());
+}
@@ -0,0 +1,160 @@
+use super::game::board::Board;
+use super::game::computations::*;
+use super::game::{new_nonce, HiddenTicket};
+use crate::locale::{format_number, tr, trf, Locale};
+use super::generate_components::{line_name_inline, position_name, POSITION_EMOJI};
+use super::recommendations::parse_data;
+
+use std::collections::HashMap;
+
+use serenity::model::id::UserId;
+use serenity::prelude::*;
+
+use lazy_static::lazy_static;
+use rand::seq::IteratorRandom;
+use rand::Rng;
+use smallset::SmallSet;
+
+// The question each user is currently looking at. It gets removed once they answer, so an answer can't be counted twice.
+lazy_static! {
+    pub static ref ACTIVE_QUIZZES: Mutex<HashMap<UserId, Quiz>> = Mutex::new(HashMap::new());
+}
+
+// Only kept in memory, so everyone's score starts over when the bot restarts.
+lazy_static! {
+    pub static ref QUIZ_SCORES: Mutex<HashMap<UserId, QuizScore>> = Mutex::new(HashMap::new());
+}
+
+// A single question: a ticket with some tiles already uncovered.
+// With fewer than 4 uncovered the player picks a tile, with exactly 4 they pick a line.
+pub struct Quiz {
+    // Same idea as Game's nonce, so pressing an old question doesn't answer the new one.
+    nonce: u32,
+    board: [u8; 9],
+}
+
+// How the player's answer compared to the solver's.
+pub struct Grade {
+    pub choice: usize,
+    pub choice_ev: f64,
+    pub best: usize,
+    pub best_ev: f64,
+}
+
+impl Grade {
+    // Ties happen (mostly on symmetric boards), and any of the tied answers is just as good.
+    pub fn is_correct(&self) -> bool {
+        self.best_ev - self.choice_ev < 1e-9
+    }
+
+    pub fn loss(&self) -> f64 {
+        (self.best_ev - self.choice_ev).max(0.)
+    }
+}
+
+#[derive(Default)]
+pub struct QuizScore {
+    pub answered: u32,
+    pub correct: u32,
+    pub streak: u32,
+    pub best_streak: u32,
+    // Total expected MGP given up across every wrong answer.
+    pub lost: f64,
+}
+
+impl QuizScore {
+    pub fn record(&mut self, grade: &Grade) {
+        self.answered += 1;
+        if grade.is_correct() {
+            self.correct += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+            self.lost += grade.loss();
+        }
+    }
+
+    pub fn describe(&self, locale: Locale) -> String {
+        trf(locale, "quiz.score", &[&self.correct, &self.answered, &self.streak, &self.best_streak, &format_number(locale, self.lost, 2)])
+    }
+}
+
+impl Quiz {
+    pub fn random<R: Rng>(rng: &mut R) -> Quiz {
+        let hidden = HiddenTicket::random(rng);
+        let mut board = [255; 9];
+        board[hidden.first() as usize] = hidden.board()[hidden.first() as usize];
+        // Anywhere from a fresh ticket to choosing a line.
+        let extra = rng.gen_range(0..4);
+        for i in (0..9).filter(|&i| i != hidden.first() as usize).choose_multiple(rng, extra) {
+            board[i] = hidden.board()[i];
+        }
+        Quiz { nonce: new_nonce(), board }
+    }
+
+    pub fn nonce(&self) -> u32 {
+        self.nonce
+    }
+
+    pub fn board(&self) -> [u8; 9] {
+        self.board
+    }
+
+    pub fn is_line_question(&self) -> bool {
+        self.board.iter().filter(|&&x| x != 255).count() == 4
+    }
+
+    fn as_board(&self) -> Board {
+        let mut unused_nums: SmallSet<[u8; 9]> = (0..9).collect();
+        for x in self.board.iter() {
+            unused_nums.remove(x);
+        }
+        Board { state: self.board, unused_nums }
+    }
+
+    // `choice` is a position for tile questions and a line for line questions.
+    // Returns None if that isn't something the player could have picked.
+    pub fn grade(&self, choice: usize) -> Option<Grade> {
+        let mut board = self.as_board();
+        let evs: Vec<Option<f64>> = if self.is_line_question() {
+            compute_best_line_rec(&mut board).iter().map(|&data| Some(parse_data(data).0)).collect()
+        } else {
+            compute_uncover_dists(&mut board).iter().map(|data| data.map(|data| parse_data(data).0)).collect()
+        };
+        let choice_ev = (*evs.get(choice)?)?;
+        let (best, best_ev) = evs.iter().enumerate()
+            .filter_map(|(i, ev)| ev.map(|ev| (i, ev)))
+            .fold((choice, choice_ev), |(max_i, max), (i, ev)| if ev > max { (i, ev) } else { (max_i, max) });
+        Some(Grade { choice, choice_ev, best, best_ev })
+    }
+
+    pub fn question(&self, score: Option<&QuizScore>, locale: Locale) -> String {
+        let prompt = tr(locale, if self.is_line_question() { "quiz.line_question" } else { "quiz.tile_question" });
+        match score {
+            Some(score) => format!("{}\n{}", score.describe(locale), prompt),
+            None => prompt.to_string(),
+        }
+    }
+
+    pub fn answer(&self, grade: &Grade, score: &QuizScore, accessible: bool, locale: Locale) -> String {
+        let name = |i: usize| match (self.is_line_question(), accessible) {
+            (true, _) => trf(locale, "the_line", &[&line_name_inline(locale, i)]),
+            (false, true) => trf(locale, "the_tile", &[&position_name(locale, i).to_lowercase()]),
+            (false, false) => trf(locale, "the_tile", &[&POSITION_EMOJI[i]]),
+        };
+        let mut output = if grade.is_correct() {
+            trf(locale, "quiz.correct", &[&name(grade.choice), &format_number(locale, grade.choice_ev, 2)])
+        } else {
+            trf(locale, "quiz.wrong", &[
+                &name(grade.choice),
+                &format_number(locale, grade.choice_ev, 2),
+                &name(grade.best),
+                &format_number(locale, grade.best_ev, 2),
+                &format_number(locale, grade.loss(), 2)
+            ])
+        };
+        output.push_str(format!("\n{}", score.describe(locale)).as_str());
+        output
+    }
+}
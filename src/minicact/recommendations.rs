@@ -1,38 +1,159 @@
+use crate::locale::{format_number, tr, trf, Locale};
 use super::game::computations::*;
 use super::game::payout::PAYOUT_VALUES;
+use super::game::board::Board;
+use super::game::payout::Payout;
 use super::game::*;
+use super::generate_components::{line_name, line_name_inline, make_board_grid, position_name, POSITION_EMOJI};
+use super::accessibility::make_table;
+use super::preferences::{Objective, Preferences};
+use super::render::{render_recommendation, Highlight};
 
-// These functions both work the same. They calculate the best move, and then make the message content.
+use serenity::prelude::*;
 
-pub fn recommend_position(game: &Game) -> (usize, String) {
+// These functions both work the same. They calculate the best move for the user's objective, and then make the message content.
+// With an image graph, the graph gets drawn as a picture instead, which goes with the message as an attachment.
+// In accessible mode, the recommendation is written out, since the buttons only show it by color, and the graph is a plain list.
+
+// Async since anything but the average isn't precomputed, and early in a ticket that search takes long enough to hold up everyone else.
+// So don't hold ACTIVE_GAMES while waiting on this.
+pub async fn recommend_position(game: &Game, preferences: &Preferences, locale: Locale) -> Result<(usize, String, Option<Vec<u8>>), SerenityError> {
+    let (mut as_board, preferences) = (game.as_board(), *preferences);
+    tokio::task::spawn_blocking(move || {
+        let (i, data) = match preferences.objective {
+            // The precomputed boards already have the answer for this one.
+            Objective::Average => compute_best_uncover(&mut as_board),
+            objective => compute_best_uncover_by(&mut as_board, &|data| objective.score(data)),
+        };
+        let (content, png) = describe_recommendation(&as_board.state, Highlight::Tile(i), data, &preferences, locale);
+        (i, content, png)
+    }).await.map_err(|_| SerenityError::Other("Recommendation task panicked??"))
+}
+
+pub fn recommend_line(game: &Game, preferences: &Preferences, locale: Locale) -> (usize, String, Option<Vec<u8>>) {
     let mut as_board = game.as_board();
-    let (i, data) = compute_best_uncover(&mut as_board);
-    let (expected_value, p_data) = parse_data(data);
-    (
-        i,
-        format!(
-            "{} Average Payout: {:.2} MGP",
-            make_graph(p_data),
-            expected_value
-        ),
-    )
+    let (i, data) = match preferences.objective {
+        Objective::Average => compute_best_line(&mut as_board),
+        objective => compute_best_line_by(&mut as_board, &|data| objective.score(data)),
+    };
+    let (content, png) = describe_recommendation(&as_board.state, Highlight::Line(i), data, preferences, locale);
+    (i, content, png)
 }
 
-pub fn recommend_line(game: &Game) -> (usize, String) {
-    let (i, data) = compute_best_line(&mut game.as_board());
+fn describe_recommendation(board: &[u8; 9], highlight: Highlight, data: [u32; 16], preferences: &Preferences, locale: Locale) -> (String, Option<Vec<u8>>) {
     let (expected_value, p_data) = parse_data(data);
-    (
-        i,
+    let advice = match highlight {
+        Highlight::Tile(i) => trf(locale, "rec.uncover", &[&position_name(locale, i).to_lowercase()]),
+        Highlight::Line(i) => trf(locale, "rec.pick", &[&line_name_inline(locale, i)]),
+    };
+    // The graph stays in the payouts printed on the ticket, only the average gets multiplied.
+    let mut average = trf(locale, "rec.average", &[&format_number(locale, preferences.scale(expected_value), 2)]);
+    if preferences.objective == Objective::Jackpot {
+        average.push_str(format!("\n{}", trf(locale, "rec.jackpot", &[&format_number(locale, p_data[15] * 100., 2)])).as_str());
+    }
+    let accessible = preferences.accessible;
+    let png = preferences.image().then(|| render_recommendation(board, highlight, p_data)).flatten();
+    // The picture still comes along for anyone who wants both, the table just says the same thing in words.
+    let content = match (accessible, &png) {
+        (true, _) => format!("{}\n{}{}", advice, make_table(p_data, locale), average),
+        (false, Some(_)) => average,
+        (false, None) => format!("{} {}", make_graph(p_data), average),
+    };
+    (content, png)
+}
+
+// What to show on a brand new game. Simulated games already have their first tile, so they can be recommended right away.
+pub async fn recommend_start(game: &Game, preferences: &Preferences, locale: Locale) -> Result<(usize, String, Option<Vec<u8>>), SerenityError> {
+    match game.last_action() {
+        Action::Start => Ok((255, tr(locale, "game.enter_first_tile").to_string(), None)),
+        _ => recommend_position(game, preferences, locale).await,
+    }
+}
+
+// Once the whole board is known, shows what they got and what every other line would have paid.
+pub fn summarize_board(game: &Game, ticket: usize, accessible: bool, locale: Locale) -> Option<String> {
+    let board = game.full_board(ticket)?;
+    let payouts = game.line_payouts(ticket)?;
+    let mut output = format!("{}\n{}", trf(locale, "summary.ticket", &[&(ticket + 1)]), make_board_grid(&board, None, accessible, locale));
+    output.push_str(make_line_list(payouts, game.chosen_line(ticket), accessible, locale).as_str());
+    Some(output)
+}
+
+// Steps back through a finished ticket, comparing what the player did to what the solver would have done at every decision.
+// Only works once the full board is known, since otherwise there's nothing to compare the lines with.
+pub fn make_replay(game: &Game, ticket: usize, accessible: bool, locale: Locale) -> Option<String> {
+    let payouts = game.line_payouts(ticket)?;
+    let chosen_line = game.chosen_line(ticket)?;
+    let (positions, numbers) = game.ticket_reveals(ticket);
+    let mut board = Board {
+        state: [255, 255, 255, 255, 255, 255, 255, 255, 255],
+        unused_nums: (0..9).collect(),
+    };
+    let mut output = format!("{}\n", trf(locale, "replay.title", &[&(ticket + 1)]));
+    let name = |i: usize| if accessible { position_name(locale, i).to_lowercase() } else { POSITION_EMOJI[i].to_string() };
+    for k in 0..4 {
+        // The first tile is revealed by the game, so there's no decision to compare.
+        if k > 0 {
+            let chosen = positions[k] as usize;
+            let dists = compute_uncover_dists(&mut board);
+            let (best, best_data) = compute_best_uncover(&mut board);
+            output.push_str(make_board_grid(&board.state, Some(chosen), accessible, locale).as_str());
+            output.push_str(
+                format!(
+                    "{}\n\n",
+                    trf(locale, "replay.tile", &[
+                        &name(chosen),
+                        &format_number(locale, dists[chosen].map(|data| parse_data(data).0).unwrap_or_default(), 2),
+                        &name(best),
+                        &format_number(locale, parse_data(best_data).0, 2)
+                    ])
+                )
+                .as_str(),
+            );
+        }
+        board.state[positions[k] as usize] = numbers[k];
+        board.unused_nums.remove(&numbers[k]);
+    }
+    let (best_line, best_data) = compute_best_line(&mut board);
+    let line_data = compute_best_line_rec(&mut board);
+    output.push_str(make_board_grid(&board.state, None, accessible, locale).as_str());
+    output.push_str(
         format!(
-            "{} Average Payout: {:.2} MGP",
-            make_graph(p_data),
-            expected_value
-        ),
-    )
+            "{}\n\n{}\n",
+            trf(locale, "replay.line", &[
+                &line_name_inline(locale, chosen_line),
+                &format_number(locale, parse_data(line_data[chosen_line]).0, 2),
+                &line_name_inline(locale, best_line),
+                &format_number(locale, parse_data(best_data).0, 2)
+            ]),
+            tr(locale, "replay.every_line")
+        )
+        .as_str(),
+    );
+    output.push_str(make_line_list(payouts, Some(chosen_line), accessible, locale).as_str());
+    Some(output)
+}
+
+// One line per line, with 👉 next to the one they picked (or written after it, in accessible mode).
+fn make_line_list(payouts: [Payout; 8], chosen_line: Option<usize>, accessible: bool, locale: Locale) -> String {
+    let mut output = String::new();
+    for line in 0..8 {
+        let chosen = chosen_line == Some(line);
+        output.push_str(
+            format!(
+                "{}{}{}\n",
+                if chosen && !accessible { "👉 " } else { "" },
+                trf(locale, "summary.line", &[&line_name(locale, line), &PAYOUT_VALUES[payouts[line] as usize]]),
+                if chosen && accessible { format!(" ({})", tr(locale, "note.your_pick")) } else { String::new() }
+            )
+            .as_str(),
+        );
+    }
+    output
 }
 
 // from an integer distribution, computes the mean payout and the actual probabilities of each payout.
-fn parse_data(data: [u32; 16]) -> (f64, [f64; 16]) {
+pub fn parse_data(data: [u32; 16]) -> (f64, [f64; 16]) {
     let mut n: f64 = 0.;
     let mut total: f64 = 0.;
     for i in 0..16 {
@@ -49,11 +170,11 @@ fn parse_data(data: [u32; 16]) -> (f64, [f64; 16]) {
 }
 
 // Zero-width character in index 0 for p r e c i s i o n
-pub const REMAINDER_BARS: [char; 9] = ['​', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+pub const REMAINDER_BARS: [char; 9] = ['\u{200b}', '▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
 
 // mmm, graphics...
 // makes a unicode bar graph from the payout probabilities.
-fn make_graph(data: [f64; 16]) -> String {
+pub fn make_graph(data: [f64; 16]) -> String {
     let mut output = "```\n".to_string();
     let max_p = data
         .iter()
@@ -89,3 +210,29 @@ fn make_bar(p: &f64, max_p: f64) -> String {
     out_string.push(REMAINDER_BARS[remainder_bar as usize]);
     out_string
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    // The slowest searches are right after the first tile, and every first tile is a corner, an edge or the center once it's simplified.
+    // The limits are far above how long it takes on a normal machine, they're just there to catch a search that blew up.
+    #[test]
+    fn worst_case_search_is_bounded() {
+        let limit = Duration::from_secs(if cfg!(debug_assertions) { 60 } else { 3 });
+        for objective in Objective::ALL.into_iter().filter(|&objective| objective != Objective::Average) {
+            std::thread::scope(|scope| {
+                for position in [0, 1, 4] {
+                    scope.spawn(move || {
+                        let mut board = Board { state: [255; 9], unused_nums: (1..9).collect() };
+                        board.state[position] = 0;
+                        let start = Instant::now();
+                        compute_best_uncover_by(&mut board, &|data| objective.score(data));
+                        assert!(start.elapsed() < limit, "{:?} took {:.2?} with a tile at {}", objective, start.elapsed(), position);
+                    });
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use super::game::payout::{PAYOUT_VALUES, POSITION_LINE_TABLE};
+
+use std::borrow::Cow;
+
+use serenity::builder::CreateInteractionResponseData;
+use serenity::json::{json, Value};
+use serenity::model::channel::AttachmentType;
+
+// What the recommendation is pointing at, as an index into the board or POSITION_LINE_TABLE.
+pub enum Highlight {
+    Tile(usize),
+    Line(usize),
+}
+
+pub const IMAGE_FILENAME: &str = "minicact.png";
+
+type Color = [u8; 3];
+
+// Roughly Discord's dark theme, so it doesn't look like a hole in the chat.
+const BACKGROUND: Color = [49, 51, 56];
+const TEXT: Color = [220, 221, 222];
+const COVERED: Color = [201, 162, 39];
+const UNCOVERED: Color = [235, 235, 235];
+const NUMBER: Color = [30, 31, 34];
+const RECOMMENDED: Color = [35, 165, 90];
+const BAR: Color = [88, 101, 242];
+
+const MARGIN: u32 = 24;
+const TILE: u32 = 72;
+const TILE_GAP: u32 = 8;
+const BOARD: u32 = 3 * TILE + 2 * TILE_GAP;
+// How thick the outline around the recommended line is.
+const OUTLINE: u32 = 6;
+
+const ROW_HEIGHT: u32 = 20;
+const TEXT_SCALE: u32 = 2;
+const HISTOGRAM_LEFT: u32 = BOARD + 2 * MARGIN;
+// Room for "10000" before the bar starts, and "100.0%" after the longest one.
+const BAR_LEFT: u32 = HISTOGRAM_LEFT + 5 * GLYPH_ADVANCE * TEXT_SCALE + 8;
+const BAR_WIDTH: u32 = 200;
+const WIDTH: u32 = BAR_LEFT + BAR_WIDTH + 8 + 6 * GLYPH_ADVANCE * TEXT_SCALE + MARGIN;
+
+// A 5x7 pixel font, one byte per row with the leftmost pixel in the 0b10000 bit.
+// Only what the histogram and board need, anything else is drawn as a space.
+const GLYPH_ADVANCE: u32 = 6;
+const GLYPH_HEIGHT: u32 = 7;
+
+fn glyph(c: char) -> [u8; 7] {
+    match c {
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '%' => [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011],
+        _ => [0; 7],
+    }
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    // RGB, row by row.
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, color: Color) -> Canvas {
+        Canvas { width, height, pixels: color.repeat((width * height) as usize) }
+    }
+
+    // Anything hanging off the edge just gets cut off.
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Color) {
+        for row in y..(y + height).min(self.height) {
+            for col in x..(x + width).min(self.width) {
+                let i = 3 * (row * self.width + col) as usize;
+                self.pixels[i..i + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    fn draw_text(&mut self, x: u32, y: u32, scale: u32, text: &str, color: Color) {
+        for (k, c) in text.chars().enumerate() {
+            let left = x + k as u32 * GLYPH_ADVANCE * scale;
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0b10000 >> col) != 0 {
+                        self.fill_rect(left + col * scale, y + row as u32 * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Option<Vec<u8>> {
+        let mut output = vec![];
+        let mut encoder = png::Encoder::new(&mut output, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().ok()?;
+        writer.write_image_data(&self.pixels).ok()?;
+        writer.finish().ok()?;
+        Some(output)
+    }
+}
+
+fn text_width(text: &str, scale: u32) -> u32 {
+    text.chars().count() as u32 * GLYPH_ADVANCE * scale - scale
+}
+
+// The board on the left, with the recommendation in green, and the same histogram as make_graph on the right.
+// Returns None if the PNG somehow fails to encode, in which case the text graph is better than nothing.
+pub fn render_recommendation(board: &[u8; 9], highlight: Highlight, p_data: [f64; 16]) -> Option<Vec<u8>> {
+    let rows = p_data.iter().filter(|&&p| p > 0.).count() as u32;
+    let height = BOARD.max(rows * ROW_HEIGHT) + 2 * MARGIN;
+    let mut canvas = Canvas::new(WIDTH, height, BACKGROUND);
+    let board_top = (height - BOARD) / 2;
+    for i in 0..9 {
+        let x = MARGIN + (i as u32 % 3) * (TILE + TILE_GAP);
+        let y = board_top + (i as u32 / 3) * (TILE + TILE_GAP);
+        // The recommended line gets drawn as an outline, so you can still see whether its tiles are covered.
+        let inset = match highlight {
+            Highlight::Line(line) if POSITION_LINE_TABLE[line][i] => {
+                canvas.fill_rect(x, y, TILE, TILE, RECOMMENDED);
+                OUTLINE
+            },
+            _ => 0,
+        };
+        let color = match highlight {
+            Highlight::Tile(position) if position == i => RECOMMENDED,
+            _ if board[i] == 255 => COVERED,
+            _ => UNCOVERED,
+        };
+        canvas.fill_rect(x + inset, y + inset, TILE - 2 * inset, TILE - 2 * inset, color);
+        if board[i] != 255 {
+            let number = (board[i] + 1).to_string();
+            let scale = 6;
+            canvas.draw_text(
+                x + (TILE - text_width(&number, scale)) / 2,
+                y + (TILE - GLYPH_HEIGHT * scale) / 2,
+                scale,
+                &number,
+                NUMBER,
+            );
+        }
+    }
+    let max_p = p_data.iter().fold(0., |max, &val| if val > max { val } else { max });
+    let mut y = (height - rows * ROW_HEIGHT) / 2;
+    let text_top = (ROW_HEIGHT - GLYPH_HEIGHT * TEXT_SCALE) / 2;
+    for (i, p) in p_data.iter().enumerate().rev() {
+        if *p > 0. {
+            let label = PAYOUT_VALUES[i + 1].to_string();
+            canvas.draw_text(BAR_LEFT - 8 - text_width(&label, TEXT_SCALE), y + text_top, TEXT_SCALE, &label, TEXT);
+            // Always at least a sliver, so tiny chances don't look like they're missing.
+            let bar = ((p / max_p * BAR_WIDTH as f64).round() as u32).max(1);
+            canvas.fill_rect(BAR_LEFT, y + 2, bar, ROW_HEIGHT - 4, BAR);
+            canvas.draw_text(BAR_LEFT + bar + 8, y + text_top, TEXT_SCALE, &format!("{:.1}%", p * 100.), TEXT);
+            y += ROW_HEIGHT;
+        }
+    }
+    canvas.encode()
+}
+
+// Puts the image on the response in place of any the message already had.
+// Editing a message keeps only the attachments it's given in this list, and that includes new uploads, so the image has to be listed too.
+// An empty list clears them all out. That means this should be called on every response in image mode, even the ones without an image, so an old one doesn't hang around.
+// serenity 0.11 has no way to set this list on an interaction response, so it goes straight into the JSON.
+pub fn attach_image<'a, 'b>(message: &'a mut CreateInteractionResponseData<'b>, image: Option<Vec<u8>>) -> &'a mut CreateInteractionResponseData<'b> {
+    let attachments = match image {
+        // The id is the upload's place among the files sent along, and there's only ever the one.
+        Some(_) => vec![json!({ "id": 0, "filename": IMAGE_FILENAME })],
+        None => vec![],
+    };
+    message.0.insert("attachments", Value::Array(attachments));
+    if let Some(image) = image {
+        message.add_file(AttachmentType::Bytes { data: Cow::from(image), filename: IMAGE_FILENAME.to_string() });
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Editing a message drops any attachment that isn't listed, including the one being uploaded.
+    #[test]
+    fn new_image_is_kept() {
+        let mut message = CreateInteractionResponseData::default();
+        attach_image(&mut message, Some(vec![1, 2, 3]));
+        assert_eq!(message.0.get("attachments"), Some(&json!([{ "id": 0, "filename": IMAGE_FILENAME }])));
+        let mut message = CreateInteractionResponseData::default();
+        attach_image(&mut message, None);
+        assert_eq!(message.0.get("attachments"), Some(&json!([])));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::logging;
+use crate::settings::settings;
+use crate::locale::{tr, Locale};
+use super::daily::mark_daily_started;
+use super::game::*;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serenity::http::Http;
+use serenity::json::{JsonMap, Value};
+use serenity::model::id::UserId;
+use serenity::prelude::*;
+
+use lazy_static::lazy_static;
+
+// Everything needed to keep games going across a restart: saving ACTIVE_GAMES on the way down and loading it on the way back up,
+// and telling anyone in the middle of a game what's going on.
+
+// The last message each player's game was shown in, by the interaction that showed it.
+// Interaction tokens are the only way to edit ephemeral messages, and they only last 15 minutes.
+struct LiveMessage {
+    token: String,
+    locale: Locale,
+    since: Instant,
+}
+
+lazy_static! {
+    static ref LIVE_MESSAGES: Mutex<HashMap<UserId, LiveMessage>> = Mutex::new(HashMap::new());
+}
+
+// A little under Discord's 15 minutes, so a token doesn't run out between checking it and using it.
+const TOKEN_LIFETIME: Duration = Duration::from_secs(14 * 60);
+
+// Players are notified all at once, but a slow Discord shouldn't hold up the shutdown forever.
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Called whenever a player's game gets shown to them.
+pub async fn track_message(user: UserId, token: &str, locale: Locale) {
+    LIVE_MESSAGES.lock().await.insert(user, LiveMessage { token: token.to_string(), locale, since: Instant::now() });
+}
+
+// So a drain can tell when everyone is done.
+pub async fn active_game_count() -> usize {
+    ACTIVE_GAMES.lock().await.len()
+}
+
+// Returns how many games were saved. Called once the shards are shut down, so nobody can change their game after it's written.
+pub async fn save_active_games() -> Result<usize, std::io::Error> {
+    let path = settings().active_games_file.clone();
+    let active_games = ACTIVE_GAMES.lock().await;
+    let contents = active_games.iter()
+        .map(|(user, game)| format!("{} {}\n", user, game.to_saved()))
+        .collect::<String>();
+    // Same as the preferences file, so a crash halfway through can't leave a cut off file behind.
+    let temp_path = format!("{}.tmp", path);
+    std::fs::write(&temp_path, contents).and_then(|_| std::fs::rename(&temp_path, &path))?;
+    Ok(active_games.len())
+}
+
+// Called once at startup. The file is removed once it's read, otherwise a crash later on would bring back games people have since finished.
+pub async fn load_active_games() {
+    let path = settings().active_games_file.clone();
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(why) => {
+            info!("No active games loaded from {}: {}", path, why);
+            return
+        }
+    };
+    let mut active_games = ACTIVE_GAMES.lock().await;
+    // Lines go by their number in the logs, since the user's ID is right there in them.
+    for (number, line) in contents.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let (user, game) = line.split_once(' ').unwrap_or((line, ""));
+        match (user.parse::<u64>(), Game::from_saved(game)) {
+            (Ok(user), Ok(game)) => {
+                mark_daily_started(&game, UserId(user)).await;
+                active_games.insert(UserId(user), game);
+            },
+            (Err(_), _) => warn!("Skipping active game line {} with a bad user", number + 1),
+            (Ok(user), Err(why)) => warn!("Skipping active game for user {}: {}", logging::user_id(UserId(user)), why),
+        }
+    }
+    if let Err(why) = std::fs::remove_file(&path) {
+        error!("Failed to remove {} after loading it, the same games will be loaded again next time: {}", path, why);
+    }
+    info!("Loaded {} active games.", active_games.len());
+}
+
+// Adds a note to the bottom of every game that's still going, as long as its message can still be edited.
+// The buttons are left alone, since the game is saved and they'll work again once the bot is back.
+// Returns how many were notified and how many couldn't be.
+pub async fn notify_players(http: Arc<Http>, restarting: bool) -> (usize, usize) {
+    let players = ACTIVE_GAMES.lock().await.keys().copied().collect::<Vec<UserId>>();
+    let mut live_messages = LIVE_MESSAGES.lock().await;
+    let tasks = players.iter()
+        .filter_map(|user| live_messages.remove(user))
+        .filter(|message| message.since.elapsed() < TOKEN_LIFETIME)
+        .map(|message| {
+            let http = http.clone();
+            let notice = tr(message.locale, if restarting { "shutdown.notice_restart" } else { "shutdown.notice" });
+            tokio::spawn(async move {
+                let original = http.get_original_interaction_response(&message.token).await?;
+                let mut map = JsonMap::new();
+                map.insert("content".to_string(), Value::from(format!("{}\n\n{}", original.content, notice)));
+                http.edit_original_interaction_response(&message.token, &Value::from(map)).await
+            })
+        })
+        .collect::<Vec<_>>();
+    let total = tasks.len();
+    let mut notified = 0;
+    let all_tasks = async {
+        for task in tasks {
+            match task.await {
+                Ok(Ok(_)) => notified += 1,
+                Ok(Err(why)) => debug!("Failed to notify a player about the shutdown: {}", why),
+                Err(why) => debug!("Notifying a player about the shutdown panicked: {}", why),
+            }
+        }
+    };
+    if tokio::time::timeout(NOTIFY_TIMEOUT, all_tasks).await.is_err() {
+        warn!("Gave up on notifying players after {:?}.", NOTIFY_TIMEOUT);
+    }
+    (notified, total - notified)
+}
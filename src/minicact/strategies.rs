@@ -0,0 +1,129 @@
+use crate::locale::{trf, Locale};
+use super::game::board::Board;
+use super::game::computations::*;
+use super::recommendations::parse_data;
+
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::{Rng, SeedableRng};
+
+// Anything that knows how to play a ticket. Both of these get a board with the same meaning as everywhere else, i.e. 255 is covered.
+pub trait Strategy {
+    // Which tile to uncover next. Only called with 1 to 3 tiles uncovered, and has to return a covered tile.
+    fn choose_tile(&mut self, board: &Board) -> usize;
+    // Which line to pick once 4 tiles are uncovered, as an index into POSITION_LINE_TABLE.
+    fn choose_line(&mut self, board: &Board) -> usize;
+    // Whether the same board always gets the same choice. Only these can be evaluated exactly.
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+// What the bot recommends.
+pub struct Optimal;
+
+impl Strategy for Optimal {
+    fn choose_tile(&mut self, board: &Board) -> usize {
+        compute_best_uncover(&mut board.clone()).0
+    }
+
+    fn choose_line(&mut self, board: &Board) -> usize {
+        compute_best_line(&mut board.clone()).0
+    }
+}
+
+// Closes its eyes and points.
+pub struct Random {
+    rng: StdRng,
+}
+
+impl Random {
+    pub fn new() -> Random {
+        Random { rng: StdRng::from_entropy() }
+    }
+}
+
+impl Strategy for Random {
+    fn choose_tile(&mut self, board: &Board) -> usize {
+        covered_tiles(board).choose(&mut self.rng).unwrap_or(4)
+    }
+
+    fn choose_line(&mut self, _board: &Board) -> usize {
+        self.rng.gen_range(0..8)
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+// Folk wisdom: scratch the diagonals, then take whichever diagonal looks better.
+pub struct Diagonals;
+
+// Center first, since it's on both.
+const DIAGONAL_TILES: [usize; 5] = [4, 0, 8, 2, 6];
+
+impl Strategy for Diagonals {
+    fn choose_tile(&mut self, board: &Board) -> usize {
+        DIAGONAL_TILES.iter().copied()
+            .chain(covered_tiles(board))
+            .find(|&i| board.state[i] == 255)
+            .unwrap_or(4)
+    }
+
+    fn choose_line(&mut self, board: &Board) -> usize {
+        let data = compute_best_line_rec(&mut board.clone());
+        // 3 and 7 are the diagonals in POSITION_LINE_TABLE.
+        if parse_data(data[3]).0 >= parse_data(data[7]).0 { 3 } else { 7 }
+    }
+}
+
+// A rule someone typed in: uncover the first covered tile in `tiles`, then pick the first line in `lines`.
+// Positions are 1-9 starting from the top left, and lines are 1-8 in the same order as POSITION_LINE_TABLE.
+pub struct Priority {
+    tiles: Vec<usize>,
+    lines: Vec<usize>,
+}
+
+impl Priority {
+    // Parses something like "tiles=5,1,3 lines=4,8". The error is written for whoever typed it in.
+    // Anything left out just falls back to the optimal choice.
+    pub fn parse(rule: &str, locale: Locale) -> Result<Priority, String> {
+        let mut output = Priority { tiles: vec![], lines: vec![] };
+        for part in rule.split_whitespace() {
+            let (key, values) = part.split_once('=').ok_or_else(|| trf(locale, "rule.bad_part", &[&part]))?;
+            let (list, max) = match key {
+                "tiles" => (&mut output.tiles, 9),
+                "lines" => (&mut output.lines, 8),
+                _ => return Err(trf(locale, "rule.bad_key", &[&key])),
+            };
+            for value in values.split(',') {
+                match value.parse::<usize>() {
+                    Ok(n) if (1..=max).contains(&n) => list.push(n - 1),
+                    _ => return Err(trf(locale, "rule.bad_number", &[&value, &max])),
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+impl Strategy for Priority {
+    fn choose_tile(&mut self, board: &Board) -> usize {
+        match self.tiles.iter().find(|&&i| board.state[i] == 255) {
+            Some(&i) => i,
+            None => Optimal.choose_tile(board),
+        }
+    }
+
+    fn choose_line(&mut self, board: &Board) -> usize {
+        match self.lines.first() {
+            Some(&line) => line,
+            None => Optimal.choose_line(board),
+        }
+    }
+}
+
+fn covered_tiles(board: &Board) -> impl Iterator<Item = usize> + '_ {
+    (0..9).filter(|&i| board.state[i] == 255)
+}
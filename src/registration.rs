@@ -0,0 +1,107 @@
+use crate::commands::create_commands;
+use crate::settings::settings;
+
+use std::collections::HashMap;
+
+use serenity::builder::CreateApplicationCommands;
+use serenity::http::Http;
+use serenity::json::Value;
+use serenity::model::application::command::{Command, CommandOption};
+
+// Registers the commands from create_commands, but only where Discord has something different already.
+// Global commands can take a while to show up everywhere, so while working on the bot it's nicer to put them in `test_guilds` instead.
+// `global_commands` decides whether they also go out globally, and defaults to only doing that when there are no test guilds.
+pub async fn register_commands(http: &Http) {
+    let settings = settings();
+    let mut wanted = CreateApplicationCommands::default();
+    create_commands(&mut wanted);
+
+    if settings.global_commands {
+        match Command::get_global_application_commands_with_localizations(http).await {
+            Ok(existing) if up_to_date(&wanted.0, &existing) => info!("Global commands are already up to date."),
+            result => {
+                if let Err(why) = result {
+                    warn!("Failed to get global commands, registering them anyway: {}", why);
+                }
+                Command::set_global_application_commands(http, create_commands)
+                    .await.expect("Failed to set application commands");
+                info!("Registered global commands.");
+            }
+        }
+    }
+
+    for guild in settings.test_guilds.iter() {
+        match guild.get_application_commands_with_localizations(http).await {
+            Ok(existing) if up_to_date(&wanted.0, &existing) => info!("Commands in test guild {} are already up to date.", guild),
+            result => {
+                if let Err(why) = result {
+                    warn!("Failed to get commands in test guild {}, registering them anyway: {}", guild, why);
+                }
+                // A bad guild in the config file shouldn't stop the bot, unlike the global commands failing.
+                match guild.set_application_commands(http, create_commands).await {
+                    Ok(_) => info!("Registered commands in test guild {}.", guild),
+                    Err(why) => error!("Failed to register commands in test guild {}: {}", guild, why),
+                }
+            }
+        }
+    }
+}
+
+// Every command we want is there exactly as we'd register it, and there's nothing extra.
+fn up_to_date(wanted: &[Value], existing: &[Command]) -> bool {
+    wanted.len() == existing.len() && wanted.iter().all(|wanted| {
+        existing.iter().any(|existing| same_command(wanted, existing))
+    })
+}
+
+// Discord sends back a lot more than we registered, so this only compares the parts create_commands can set.
+// Anything we left out counts the same as Discord's default for it.
+fn same_command(wanted: &Value, existing: &Command) -> bool {
+    string(wanted, "name") == existing.name
+        && string(wanted, "description") == existing.description
+        && localizations(wanted, "name_localizations") == existing.name_localizations.clone().unwrap_or_default()
+        && localizations(wanted, "description_localizations") == existing.description_localizations.clone().unwrap_or_default()
+        && wanted.get("default_member_permissions").and_then(Value::as_str).and_then(|bits| bits.parse::<u64>().ok())
+            == existing.default_member_permissions.map(|permissions| permissions.bits())
+        && wanted.get("dm_permission").and_then(Value::as_bool).unwrap_or(true) == existing.dm_permission.unwrap_or(true)
+        && same_options(wanted.get("options"), &existing.options)
+}
+
+fn same_options(wanted: Option<&Value>, existing: &[CommandOption]) -> bool {
+    let wanted = wanted.and_then(Value::as_array).map(|options| options.as_slice()).unwrap_or_default();
+    wanted.len() == existing.len() && wanted.iter().zip(existing).all(|(wanted, existing)| same_option(wanted, existing))
+}
+
+fn same_option(wanted: &Value, existing: &CommandOption) -> bool {
+    let number = |key: &str| wanted.get(key).and_then(Value::as_f64);
+    let choices = wanted.get("choices").and_then(Value::as_array).map(|choices| choices.as_slice()).unwrap_or_default();
+    let channel_types = wanted.get("channel_types").and_then(Value::as_array)
+        .map(|types| types.iter().filter_map(Value::as_u64).collect::<Vec<u64>>())
+        .unwrap_or_default();
+    wanted.get("type").and_then(Value::as_u64) == Some(existing.kind as u64)
+        && string(wanted, "name") == existing.name
+        && string(wanted, "description") == existing.description
+        && localizations(wanted, "name_localizations") == existing.name_localizations.clone().unwrap_or_default()
+        && localizations(wanted, "description_localizations") == existing.description_localizations.clone().unwrap_or_default()
+        && wanted.get("required").and_then(Value::as_bool).unwrap_or(false) == existing.required
+        && number("min_value") == existing.min_value.as_ref().and_then(|value| value.as_f64())
+        && number("max_value") == existing.max_value.as_ref().and_then(|value| value.as_f64())
+        && channel_types == existing.channel_types.iter().map(|kind| *kind as u64).collect::<Vec<u64>>()
+        && choices.len() == existing.choices.len()
+        && choices.iter().zip(&existing.choices).all(|(wanted, existing)| {
+            string(wanted, "name") == existing.name
+                && localizations(wanted, "name_localizations") == existing.name_localizations.clone().unwrap_or_default()
+                && wanted.get("value") == Some(&existing.value)
+        })
+        && same_options(wanted.get("options"), &existing.options)
+}
+
+fn string<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn localizations(value: &Value, key: &str) -> HashMap<String, String> {
+    value.get(key).and_then(Value::as_object)
+        .map(|map| map.iter().filter_map(|(locale, text)| Some((locale.clone(), text.as_str()?.to_string()))).collect())
+        .unwrap_or_default()
+}
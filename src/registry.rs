@@ -0,0 +1,73 @@
+use crate::commands::Permission;
+use crate::{admin, commands, components, minicact};
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+
+use serenity::builder::CreateApplicationCommand;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::prelude::interaction::message_component::MessageComponentInteraction;
+use serenity::prelude::*;
+
+// Every slash command and component the bot knows about, each in one place with whatever runs it.
+// Modules list their own in a COMMANDS or COMPONENTS const, and this just puts them all together.
+
+pub type HandlerFuture = Pin<Box<dyn Future<Output = Result<(), SerenityError>> + Send>>;
+
+pub struct SlashCommand {
+    pub name: &'static str,
+    pub permission: Permission,
+    // Everything but the name, which gets filled in from above along with the permission.
+    pub create: fn(&mut CreateApplicationCommand) -> &mut CreateApplicationCommand,
+    pub handler: fn(Context, ApplicationCommandInteraction) -> HandlerFuture,
+}
+
+// Components are routed by the start of their custom_id, since most of them carry data after it.
+pub struct ComponentRoute {
+    pub prefix: &'static str,
+    pub handler: fn(Context, MessageComponentInteraction) -> HandlerFuture,
+}
+
+pub fn all_commands() -> impl Iterator<Item = &'static SlashCommand> {
+    commands::COMMANDS.iter().chain(admin::COMMANDS).chain(minicact::commands::COMMANDS)
+}
+
+pub fn all_components() -> impl Iterator<Item = &'static ComponentRoute> {
+    components::COMPONENTS.iter().chain(minicact::components::COMPONENTS)
+}
+
+pub fn find_command(name: &str) -> Option<&'static SlashCommand> {
+    all_commands().find(|command| command.name == name)
+}
+
+// The longest prefix wins, so a module can claim a whole prefix and still leave specific ids to someone else.
+pub fn find_component(custom_id: &str) -> Option<&'static ComponentRoute> {
+    all_components()
+        .filter(|route| custom_id.starts_with(route.prefix))
+        .max_by_key(|route| route.prefix.len())
+}
+
+// Called once at startup, so a mistake here shows up right away instead of the first time someone uses the command.
+// Panics, since a command that can never run or two that fight over a name is a bug, not something to carry on with.
+pub fn check_registry() {
+    let mut names = HashSet::new();
+    for command in all_commands() {
+        // Discord's rules for command names.
+        let valid = (1..=32).contains(&command.name.len())
+            && command.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+        assert!(valid, "Command {} has a name Discord won't accept", command.name);
+        assert!(names.insert(command.name), "Command {} is registered more than once, only the first one would ever run", command.name);
+        let mut definition = CreateApplicationCommand::default();
+        (command.create)(&mut definition);
+        assert!(!definition.0.contains_key("name"), "Command {} sets its own name, which would send it to the wrong handler", command.name);
+        assert!(definition.0.get("description").is_some_and(|description| !description.as_str().unwrap_or_default().is_empty()),
+            "Command {} has no description, so Discord would refuse to register it", command.name);
+    }
+    let mut prefixes = HashSet::new();
+    for route in all_components() {
+        assert!(!route.prefix.is_empty(), "A component has an empty prefix, which would catch every custom_id");
+        assert!(prefixes.insert(route.prefix), "Component prefix {} is registered more than once, only the first one would ever run", route.prefix);
+    }
+    info!("Checked {} commands and {} component prefixes.", names.len(), prefixes.len());
+}
@@ -0,0 +1,318 @@
+use crate::locale::{tr, trf, Locale};
+use crate::logging::{self, LogSettings};
+
+use std::env;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use serenity::model::id::{ChannelId, GuildId, UserId};
+
+use config::{Config, ConfigError, File};
+
+use lazy_static::lazy_static;
+
+// Everything from the config file, checked and with the defaults filled in.
+// Anything that needs a setting calls settings(), which is cheap, and gets a snapshot that can't change out from under it.
+// The file can be reloaded while the bot is running (see reload_settings), and a config with anything wrong in it is refused as a whole.
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Settings {
+    pub token: String,
+    // Bot admins, who can use anything anywhere. If nobody is listed, nobody is one.
+    pub admins: Vec<UserId>,
+    pub daily_channels: Vec<ChannelId>,
+    // The hour (UTC) a new daily puzzle goes up.
+    pub daily_hour: i64,
+    pub preferences_file: String,
+    pub guild_config_file: String,
+    pub active_games_file: String,
+    pub board_history_file: String,
+    pub daily_puzzles_file: String,
+    pub test_guilds: Vec<GuildId>,
+    pub global_commands: bool,
+    pub metrics_address: Option<String>,
+    pub shutdown_notify_players: bool,
+    // How long nobody has to use the bot before a drain gives up on the games still going, and how long a drain can take at most.
+    pub shutdown_drain_idle: Duration,
+    pub shutdown_drain_timeout: Duration,
+    pub logging: LogSettings,
+}
+
+// Mini Cactpot resets at 15:00 UTC, so that's when a new puzzle goes up unless the config says otherwise.
+pub const DEFAULT_DAILY_HOUR: i64 = 15;
+const DEFAULT_PREFERENCES_FILE: &str = "preferences.txt";
+const DEFAULT_GUILD_CONFIG_FILE: &str = "guild_config.txt";
+const DEFAULT_ACTIVE_GAMES_FILE: &str = "active_games.txt";
+const DEFAULT_BOARD_HISTORY_FILE: &str = "board_history.txt";
+const DEFAULT_DAILY_PUZZLES_FILE: &str = "daily_puzzles.txt";
+const DEFAULT_DRAIN_IDLE_SECONDS: i64 = 60;
+const DEFAULT_DRAIN_TIMEOUT_SECONDS: i64 = 600;
+
+// The same extensions the config crate looks for.
+const CONFIG_FILES: [&str; 7] = ["config.toml", "config.json", "config.yaml", "config.yml", "config.ini", "config.ron", "config.json5"];
+
+// How often the config file gets checked for changes.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+// None until load_settings is called, which main does first thing.
+// std::sync since it's only ever held long enough to clone the Arc.
+lazy_static! { static ref SETTINGS: RwLock<Option<Arc<Settings>>> = RwLock::new(None); }
+
+pub fn settings() -> Arc<Settings> {
+    SETTINGS.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone().expect("Settings used before they were loaded??")
+}
+
+fn build_config() -> Result<Config, ConfigError> {
+    Config::builder()
+        .add_source(File::with_name("config"))
+        .set_override_option("token", env::var("DISCORD_TOKEN").ok())?
+        .build()
+}
+
+impl Settings {
+    // Every problem at once, so they can all be fixed in one go.
+    fn from_config(config: &Config) -> Result<Settings, Vec<String>> {
+        let mut problems = vec![];
+        let token = match config.get_string("token") {
+            Ok(token) if !token.is_empty() => token,
+            _ => {
+                problems.push("Token not found. Either put it in the `config` file (token = \"token\") or set environment variable DISCORD_TOKEN.".to_string());
+                String::new()
+            }
+        };
+        let admins = ids(config, "admins", &mut problems).into_iter().map(UserId).collect();
+        let daily_channels = ids(config, "daily_channels", &mut problems).into_iter().map(ChannelId).collect();
+        let daily_hour = or_default(config.get_int("daily_hour"), "daily_hour", DEFAULT_DAILY_HOUR, &mut problems);
+        if !(0..24).contains(&daily_hour) {
+            problems.push(format!("daily_hour should be from 0 to 23, not {}", daily_hour));
+        }
+        let mut file = |key: &str, default: &str| {
+            let path = or_default(config.get_string(key), key, default.to_string(), &mut problems);
+            if path.is_empty() {
+                problems.push(format!("{} can't be empty", key));
+            }
+            path
+        };
+        let preferences_file = file("preferences_file", DEFAULT_PREFERENCES_FILE);
+        let guild_config_file = file("guild_config_file", DEFAULT_GUILD_CONFIG_FILE);
+        let active_games_file = file("active_games_file", DEFAULT_ACTIVE_GAMES_FILE);
+        let board_history_file = file("board_history_file", DEFAULT_BOARD_HISTORY_FILE);
+        let daily_puzzles_file = file("daily_puzzles_file", DEFAULT_DAILY_PUZZLES_FILE);
+        let test_guilds = ids(config, "test_guilds", &mut problems).into_iter().map(GuildId).collect::<Vec<GuildId>>();
+        // Global commands can take a while to show up everywhere, so while there are test guilds they're left alone unless asked for.
+        let global_commands = or_default(config.get_bool("global_commands"), "global_commands", test_guilds.is_empty(), &mut problems);
+        let metrics_address = or_default(config.get_string("metrics_address").map(Some), "metrics_address", None, &mut problems)
+            .filter(|address| !address.is_empty());
+        let shutdown_notify_players = or_default(config.get_bool("shutdown_notify_players"), "shutdown_notify_players", true, &mut problems);
+        let mut seconds = |key: &str, default: i64| {
+            let seconds = or_default(config.get_int(key), key, default, &mut problems);
+            if seconds < 0 {
+                problems.push(format!("{} can't be negative", key));
+            }
+            Duration::from_secs(seconds.max(0) as u64)
+        };
+        let shutdown_drain_idle = seconds("shutdown_drain_idle_seconds", DEFAULT_DRAIN_IDLE_SECONDS);
+        let shutdown_drain_timeout = seconds("shutdown_drain_timeout_seconds", DEFAULT_DRAIN_TIMEOUT_SECONDS);
+        let logging = LogSettings::from_config(config, &mut problems);
+        if !problems.is_empty() {
+            return Err(problems)
+        }
+        Ok(Settings {
+            token,
+            admins,
+            daily_channels,
+            daily_hour,
+            preferences_file,
+            guild_config_file,
+            active_games_file,
+            board_history_file,
+            daily_puzzles_file,
+            test_guilds,
+            global_commands,
+            metrics_address,
+            shutdown_notify_players,
+            shutdown_drain_idle,
+            shutdown_drain_timeout,
+            logging,
+        })
+    }
+
+    // The names of every setting that's different in `other`, for telling whoever reloaded what happened.
+    fn changes(&self, other: &Settings) -> Vec<&'static str> {
+        [
+            ("token", self.token != other.token),
+            ("admins", self.admins != other.admins),
+            ("daily_channels", self.daily_channels != other.daily_channels),
+            ("daily_hour", self.daily_hour != other.daily_hour),
+            ("preferences_file", self.preferences_file != other.preferences_file),
+            ("guild_config_file", self.guild_config_file != other.guild_config_file),
+            ("active_games_file", self.active_games_file != other.active_games_file),
+            ("board_history_file", self.board_history_file != other.board_history_file),
+            ("daily_puzzles_file", self.daily_puzzles_file != other.daily_puzzles_file),
+            ("test_guilds", self.test_guilds != other.test_guilds),
+            ("global_commands", self.global_commands != other.global_commands),
+            ("metrics_address", self.metrics_address != other.metrics_address),
+            ("shutdown_notify_players", self.shutdown_notify_players != other.shutdown_notify_players),
+            ("shutdown_drain_idle_seconds", self.shutdown_drain_idle != other.shutdown_drain_idle),
+            ("shutdown_drain_timeout_seconds", self.shutdown_drain_timeout != other.shutdown_drain_timeout),
+            ("logging", self.logging != other.logging),
+        ].into_iter().filter(|(_, changed)| *changed).map(|(name, _)| name).collect()
+    }
+}
+
+// These are only read once, so changing them does nothing until the bot restarts.
+const RESTART_ONLY: [&str; 4] = ["token", "metrics_address", "test_guilds", "global_commands"];
+
+// Settings that are missing get their default, but settings that are there and wrong are a problem.
+pub fn or_default<T>(result: Result<T, ConfigError>, key: &str, default: T, problems: &mut Vec<String>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(ConfigError::NotFound(_)) => default,
+        Err(why) => {
+            problems.push(format!("{}: {}", key, why));
+            default
+        }
+    }
+}
+
+// Lists of user, channel or guild IDs.
+fn ids(config: &Config, key: &str, problems: &mut Vec<String>) -> Vec<u64> {
+    or_default(config.get_array(key), key, vec![], problems).into_iter()
+        .filter_map(|val| val.into_uint().map_err(|_| problems.push(format!("every entry in {} should be an ID", key))).ok())
+        .collect()
+}
+
+// Called once at startup, before anything else. A bad config stops the bot here, since there's nothing to fall back on yet.
+pub fn load_settings() -> Result<Arc<Settings>, String> {
+    let config = build_config().map_err(|why| why.to_string())?;
+    let settings = Arc::new(Settings::from_config(&config).map_err(|problems| problems.join("\n"))?);
+    logging::init_logging(&settings.logging);
+    if settings.admins.is_empty() {
+        warn!("No admin users specified! Nobody will be able to shut down your bot or use other admin-only commands.");
+    }
+    *SETTINGS.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(settings.clone());
+    Ok(settings)
+}
+
+// What reload_settings did. These get shown to whoever asked, so they're written for them.
+#[derive(Debug, PartialEq)]
+pub enum Reloaded {
+    Unchanged,
+    // The names of the settings that changed.
+    Changed(Vec<&'static str>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReloadError {
+    // Everything wrong with it, straight from the config crate or from_config.
+    BadConfig(String),
+    NoAdmins,
+}
+
+impl Reloaded {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            Reloaded::Unchanged => tr(locale, "reload.unchanged").to_string(),
+            Reloaded::Changed(changes) => {
+                let restart_only = changes.iter().filter(|name| RESTART_ONLY.contains(name)).copied().collect::<Vec<&str>>();
+                let mut output = trf(locale, "reload.changed", &[&changes.join(", ")]);
+                if !restart_only.is_empty() {
+                    output.push_str(&trf(locale, "reload.restart_only", &[&restart_only.join(", ")]));
+                }
+                output
+            },
+        }
+    }
+}
+
+impl ReloadError {
+    pub fn message(&self, locale: Locale) -> String {
+        match self {
+            ReloadError::BadConfig(problems) => trf(locale, "reload.bad_config", &[problems]),
+            ReloadError::NoAdmins => tr(locale, "reload.no_admins").to_string(),
+        }
+    }
+}
+
+// Reads the config file again and swaps it in, as long as nothing's wrong with it. Otherwise the old settings stay.
+pub fn reload_settings() -> Result<Reloaded, ReloadError> {
+    let config = build_config().map_err(|why| ReloadError::BadConfig(why.to_string()))?;
+    let new = Settings::from_config(&config).map_err(|problems| ReloadError::BadConfig(problems.join("\n")))?;
+    // Otherwise the only way to fix it would be to restart.
+    if new.admins.is_empty() {
+        return Err(ReloadError::NoAdmins)
+    }
+    let mut current = SETTINGS.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let changes = current.as_ref().map_or(vec![], |current| current.changes(&new));
+    if changes.is_empty() {
+        return Ok(Reloaded::Unchanged)
+    }
+    if changes.contains(&"logging") {
+        logging::init_logging(&new.logging);
+    }
+    *current = Some(Arc::new(new));
+    drop(current);
+    info!("Reloaded config, changed {}.", changes.join(", "));
+    Ok(Reloaded::Changed(changes))
+}
+
+// Reloads whenever the config file changes, or on SIGHUP. Nothing is waiting on this, it just runs until the bot stops.
+pub fn watch_settings() {
+    tokio::spawn(async {
+        let mut sighup = listen_for_sighup();
+        let mut last_modified = modified_times();
+        loop {
+            let signaled = tokio::select! {
+                _ = tokio::time::sleep(WATCH_INTERVAL) => false,
+                _ = next_sighup(&mut sighup) => true,
+            };
+            let modified = modified_times();
+            if modified == last_modified && !signaled {
+                continue
+            }
+            last_modified = modified;
+            match reload_settings() {
+                Ok(result) => info!("{}", result.message(Locale::English)),
+                Err(why) => error!("{}", why.message(Locale::English)),
+            }
+        }
+    });
+}
+
+fn modified_times() -> Vec<Option<SystemTime>> {
+    CONFIG_FILES.iter().map(|path| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()).collect()
+}
+
+#[cfg(unix)]
+type Sighup = Option<tokio::signal::unix::Signal>;
+
+// Anywhere else, there's no SIGHUP, so only changes to the file get picked up.
+#[cfg(not(unix))]
+type Sighup = ();
+
+#[cfg(unix)]
+fn listen_for_sighup() -> Sighup {
+    use tokio::signal::unix::{signal, SignalKind};
+    signal(SignalKind::hangup())
+        .map_err(|why| error!("Failed to listen for SIGHUP, the config will only reload when the file changes: {}", why))
+        .ok()
+}
+
+#[cfg(not(unix))]
+fn listen_for_sighup() -> Sighup {}
+
+// Never finishes if there's no SIGHUP to wait for.
+#[cfg(unix)]
+async fn next_sighup(sighup: &mut Sighup) {
+    if let Some(sighup) = sighup {
+        if sighup.recv().await.is_some() {
+            return
+        }
+    }
+    std::future::pending().await
+}
+
+#[cfg(not(unix))]
+async fn next_sighup(_: &mut Sighup) {
+    std::future::pending().await
+}
@@ -0,0 +1,212 @@
+use crate::metrics;
+use crate::settings::settings;
+use crate::minicact::saved_games::{active_game_count, notify_players, save_active_games};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use serenity::client::bridge::gateway::ShardManager;
+use serenity::http::Http;
+use serenity::prelude::*;
+
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::task::JoinHandle;
+
+use lazy_static::lazy_static;
+
+// Everything that happens between someone asking the bot to stop and the bot actually stopping.
+// /shutdown and SIGINT/SIGTERM both end up in handle_shutdown, which tells players, stops the shards, and saves every game in progress.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ShutdownMode {
+    // Stop right away. Games in progress are saved, so they can pick up where they left off once the bot is back.
+    Immediate,
+    // Stop starting new games, wait until nobody has used the bot for a bit, then stop.
+    Drain,
+    // Stop right away, then start back up in a new process.
+    Restart,
+}
+
+impl ShutdownMode {
+    pub const ALL: [ShutdownMode; 3] = [ShutdownMode::Immediate, ShutdownMode::Drain, ShutdownMode::Restart];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShutdownMode::Immediate => "immediate",
+            ShutdownMode::Drain => "drain",
+            ShutdownMode::Restart => "restart",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ShutdownMode> {
+        ShutdownMode::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+}
+
+// loosely based on https://stackoverflow.com/a/65456463
+lazy_static! { static ref SHUTDOWN_SENDER: Mutex<Option<Sender<ShutdownMode>>> = Mutex::new(None); }
+
+// Set once a drain starts, so nobody starts a game that would just get cut off.
+static DRAINING: AtomicBool = AtomicBool::new(false);
+
+// How often a drain checks whether everyone's games are finished.
+const DRAIN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+// When someone last used the bot, so a drain knows when it's quiet. std::sync since it's set from every interaction.
+lazy_static! { static ref LAST_ACTIVITY: std::sync::Mutex<Instant> = std::sync::Mutex::new(Instant::now()); }
+
+pub fn is_draining() -> bool {
+    DRAINING.load(Ordering::Relaxed)
+}
+
+pub fn record_activity() {
+    *LAST_ACTIVITY.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Instant::now();
+}
+
+pub async fn request_shutdown(mode: ShutdownMode) {
+    // keep the lock separate because you have to (which is neat)
+    let lock = SHUTDOWN_SENDER.lock().await;
+    let sender = match lock.as_ref() {
+        Some(sender) => sender,
+        None => {
+            error!("Shutdown ({}) requested before the shutdown handler started, ignoring it.", mode.name());
+            return
+        }
+    };
+    // The handler stops listening once it's done, so anything asked for after that is already taken care of.
+    if let Err(why) = sender.send(mode).await {
+        warn!("Shutdown ({}) requested after the shutdown handler finished, ignoring it: {}", mode.name(), why);
+        return
+    }
+    debug!("Passed shutdown message: {}", mode.name());
+}
+
+// Call before starting the client. The handle finishes once everything is saved, with whatever mode the bot ended up stopping in.
+pub async fn start_shutdown_handler(shard_manager: Arc<Mutex<ShardManager>>, http: Arc<Http>) -> JoinHandle<ShutdownMode> {
+    let (sender, receiver) = mpsc::channel(64);
+    *SHUTDOWN_SENDER.lock().await = Some(sender);
+    listen_for_signals();
+    tokio::spawn(handle_shutdown(receiver, shard_manager, http))
+}
+
+async fn handle_shutdown(mut receiver: Receiver<ShutdownMode>, shard_manager: Arc<Mutex<ShardManager>>, http: Arc<Http>) -> ShutdownMode {
+    // If this returns None, then something is wrong so we want to panic
+    let mut mode = receiver.recv().await.expect("Shutdown message pass error");
+    let start_time = Instant::now();
+    info!("Shutting down ({}).", mode.name());
+    if mode == ShutdownMode::Drain {
+        mode = drain(&mut receiver).await;
+    }
+    let (notified, not_notified) = if settings().shutdown_notify_players { notify_players(http, mode == ShutdownMode::Restart).await } else { (0, 0) };
+    metrics::set_ready(false);
+    shard_manager.lock().await.shutdown_all().await;
+    info!("Shutdown shard manager");
+    // Anyone still pressing buttons is done by now, since the shards are gone.
+    let saved = match save_active_games().await {
+        Ok(saved) => saved,
+        Err(why) => {
+            error!("Failed to save active games, they'll be lost: {}", why);
+            0
+        }
+    };
+    info!(
+        "Shut down ({}) in {:.2?}: saved {} games in progress, notified {} players and couldn't notify {}.",
+        mode.name(), start_time.elapsed(), saved, notified, not_notified
+    );
+    mode
+}
+
+// Returns once every game is finished, or nobody has touched theirs in a while, or with a different mode if someone asks for one in the meantime.
+async fn drain(receiver: &mut Receiver<ShutdownMode>) -> ShutdownMode {
+    DRAINING.store(true, Ordering::Relaxed);
+    let settings = settings();
+    let (idle, timeout) = (settings.shutdown_drain_idle, settings.shutdown_drain_timeout);
+    let deadline = Instant::now() + timeout;
+    loop {
+        if active_game_count().await == 0 {
+            info!("Nobody is playing anymore, finishing the shutdown.");
+            return ShutdownMode::Drain
+        }
+        // Whoever is left probably walked away from their game, and it's saved either way.
+        let idle_for = LAST_ACTIVITY.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).elapsed();
+        if idle_for >= idle {
+            info!("Nobody has used the bot for {:.0?}, finishing the shutdown.", idle_for);
+            return ShutdownMode::Drain
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            info!("Still not quiet after {:.0?}, shutting down anyways.", timeout);
+            return ShutdownMode::Drain
+        }
+        // Finishing a game doesn't tell anyone, so the games get checked every so often too.
+        let wait = (idle - idle_for).min(deadline - now).min(DRAIN_CHECK_INTERVAL);
+        tokio::select! {
+            Some(next) = receiver.recv() => if next != ShutdownMode::Drain {
+                info!("Switching from drain to {}.", next.name());
+                return next
+            },
+            _ = tokio::time::sleep(wait) => (),
+        }
+    }
+}
+
+// SIGINT (Ctrl+C) and SIGTERM shut down the same way as /shutdown. A second one exits right away, in case the first one gets stuck.
+#[cfg(unix)]
+fn listen_for_signals() {
+    use tokio::signal::unix::{signal, SignalKind};
+    let (mut terminate, mut interrupt) = match (signal(SignalKind::terminate()), signal(SignalKind::interrupt())) {
+        (Ok(terminate), Ok(interrupt)) => (terminate, interrupt),
+        (Err(why), _) | (_, Err(why)) => {
+            error!("Failed to listen for SIGINT and SIGTERM, only /shutdown will save games: {}", why);
+            return
+        }
+    };
+    tokio::spawn(async move {
+        let mut signaled_before = false;
+        loop {
+            tokio::select! {
+                Some(_) = terminate.recv() => (),
+                Some(_) = interrupt.recv() => (),
+                // Only happens if the runtime is going away, so there's nothing left to listen for.
+                else => return,
+            }
+            if signaled_before {
+                warn!("Got another signal, exiting without finishing the shutdown.");
+                std::process::exit(1);
+            }
+            signaled_before = true;
+            info!("Got a signal to stop.");
+            request_shutdown(ShutdownMode::Immediate).await;
+        }
+    });
+}
+
+// Anywhere else, Ctrl+C just stops the bot like it always has.
+#[cfg(not(unix))]
+fn listen_for_signals() {}
+
+// Starts the bot over in a new process with the same arguments. Only returns if that didn't work.
+pub fn restart() {
+    let executable = match std::env::current_exe() {
+        Ok(executable) => executable,
+        Err(why) => {
+            error!("Failed to restart, couldn't find the executable: {}", why);
+            return
+        }
+    };
+    let mut command = std::process::Command::new(executable);
+    command.args(std::env::args_os().skip(1));
+    info!("Restarting...");
+    // Replacing this process keeps the same PID, so anything watching it (like systemd) doesn't think the bot died.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let why = command.exec();
+        error!("Failed to restart: {}", why);
+    }
+    #[cfg(not(unix))]
+    if let Err(why) = command.spawn() {
+        error!("Failed to restart: {}", why);
+    }
+}
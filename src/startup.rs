@@ -1,5 +1,15 @@
+use super::guild_config::load_guild_configs;
+use super::metrics::start_metrics_server;
 use super::minicact;
+use super::registry::check_registry;
 
-pub async fn startup() {
-    minicact::startup().await;
+use std::sync::Arc;
+
+use serenity::http::Http;
+
+pub async fn startup(http: Arc<Http>) {
+    check_registry();
+    load_guild_configs().await;
+    start_metrics_server().await;
+    minicact::startup(http).await;
 }
\ No newline at end of file